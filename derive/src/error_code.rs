@@ -0,0 +1,637 @@
+use std::collections::BTreeMap;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+    Data, DeriveInput, Fields, LitInt, LitStr, Token, Variant,
+};
+
+/// Returns the `ErrorCode` implementation for the given enum.
+pub fn derive(derive_input: &DeriveInput) -> syn::Result<TokenStream> {
+    let data_enum = match &derive_input.data {
+        Data::Enum(data_enum) => data_enum,
+        Data::Struct(_) | Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                &derive_input.ident,
+                "`ErrorCode` can only be derived for enums.",
+            ));
+        }
+    };
+    if data_enum.variants.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &derive_input.ident,
+            "`ErrorCode` cannot be derived for an enum without variants.",
+        ));
+    }
+
+    let container_attrs = ContainerAttrs::parse(derive_input)?;
+    let variant_codes = data_enum
+        .variants
+        .iter()
+        .map(VariantCode::parse)
+        .collect::<syn::Result<Vec<_>>>()?;
+    let code_max = code_max(&container_attrs, &variant_codes)?;
+
+    let ident = &derive_input.ident;
+    let (impl_generics, ty_generics, where_clause) = derive_input.generics.split_for_impl();
+    let prefix = container_attrs.prefix.as_ref().map(|prefix| {
+        quote! {
+            const PREFIX: &'static str = #prefix;
+        }
+    });
+    let code_arms = variant_codes.iter().map(|variant_code| {
+        let variant = variant_code.ident;
+        let code = &variant_code.code;
+        quote!(Self::#variant => #code)
+    });
+    let description_arms = variant_codes.iter().map(|variant_code| {
+        let variant = variant_code.ident;
+        let description = &variant_code.description;
+        quote!(Self::#variant => #description)
+    });
+    let explanation = if variant_codes
+        .iter()
+        .any(|variant_code| variant_code.explanation.is_some())
+    {
+        let explanation_arms = variant_codes.iter().map(|variant_code| {
+            let variant = variant_code.ident;
+            match variant_code.explanation.as_ref() {
+                Some(explanation) => {
+                    quote!(Self::#variant => ::std::option::Option::Some(#explanation))
+                }
+                None => quote!(Self::#variant => ::std::option::Option::None),
+            }
+        });
+        Some(quote! {
+            fn explanation(self) -> ::std::option::Option<&'static str> {
+                match self {
+                    #(#explanation_arms,)*
+                }
+            }
+        })
+    } else {
+        None
+    };
+    let severity = if variant_codes
+        .iter()
+        .any(|variant_code| variant_code.severity.is_some())
+    {
+        let severity_arms = variant_codes.iter().map(|variant_code| {
+            let variant = variant_code.ident;
+            let severity = variant_code
+                .severity
+                .as_ref()
+                .map(|severity| quote!(#severity))
+                .unwrap_or_else(|| quote!(Error));
+            quote!(Self::#variant => ::srcerr::codespan_reporting::diagnostic::Severity::#severity)
+        });
+        Some(quote! {
+            fn severity(self) -> ::srcerr::codespan_reporting::diagnostic::Severity {
+                match self {
+                    #(#severity_arms,)*
+                }
+            }
+        })
+    } else {
+        None
+    };
+    let mut variants_sorted = variant_codes.iter().collect::<Vec<_>>();
+    variants_sorted.sort_by_key(|variant_code| variant_code.code_value);
+    let variants_sorted = variants_sorted
+        .into_iter()
+        .map(|variant_code| variant_code.ident);
+
+    Ok(quote! {
+        impl #impl_generics ::srcerr::ErrorCode for #ident #ty_generics #where_clause {
+            const ERROR_CODE_MAX: usize = #code_max;
+            #prefix
+
+            fn code(self) -> usize {
+                match self {
+                    #(#code_arms,)*
+                }
+            }
+
+            fn description(self) -> &'static str {
+                match self {
+                    #(#description_arms,)*
+                }
+            }
+
+            #explanation
+
+            #severity
+
+            fn all() -> &'static [Self]
+            where
+                Self: 'static,
+            {
+                &[#(Self::#variants_sorted),*]
+            }
+        }
+    })
+}
+
+/// Returns the `ERROR_CODE_MAX` value, ensuring codes are unique and non-zero.
+fn code_max(container_attrs: &ContainerAttrs, variant_codes: &[VariantCode]) -> syn::Result<usize> {
+    let mut errors = Vec::new();
+    let mut codes_seen = BTreeMap::<usize, &VariantCode>::new();
+    variant_codes.iter().for_each(|variant_code| {
+        let code_value = variant_code.code_value;
+        if code_value == 0 {
+            errors.push(syn::Error::new_spanned(
+                &variant_code.code,
+                "Error code must be non-zero, as codes are counted from `1`.",
+            ));
+        } else if let Some(variant_code_first) = codes_seen.get(&code_value) {
+            errors.push(syn::Error::new_spanned(
+                &variant_code.code,
+                format!(
+                    "Error code `{code_value}` is already used by `{variant}`.",
+                    variant = variant_code_first.ident
+                ),
+            ));
+        } else {
+            codes_seen.insert(code_value, variant_code);
+        }
+    });
+
+    let code_largest = codes_seen.keys().next_back().copied().unwrap_or(0);
+    if let Some(max) = container_attrs.max.as_ref() {
+        let max_value = max.base10_parse::<usize>()?;
+        if max_value < code_largest {
+            errors.push(syn::Error::new_spanned(
+                max,
+                format!("`max` must not be less than the largest error code: `{code_largest}`."),
+            ));
+        }
+
+        combine(errors).map(|()| max_value)
+    } else {
+        combine(errors).map(|()| code_largest)
+    }
+}
+
+/// Combines the errors into one, returning `Ok(())` if there are none.
+fn combine(errors: Vec<syn::Error>) -> syn::Result<()> {
+    let mut errors = errors.into_iter();
+    match errors.next() {
+        Some(mut error_combined) => {
+            errors.for_each(|error| error_combined.combine(error));
+            Err(error_combined)
+        }
+        None => Ok(()),
+    }
+}
+
+/// Attributes on the enum, `#[error_code(prefix = "E", max = 999)]`.
+#[derive(Default)]
+struct ContainerAttrs {
+    /// Prefix of the error code.
+    prefix: Option<LitStr>,
+    /// Explicit `ERROR_CODE_MAX`.
+    max: Option<LitInt>,
+}
+
+impl ContainerAttrs {
+    fn parse(derive_input: &DeriveInput) -> syn::Result<Self> {
+        let mut container_attrs = ContainerAttrs::default();
+        derive_input
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("error_code"))
+            .try_for_each(|attr| {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("prefix") {
+                        container_attrs.prefix = Some(meta.value()?.parse()?);
+                        Ok(())
+                    } else if meta.path.is_ident("max") {
+                        container_attrs.max = Some(meta.value()?.parse()?);
+                        Ok(())
+                    } else {
+                        Err(meta.error("Expected one of: `prefix`, `max`."))
+                    }
+                })
+            })?;
+
+        Ok(container_attrs)
+    }
+}
+
+/// Code and description of a variant, `#[code(3, "Value out of range.")]`.
+struct VariantCode<'v> {
+    /// Name of the variant.
+    ident: &'v syn::Ident,
+    /// The error code literal.
+    code: LitInt,
+    /// The parsed error code.
+    code_value: usize,
+    /// Short description of the error.
+    description: LitStr,
+    /// Expression for the extended explanation, from `#[explanation(..)]`.
+    explanation: Option<TokenStream>,
+    /// Default severity of the error, from `#[severity(..)]`.
+    severity: Option<syn::Ident>,
+}
+
+impl<'v> VariantCode<'v> {
+    fn parse(variant: &'v Variant) -> syn::Result<Self> {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new(
+                variant.fields.span(),
+                "`ErrorCode` variants must not have fields.",
+            ));
+        }
+
+        let mut attrs = variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("code"));
+        let attr = attrs.next().ok_or_else(|| {
+            syn::Error::new_spanned(
+                &variant.ident,
+                "Missing `#[code(n, \"description\")]` attribute.",
+            )
+        })?;
+        if let Some(attr_extra) = attrs.next() {
+            return Err(syn::Error::new_spanned(
+                attr_extra,
+                "Duplicate `#[code(..)]` attribute.",
+            ));
+        }
+
+        let CodeArgs { code, description } = attr.parse_args::<CodeArgs>()?;
+        let code_value = code.base10_parse::<usize>()?;
+        let explanation = Explanation::parse(variant)?;
+        let severity = Self::severity_parse(variant)?;
+
+        Ok(VariantCode {
+            ident: &variant.ident,
+            code,
+            code_value,
+            description,
+            explanation,
+            severity,
+        })
+    }
+
+    /// Returns the severity from `#[severity(Warning)]`, if the variant has one.
+    fn severity_parse(variant: &Variant) -> syn::Result<Option<syn::Ident>> {
+        let mut attrs = variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("severity"));
+        let attr = match attrs.next() {
+            Some(attr) => attr,
+            None => return Ok(None),
+        };
+        if let Some(attr_extra) = attrs.next() {
+            return Err(syn::Error::new_spanned(
+                attr_extra,
+                "Duplicate `#[severity(..)]` attribute.",
+            ));
+        }
+
+        let severity = attr.parse_args::<syn::Ident>()?;
+        if !SEVERITIES.iter().any(|variant| severity == variant) {
+            return Err(syn::Error::new_spanned(
+                severity,
+                "Expected one of `Bug`, `Error`, `Warning`, `Note`, or `Help`.",
+            ));
+        }
+
+        Ok(Some(severity))
+    }
+}
+
+/// Variants of `codespan_reporting::diagnostic::Severity`.
+const SEVERITIES: &[&str] = &["Bug", "Error", "Warning", "Note", "Help"];
+
+/// Arguments within `#[code(..)]`.
+struct CodeArgs {
+    code: LitInt,
+    description: LitStr,
+}
+
+impl Parse for CodeArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let code = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let description = input.parse()?;
+        if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+        }
+
+        Ok(CodeArgs { code, description })
+    }
+}
+
+/// Extended explanation of a variant.
+///
+/// * `#[explanation("markdown")]`: The explanation inline.
+/// * `#[explanation(file = "path.md")]`: The explanation in a file, relative to
+///   the crate's `Cargo.toml`.
+enum Explanation {
+    Inline(LitStr),
+    File(LitStr),
+}
+
+impl Explanation {
+    /// Returns the explanation expression, if the variant has one.
+    fn parse(variant: &Variant) -> syn::Result<Option<TokenStream>> {
+        let mut attrs = variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("explanation"));
+        let attr = match attrs.next() {
+            Some(attr) => attr,
+            None => return Ok(None),
+        };
+        if let Some(attr_extra) = attrs.next() {
+            return Err(syn::Error::new_spanned(
+                attr_extra,
+                "Duplicate `#[explanation(..)]` attribute.",
+            ));
+        }
+
+        let explanation = match attr.parse_args::<Explanation>()? {
+            Explanation::Inline(markdown) => quote!(#markdown),
+            Explanation::File(path) => quote! {
+                ::std::include_str!(::std::concat!(
+                    ::std::env!("CARGO_MANIFEST_DIR"),
+                    "/",
+                    #path
+                ))
+            },
+        };
+
+        Ok(Some(explanation))
+    }
+}
+
+impl Parse for Explanation {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(LitStr) {
+            return input.parse().map(Explanation::Inline);
+        }
+
+        let ident = input.parse::<syn::Ident>()?;
+        if ident != "file" {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "Expected `#[explanation(\"markdown\")]` or `#[explanation(file = \"path.md\")]`.",
+            ));
+        }
+        input.parse::<Token![=]>()?;
+        input.parse().map(Explanation::File)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{parse_quote, DeriveInput};
+
+    use super::derive;
+
+    #[test]
+    fn computes_error_code_max_from_largest_code() {
+        let derive_input: DeriveInput = parse_quote! {
+            enum Ec {
+                #[code(1, "one")]
+                One,
+                #[code(12, "twelve")]
+                Twelve,
+            }
+        };
+
+        let tokens = derive(&derive_input).expect("Expected derive to succeed.");
+
+        assert!(tokens
+            .to_string()
+            .contains("const ERROR_CODE_MAX : usize = 12usize"));
+    }
+
+    #[test]
+    fn uses_explicit_max_when_specified() {
+        let derive_input: DeriveInput = parse_quote! {
+            #[error_code(prefix = "CFG", max = 999)]
+            enum Ec {
+                #[code(1, "one")]
+                One,
+            }
+        };
+
+        let tokens = derive(&derive_input)
+            .expect("Expected derive to succeed.")
+            .to_string();
+
+        assert!(tokens.contains("const ERROR_CODE_MAX : usize = 999usize"));
+        assert!(tokens.contains("const PREFIX : & 'static str = \"CFG\""));
+    }
+
+    #[test]
+    fn rejects_duplicate_codes() {
+        let derive_input: DeriveInput = parse_quote! {
+            enum Ec {
+                #[code(1, "one")]
+                One,
+                #[code(1, "uno")]
+                Uno,
+            }
+        };
+
+        let error = derive(&derive_input).expect_err("Expected duplicate codes to be rejected.");
+
+        assert_eq!(
+            "Error code `1` is already used by `One`.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn rejects_zero_code() {
+        let derive_input: DeriveInput = parse_quote! {
+            enum Ec {
+                #[code(0, "zero")]
+                Zero,
+            }
+        };
+
+        let error = derive(&derive_input).expect_err("Expected zero code to be rejected.");
+
+        assert_eq!(
+            "Error code must be non-zero, as codes are counted from `1`.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn rejects_max_less_than_largest_code() {
+        let derive_input: DeriveInput = parse_quote! {
+            #[error_code(max = 2)]
+            enum Ec {
+                #[code(3, "three")]
+                Three,
+            }
+        };
+
+        let error = derive(&derive_input).expect_err("Expected small `max` to be rejected.");
+
+        assert_eq!(
+            "`max` must not be less than the largest error code: `3`.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn rejects_variant_without_code_attribute() {
+        let derive_input: DeriveInput = parse_quote! {
+            enum Ec {
+                One,
+            }
+        };
+
+        let error = derive(&derive_input).expect_err("Expected missing `#[code]` to be rejected.");
+
+        assert_eq!(
+            "Missing `#[code(n, \"description\")]` attribute.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn rejects_variant_with_fields() {
+        let derive_input: DeriveInput = parse_quote! {
+            enum Ec {
+                #[code(1, "one")]
+                One(u32),
+            }
+        };
+
+        let error = derive(&derive_input).expect_err("Expected variant fields to be rejected.");
+
+        assert_eq!(
+            "`ErrorCode` variants must not have fields.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn generates_all_in_code_order() {
+        let derive_input: DeriveInput = parse_quote! {
+            enum Ec {
+                #[code(2, "two")]
+                Two,
+                #[code(1, "one")]
+                One,
+            }
+        };
+
+        let tokens = derive(&derive_input)
+            .expect("Expected derive to succeed.")
+            .to_string();
+
+        assert!(tokens.contains("& [Self :: One , Self :: Two]"));
+        assert!(!tokens.contains("fn explanation"));
+    }
+
+    #[test]
+    fn generates_explanation_from_inline_and_file() {
+        let derive_input: DeriveInput = parse_quote! {
+            enum Ec {
+                #[code(1, "one")]
+                #[explanation("# One")]
+                One,
+                #[code(2, "two")]
+                #[explanation(file = "explanations/E2.md")]
+                Two,
+                #[code(3, "three")]
+                Three,
+            }
+        };
+
+        let tokens = derive(&derive_input)
+            .expect("Expected derive to succeed.")
+            .to_string();
+
+        assert!(tokens.contains("Self :: One => :: std :: option :: Option :: Some (\"# One\")"));
+        assert!(tokens.contains("\"explanations/E2.md\""));
+        assert!(tokens.contains("Self :: Three => :: std :: option :: Option :: None"));
+    }
+
+    #[test]
+    fn rejects_unknown_explanation_key() {
+        let derive_input: DeriveInput = parse_quote! {
+            enum Ec {
+                #[code(1, "one")]
+                #[explanation(path = "E1.md")]
+                One,
+            }
+        };
+
+        let error = derive(&derive_input).expect_err("Expected unknown key to be rejected.");
+
+        assert_eq!(
+            "Expected `#[explanation(\"markdown\")]` or `#[explanation(file = \"path.md\")]`.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn generates_severity_defaulting_to_error() {
+        let derive_input: DeriveInput = parse_quote! {
+            enum Ec {
+                #[code(1, "one")]
+                #[severity(Warning)]
+                One,
+                #[code(2, "two")]
+                Two,
+            }
+        };
+
+        let tokens = derive(&derive_input)
+            .expect("Expected derive to succeed.")
+            .to_string();
+
+        assert!(tokens.contains(
+            "Self :: One => :: srcerr :: codespan_reporting :: diagnostic :: Severity :: Warning"
+        ));
+        assert!(tokens.contains(
+            "Self :: Two => :: srcerr :: codespan_reporting :: diagnostic :: Severity :: Error"
+        ));
+    }
+
+    #[test]
+    fn rejects_unknown_severity() {
+        let derive_input: DeriveInput = parse_quote! {
+            enum Ec {
+                #[code(1, "one")]
+                #[severity(Fatal)]
+                One,
+            }
+        };
+
+        let error = derive(&derive_input).expect_err("Expected unknown severity to be rejected.");
+
+        assert_eq!(
+            "Expected one of `Bug`, `Error`, `Warning`, `Note`, or `Help`.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn rejects_structs() {
+        let derive_input: DeriveInput = parse_quote! {
+            struct Ec;
+        };
+
+        let error = derive(&derive_input).expect_err("Expected structs to be rejected.");
+
+        assert_eq!(
+            "`ErrorCode` can only be derived for enums.",
+            error.to_string()
+        );
+    }
+}
@@ -0,0 +1,403 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse_quote, spanned::Spanned, Attribute, Data, DeriveInput, Field, Fields, Ident, LitStr, Type,
+};
+
+/// Returns the `ErrorDetail` implementation for the given struct or enum.
+pub fn derive(derive_input: &DeriveInput) -> syn::Result<TokenStream> {
+    let files = container_files(derive_input)?;
+    let (labels_body, notes_body) = match &derive_input.data {
+        Data::Struct(data_struct) => {
+            let mut notes = Notes::parse(&derive_input.attrs)?;
+            let fields_detail = FieldsDetail::parse(&data_struct.fields, &mut notes)?;
+            let pattern = fields_detail.pattern(quote!(Self));
+            let labels = fields_detail.labels();
+            let notes = notes.0;
+
+            let labels_body = quote! {
+                #[allow(unused_variables)]
+                let #pattern = self;
+                vec![#(#labels),*]
+            };
+            let notes_body = quote! {
+                #[allow(unused_variables)]
+                let #pattern = self;
+                vec![#(#notes),*]
+            };
+            (labels_body, notes_body)
+        }
+        Data::Enum(data_enum) => {
+            if let Some(attr) = derive_input
+                .attrs
+                .iter()
+                .find(|attr| attr.path().is_ident("note"))
+            {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "`#[note(..)]` must be placed on enum variants instead of the enum.",
+                ));
+            }
+
+            let (label_arms, note_arms) = data_enum
+                .variants
+                .iter()
+                .map(|variant| {
+                    let mut notes = Notes::parse(&variant.attrs)?;
+                    let fields_detail = FieldsDetail::parse(&variant.fields, &mut notes)?;
+                    let variant_ident = &variant.ident;
+                    let pattern = fields_detail.pattern(quote!(Self::#variant_ident));
+                    let labels = fields_detail.labels();
+                    let notes = notes.0;
+
+                    let label_arm = quote! {
+                        #[allow(unused_variables)]
+                        #pattern => vec![#(#labels),*]
+                    };
+                    let note_arm = quote! {
+                        #[allow(unused_variables)]
+                        #pattern => vec![#(#notes),*]
+                    };
+                    Ok((label_arm, note_arm))
+                })
+                .collect::<syn::Result<(Vec<_>, Vec<_>)>>()?;
+
+            if data_enum.variants.is_empty() {
+                (quote!(match *self {}), quote!(match *self {}))
+            } else {
+                let labels_body = quote! {
+                    match self {
+                        #(#label_arms,)*
+                    }
+                };
+                let notes_body = quote! {
+                    match self {
+                        #(#note_arms,)*
+                    }
+                };
+                (labels_body, notes_body)
+            }
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                &derive_input.ident,
+                "`ErrorDetail` can only be derived for structs and enums.",
+            ));
+        }
+    };
+
+    let ident = &derive_input.ident;
+    let mut generics = derive_input.generics.clone();
+    generics.params.insert(0, parse_quote!('files));
+    let (impl_generics, _, _) = generics.split_for_impl();
+    let (_, ty_generics, where_clause) = derive_input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::srcerr::ErrorDetail<'files> for #ident #ty_generics #where_clause {
+            type Files = #files;
+
+            fn labels(
+                &self,
+            ) -> ::std::vec::Vec<
+                ::srcerr::model::Label<
+                    <Self::Files as ::srcerr::codespan_reporting::files::Files<'files>>::FileId,
+                >,
+            > {
+                #labels_body
+            }
+
+            fn notes(&self, _files: &Self::Files) -> ::std::vec::Vec<::std::string::String> {
+                #notes_body
+            }
+        }
+    })
+}
+
+/// Returns the `Files` type from `#[error_detail(files = ..)]`.
+fn container_files(derive_input: &DeriveInput) -> syn::Result<Type> {
+    let mut files = None;
+    derive_input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("error_detail"))
+        .try_for_each(|attr| {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("files") {
+                    files = Some(meta.value()?.parse::<Type>()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("Expected `files`."))
+                }
+            })
+        })?;
+
+    files.ok_or_else(|| {
+        syn::Error::new_spanned(
+            &derive_input.ident,
+            "Missing `#[error_detail(files = ..)]` attribute.",
+        )
+    })
+}
+
+/// `format!` expressions for each `#[note("..")]`.
+struct Notes(Vec<TokenStream>);
+
+impl Notes {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut notes = Notes(Vec::new());
+        notes.extend(attrs)?;
+        Ok(notes)
+    }
+
+    fn extend(&mut self, attrs: &[Attribute]) -> syn::Result<()> {
+        attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("note"))
+            .try_for_each(|attr| {
+                let message = attr.parse_args::<LitStr>()?;
+                self.0.push(quote!(::std::format!(#message)));
+                Ok(())
+            })
+    }
+}
+
+/// Whether a label is the primary or secondary label.
+#[derive(Clone, Copy)]
+enum LabelStyle {
+    Primary,
+    Secondary,
+}
+
+/// A field annotated with `#[primary]` or `#[secondary]`.
+struct FieldLabel<'f> {
+    /// Name of the field holding the byte range.
+    ident: &'f Ident,
+    /// Primary or secondary.
+    style: LabelStyle,
+    /// Message of the label.
+    message: Option<LitStr>,
+}
+
+/// Fields of a struct or enum variant.
+struct FieldsDetail<'f> {
+    /// Names of all fields.
+    idents: Vec<&'f Ident>,
+    /// The `#[file_id]` field.
+    file_id: Option<&'f Ident>,
+    /// Fields that are rendered as labels.
+    labels: Vec<FieldLabel<'f>>,
+}
+
+impl<'f> FieldsDetail<'f> {
+    fn parse(fields: &'f Fields, notes: &mut Notes) -> syn::Result<Self> {
+        let fields_named = match fields {
+            Fields::Named(fields_named) => Some(fields_named),
+            Fields::Unit => None,
+            Fields::Unnamed(_) => {
+                return Err(syn::Error::new(
+                    fields.span(),
+                    "`ErrorDetail` can only be derived for named fields.",
+                ));
+            }
+        };
+
+        let mut fields_detail = FieldsDetail {
+            idents: Vec::new(),
+            file_id: None,
+            labels: Vec::new(),
+        };
+        fields_named
+            .into_iter()
+            .flat_map(|fields_named| fields_named.named.iter())
+            .try_for_each(|field| fields_detail.parse_field(field, notes))?;
+
+        if fields_detail.file_id.is_none() && !fields_detail.labels.is_empty() {
+            return Err(syn::Error::new(
+                fields.span(),
+                "Missing `#[file_id]` field, which is required for `#[primary]` and \
+                 `#[secondary]` labels.",
+            ));
+        }
+
+        Ok(fields_detail)
+    }
+
+    fn parse_field(&mut self, field: &'f Field, notes: &mut Notes) -> syn::Result<()> {
+        let ident = field
+            .ident
+            .as_ref()
+            .expect("Expected named fields to have an ident.");
+        self.idents.push(ident);
+
+        field.attrs.iter().try_for_each(|attr| {
+            let style = if attr.path().is_ident("file_id") {
+                attr.meta.require_path_only()?;
+                if self.file_id.replace(ident).is_some() {
+                    return Err(syn::Error::new_spanned(
+                        attr,
+                        "Only one field may be marked with `#[file_id]`.",
+                    ));
+                }
+                return Ok(());
+            } else if attr.path().is_ident("primary") {
+                LabelStyle::Primary
+            } else if attr.path().is_ident("secondary") {
+                LabelStyle::Secondary
+            } else {
+                return Ok(());
+            };
+
+            let message = match &attr.meta {
+                syn::Meta::Path(_) => None,
+                syn::Meta::List(_) => Some(attr.parse_args::<LitStr>()?),
+                syn::Meta::NameValue(_) => {
+                    return Err(syn::Error::new_spanned(
+                        attr,
+                        "Expected `#[primary]` or `#[primary(\"message\")]`.",
+                    ));
+                }
+            };
+            self.labels.push(FieldLabel {
+                ident,
+                style,
+                message,
+            });
+
+            Ok(())
+        })?;
+
+        notes.extend(&field.attrs)
+    }
+
+    /// Returns the pattern that binds every field by reference.
+    fn pattern(&self, path: TokenStream) -> TokenStream {
+        let idents = &self.idents;
+        quote!(#path { #(#idents),* })
+    }
+
+    /// Returns the `Label` expressions for each labelled field.
+    fn labels(&self) -> Vec<TokenStream> {
+        let file_id = self.file_id;
+        self.labels
+            .iter()
+            .map(|field_label| {
+                let FieldLabel {
+                    ident,
+                    style,
+                    message,
+                } = field_label;
+                let constructor = match style {
+                    LabelStyle::Primary => quote!(primary),
+                    LabelStyle::Secondary => quote!(secondary),
+                };
+                let with_message = message
+                    .as_ref()
+                    .map(|message| quote!(.with_message(::std::format!(#message))));
+
+                quote! {
+                    ::srcerr::model::Label::#constructor(
+                        ::std::clone::Clone::clone(#file_id),
+                        ::std::clone::Clone::clone(#ident),
+                    )
+                    #with_message
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::{parse_quote, DeriveInput};
+
+    use super::derive;
+
+    #[test]
+    fn rejects_missing_files_attribute() {
+        let derive_input: DeriveInput = parse_quote! {
+            struct Ed {
+                #[file_id]
+                file_id: usize,
+            }
+        };
+
+        let error = derive(&derive_input).expect_err("Expected missing `files` to be rejected.");
+
+        assert_eq!(
+            "Missing `#[error_detail(files = ..)]` attribute.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn rejects_labels_without_file_id() {
+        let derive_input: DeriveInput = parse_quote! {
+            #[error_detail(files = SimpleFiles<&'files str, &'files str>)]
+            struct Ed {
+                #[primary("here")]
+                value_byte_indices: Range<usize>,
+            }
+        };
+
+        let error = derive(&derive_input).expect_err("Expected missing `file_id` to be rejected.");
+
+        assert_eq!(
+            "Missing `#[file_id]` field, which is required for `#[primary]` and `#[secondary]` \
+             labels.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn rejects_multiple_file_ids() {
+        let derive_input: DeriveInput = parse_quote! {
+            #[error_detail(files = SimpleFiles<&'files str, &'files str>)]
+            struct Ed {
+                #[file_id]
+                file_id: usize,
+                #[file_id]
+                file_id_other: usize,
+            }
+        };
+
+        let error = derive(&derive_input).expect_err("Expected two `file_id`s to be rejected.");
+
+        assert_eq!(
+            "Only one field may be marked with `#[file_id]`.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn rejects_tuple_fields() {
+        let derive_input: DeriveInput = parse_quote! {
+            #[error_detail(files = SimpleFiles<&'files str, &'files str>)]
+            struct Ed(usize);
+        };
+
+        let error = derive(&derive_input).expect_err("Expected tuple fields to be rejected.");
+
+        assert_eq!(
+            "`ErrorDetail` can only be derived for named fields.",
+            error.to_string()
+        );
+    }
+
+    #[test]
+    fn rejects_note_on_enum() {
+        let derive_input: DeriveInput = parse_quote! {
+            #[error_detail(files = SimpleFiles<&'files str, &'files str>)]
+            #[note("note")]
+            enum Ed {
+                One,
+            }
+        };
+
+        let error = derive(&derive_input).expect_err("Expected enum `#[note]` to be rejected.");
+
+        assert_eq!(
+            "`#[note(..)]` must be placed on enum variants instead of the enum.",
+            error.to_string()
+        );
+    }
+}
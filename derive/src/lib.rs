@@ -0,0 +1,147 @@
+#![deny(missing_docs, missing_debug_implementations)]
+
+//! Derive macros for the [`srcerr`] crate.
+//!
+//! These are re-exported from `srcerr` when the `"derive"` feature is
+//! enabled, and should be used through that crate.
+//!
+//! [`srcerr`]: https://docs.rs/srcerr
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+mod error_code;
+mod error_detail;
+
+/// Derives `srcerr::ErrorCode` for a flat enum.
+///
+/// Each variant must be a unit variant annotated with `#[code(n,
+/// "description")]`, where `n` is a unique, non-zero error code.
+/// `ERROR_CODE_MAX` is computed from the largest code.
+///
+/// # Attributes
+///
+/// * `#[error_code(prefix = "E")]`: Prefix of the error code, defaults to
+///   `"E"`.
+/// * `#[error_code(max = 999)]`: Overrides the computed `ERROR_CODE_MAX`, e.g.
+///   to reserve room for future codes. Must not be less than the largest code.
+/// * `#[code(3, "Value out of range.")]`: Code and description of a variant.
+/// * `#[explanation("markdown")]`: Extended explanation of a variant, returned
+///   by `ErrorCode::explanation`. Optional.
+/// * `#[explanation(file = "explanations/E003.md")]`: Extended explanation read
+///   from a file, relative to the crate's `Cargo.toml`. Optional.
+/// * `#[severity(Warning)]`: Default severity of a variant, returned by
+///   `ErrorCode::severity`. Optional, defaults to `Error`.
+///
+/// `ErrorCode::all` returns every variant in ascending order of code.
+///
+/// # Examples
+///
+/// ```rust
+/// use srcerr::ErrorCode;
+///
+/// #[derive(Clone, Copy, Debug, PartialEq, Eq, ErrorCode)]
+/// #[error_code(prefix = "CFG")]
+/// pub enum ConfigErrorCode {
+///     /// Error when a value is out of range.
+///     #[code(1, "Value out of range.")]
+///     ValueOutOfRange,
+///     /// Error when a string is too long.
+///     #[code(2, "String provided is too long.")]
+///     #[explanation("The string must be at most 32 characters long.")]
+///     StringTooLong,
+/// }
+///
+/// assert_eq!(2, ConfigErrorCode::ERROR_CODE_MAX);
+/// assert_eq!("CFG", ConfigErrorCode::PREFIX);
+/// assert_eq!(1, ConfigErrorCode::ValueOutOfRange.code());
+/// assert_eq!(
+///     &[
+///         ConfigErrorCode::ValueOutOfRange,
+///         ConfigErrorCode::StringTooLong
+///     ],
+///     ConfigErrorCode::all()
+/// );
+/// assert_eq!(None, ConfigErrorCode::ValueOutOfRange.explanation());
+/// ```
+#[proc_macro_derive(ErrorCode, attributes(error_code, code, explanation, severity))]
+pub fn error_code_derive(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+
+    error_code::derive(&derive_input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `srcerr::ErrorDetail` for a struct or enum with named fields.
+///
+/// Labels and notes are generated from attributes on the fields. Message
+/// strings are passed to `format!`, so fields may be referenced by name, e.g.
+/// `{range:?}`.
+///
+/// # Attributes
+///
+/// * `#[error_detail(files = Type)]`: The `ErrorDetail::Files` type, which may
+///   use the `'files` lifetime. Required.
+/// * `#[file_id]`: Field holding the file ID that labels refer to.
+/// * `#[primary("message")]`: Field holding the byte range of a primary label.
+///   The message is optional.
+/// * `#[secondary("message")]`: Field holding the byte range of a secondary
+///   label. The message is optional.
+/// * `#[note("message")]`: Note to display beneath the snippet. May be placed
+///   on a struct, enum variant, or field.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::ops::{Range, RangeInclusive};
+///
+/// use srcerr::{codespan_reporting::files::SimpleFiles, ErrorDetail};
+///
+/// #[derive(Debug, ErrorDetail)]
+/// #[error_detail(files = SimpleFiles<&'files str, &'files str>)]
+/// pub enum ConfigErrorDetail {
+///     /// Error when a value is out of range.
+///     #[note("expected value to be within: `{range:?}`")]
+///     ValueOutOfRange {
+///         /// ID of the file containing the invalid value.
+///         #[file_id]
+///         file_id: usize,
+///         /// The value.
+///         value: i32,
+///         /// Byte begin and end indices where the value is defined.
+///         #[primary("not within the range: `{range:?}`")]
+///         value_byte_indices: Range<usize>,
+///         /// Range that the value must be within.
+///         range: RangeInclusive<u32>,
+///     },
+/// }
+///
+/// let detail = ConfigErrorDetail::ValueOutOfRange {
+///     file_id: 0,
+///     value: -1,
+///     value_byte_indices: 21..23,
+///     range: 1..=3,
+/// };
+///
+/// let labels = detail.labels();
+/// assert_eq!(
+///     Some("not within the range: `1..=3`"),
+///     labels.first().map(|label| label.message.as_str())
+/// );
+/// assert_eq!(
+///     vec![String::from("expected value to be within: `1..=3`")],
+///     detail.notes(&SimpleFiles::new())
+/// );
+/// ```
+#[proc_macro_derive(
+    ErrorDetail,
+    attributes(error_detail, file_id, primary, secondary, note)
+)]
+pub fn error_detail_derive(input: TokenStream) -> TokenStream {
+    let derive_input = parse_macro_input!(input as DeriveInput);
+
+    error_detail::derive(&derive_input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
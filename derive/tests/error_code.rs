@@ -0,0 +1,88 @@
+use srcerr::{codespan_reporting::diagnostic::Severity, fmt::Code, ErrorCode};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ErrorCode)]
+#[error_code(prefix = "CFG")]
+enum ConfigErrorCode {
+    #[code(1, "Value out of range.")]
+    ValueOutOfRange,
+    #[code(12, "String provided is too long.")]
+    #[explanation(file = "tests/explanations/CFG12.md")]
+    StringTooLong,
+    #[code(2, "Value is not a number.")]
+    #[severity(Warning)]
+    #[explanation("Values must be numeric.")]
+    NotANumber,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ErrorCode)]
+#[error_code(max = 100)]
+enum DefaultPrefixErrorCode {
+    #[code(3, "Three.")]
+    Three,
+}
+
+#[test]
+fn derives_code_and_description_per_variant() {
+    assert_eq!(1, ConfigErrorCode::ValueOutOfRange.code());
+    assert_eq!(12, ConfigErrorCode::StringTooLong.code());
+    assert_eq!(2, ConfigErrorCode::NotANumber.code());
+    assert_eq!(
+        "Value out of range.",
+        ConfigErrorCode::ValueOutOfRange.description()
+    );
+    assert_eq!(
+        "String provided is too long.",
+        ConfigErrorCode::StringTooLong.description()
+    );
+}
+
+#[test]
+fn derives_prefix_and_error_code_max() {
+    assert_eq!("CFG", ConfigErrorCode::PREFIX);
+    assert_eq!(12, ConfigErrorCode::ERROR_CODE_MAX);
+    assert_eq!("CFG01", Code::string(ConfigErrorCode::ValueOutOfRange));
+}
+
+#[test]
+fn uses_default_prefix_and_explicit_max() {
+    assert_eq!("E", DefaultPrefixErrorCode::PREFIX);
+    assert_eq!(100, DefaultPrefixErrorCode::ERROR_CODE_MAX);
+    assert_eq!("E003", Code::string(DefaultPrefixErrorCode::Three));
+}
+
+#[test]
+fn derives_all_in_code_order() {
+    assert_eq!(
+        &[
+            ConfigErrorCode::ValueOutOfRange,
+            ConfigErrorCode::NotANumber,
+            ConfigErrorCode::StringTooLong,
+        ],
+        ConfigErrorCode::all()
+    );
+    assert_eq!(
+        &[DefaultPrefixErrorCode::Three],
+        DefaultPrefixErrorCode::all()
+    );
+}
+
+#[test]
+fn derives_explanation_from_attribute_or_file() {
+    assert_eq!(None, ConfigErrorCode::ValueOutOfRange.explanation());
+    assert_eq!(
+        Some("Values must be numeric."),
+        ConfigErrorCode::NotANumber.explanation()
+    );
+    assert_eq!(
+        Some("# String too long\n\nStrings must be at most 32 characters long.\n"),
+        ConfigErrorCode::StringTooLong.explanation()
+    );
+    assert_eq!(None, DefaultPrefixErrorCode::Three.explanation());
+}
+
+#[test]
+fn derives_severity_defaulting_to_error() {
+    assert_eq!(Severity::Error, ConfigErrorCode::ValueOutOfRange.severity());
+    assert_eq!(Severity::Warning, ConfigErrorCode::NotANumber.severity());
+    assert_eq!(Severity::Error, DefaultPrefixErrorCode::Three.severity());
+}
@@ -0,0 +1,105 @@
+use std::ops::{Range, RangeInclusive};
+
+use srcerr::{codespan_reporting::files::SimpleFiles, ErrorDetail, Label};
+
+#[derive(Debug, ErrorDetail)]
+#[error_detail(files = SimpleFiles<&'files str, &'files str>)]
+enum ConfigErrorDetail {
+    #[note("expected value to be within: `{range:?}`")]
+    ValueOutOfRange {
+        #[file_id]
+        file_id: usize,
+        value: i32,
+        #[primary("not within the range: `{range:?}`")]
+        value_byte_indices: Range<usize>,
+        range: RangeInclusive<u32>,
+    },
+    StringTooLong {
+        #[file_id]
+        file_id: usize,
+        #[primary]
+        value_byte_indices: Range<usize>,
+        #[note("string must be at most {limit} characters")]
+        limit: usize,
+    },
+    Unknown,
+}
+
+#[derive(Debug, ErrorDetail)]
+#[error_detail(files = SimpleFiles<&'files str, &'files str>)]
+#[note("`chosen` value must come from one of `available` values")]
+struct SourceRefHintErrorDetail {
+    #[file_id]
+    file_id: usize,
+    value: String,
+    #[primary("invalid value `{value}` specified")]
+    value_byte_indices: Range<usize>,
+    #[secondary("defined here")]
+    valid_values_byte_indices: Range<usize>,
+}
+
+#[test]
+fn enum_variant_labels_use_file_id_and_formatted_message() {
+    let detail = ConfigErrorDetail::ValueOutOfRange {
+        file_id: 1,
+        value: -1,
+        value_byte_indices: 21..23,
+        range: 1..=3,
+    };
+
+    assert_eq!(
+        vec![Label::primary(1, 21..23).with_message("not within the range: `1..=3`")],
+        detail.labels()
+    );
+    assert_eq!(
+        vec![String::from("expected value to be within: `1..=3`")],
+        detail.notes(&SimpleFiles::new())
+    );
+}
+
+#[test]
+fn enum_variant_label_without_message_and_field_note() {
+    let detail = ConfigErrorDetail::StringTooLong {
+        file_id: 0,
+        value_byte_indices: 39..48,
+        limit: 5,
+    };
+
+    assert_eq!(vec![Label::primary(0, 39..48)], detail.labels());
+    assert_eq!(
+        vec![String::from("string must be at most 5 characters")],
+        detail.notes(&SimpleFiles::new())
+    );
+}
+
+#[test]
+fn unit_variant_has_no_labels_or_notes() {
+    let detail = ConfigErrorDetail::Unknown;
+
+    assert!(detail.labels().is_empty());
+    assert!(detail.notes(&SimpleFiles::new()).is_empty());
+}
+
+#[test]
+fn struct_labels_include_primary_and_secondary() {
+    let detail = SourceRefHintErrorDetail {
+        file_id: 0,
+        value: String::from("ghi"),
+        value_byte_indices: 44..49,
+        valid_values_byte_indices: 4..34,
+    };
+
+    assert_eq!(
+        vec![
+            Label::primary(0, 44..49).with_message("invalid value `ghi` specified"),
+            Label::secondary(0, 4..34).with_message("defined here"),
+        ],
+        detail.labels()
+    );
+    assert_eq!(
+        vec![String::from(
+            "`chosen` value must come from one of `available` values"
+        )],
+        detail.notes(&SimpleFiles::new())
+    );
+}
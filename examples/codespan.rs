@@ -3,13 +3,13 @@ use std::{borrow::Cow, ops::RangeInclusive, path::Path};
 use srcerr::{
     codespan::{FileId, Files, Span},
     codespan_reporting::{
-        diagnostic::{Label, Severity},
+        diagnostic::Severity,
         files::Error,
         term,
         term::termcolor::{ColorChoice, StandardStream},
     },
     fmt::Note,
-    ErrorCode, ErrorDetail, SourceError,
+    ErrorCode, ErrorDetail, Label, SourceError,
 };
 
 const SIMPLE_TOML: &str = include_str!("simple.toml");
@@ -136,7 +136,7 @@ impl<'files> ErrorDetail<'files> for SimpleErrorDetail {
                 ..
             } => {
                 vec![
-                    Label::primary(*file_id, value_byte_indices.clone()).with_message(format!(
+                    Label::primary(*file_id, *value_byte_indices).with_message(format!(
                         "not within the range: `{}..={}`",
                         range.start(),
                         range.end()
@@ -149,10 +149,8 @@ impl<'files> ErrorDetail<'files> for SimpleErrorDetail {
                 limit,
                 ..
             } => {
-                vec![
-                    Label::primary(*file_id, value_byte_indices.clone())
-                        .with_message(format!("exceeds the {} character limit.", limit)),
-                ]
+                vec![Label::primary(*file_id, *value_byte_indices)
+                    .with_message(format!("exceeds the {} character limit.", limit))]
             }
         }
     }
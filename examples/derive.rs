@@ -0,0 +1,118 @@
+use std::{
+    ops::{Range, RangeInclusive},
+    path::Path,
+};
+
+use srcerr::{
+    codespan_reporting::{
+        diagnostic::Severity,
+        files::{Error, Files, SimpleFiles},
+        term,
+        term::termcolor::{ColorChoice, StandardStream},
+    },
+    ErrorCode, ErrorDetail, SourceError,
+};
+
+const SIMPLE_TOML: &str = include_str!("simple.toml");
+
+fn main() -> Result<(), Error> {
+    // Path to file containing error.
+    let path = Path::new("examples/simple.toml");
+    // Content from the file.
+    let content = SIMPLE_TOML;
+
+    let mut files = SimpleFiles::new();
+    let path_display = path.display().to_string();
+    let file_id = files.add(path_display.as_str(), content);
+    let content = files
+        .source(file_id)
+        .expect("Expected to get file content.");
+
+    let value_out_of_range = value_out_of_range(file_id);
+    let value_out_of_range = value_out_of_range.as_diagnostic(&files);
+    let string_too_long = string_too_long(file_id, content);
+    let string_too_long = string_too_long.as_diagnostic(&files);
+
+    let writer = StandardStream::stderr(ColorChoice::Always);
+    let config = term::Config::default();
+    term::emit(&mut writer.lock(), &config, &files, &value_out_of_range)?;
+    term::emit(&mut writer.lock(), &config, &files, &string_too_long)?;
+
+    Ok(())
+}
+
+fn value_out_of_range<'f>(
+    file_id: usize,
+) -> SourceError<'f, DeriveErrorCode, DeriveErrorDetail, SimpleFiles<&'f str, &'f str>> {
+    let error_code = DeriveErrorCode::ValueOutOfRange;
+    let error_detail = DeriveErrorDetail::ValueOutOfRange {
+        file_id,
+        value: -1,
+        value_byte_indices: 21..23,
+        range: 1..=3,
+    };
+    let severity = Severity::Error;
+
+    SourceError::new(error_code, error_detail, severity)
+}
+
+fn string_too_long<'f>(
+    file_id: usize,
+    content: &str,
+) -> SourceError<'f, DeriveErrorCode, DeriveErrorDetail, SimpleFiles<&'f str, &'f str>> {
+    let error_code = DeriveErrorCode::StringTooLong;
+    let error_detail = DeriveErrorDetail::StringTooLong {
+        file_id,
+        value: content[40..47].to_string(),
+        value_byte_indices: 39..48,
+        limit: 5,
+    };
+    let severity = Severity::Error;
+
+    SourceError::new(error_code, error_detail, severity)
+}
+
+/// Error codes for derive example.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ErrorCode)]
+#[error_code(prefix = "E")]
+pub enum DeriveErrorCode {
+    /// Error when a value is out of range.
+    #[code(1, "Value out of range.")]
+    ValueOutOfRange,
+    /// Error when a string is too long.
+    #[code(2, "String provided is too long.")]
+    StringTooLong,
+}
+
+/// Error detail for derive example.
+#[derive(Debug, ErrorDetail)]
+#[error_detail(files = SimpleFiles<&'files str, &'files str>)]
+pub enum DeriveErrorDetail {
+    /// Error when a value is out of range.
+    #[note("expected value to be within: `{range:?}`")]
+    ValueOutOfRange {
+        /// ID of the file containing the invalid value.
+        #[file_id]
+        file_id: usize,
+        /// The value.
+        value: i32,
+        /// Byte begin and end indices where the value is defined.
+        #[primary("not within the range: `{range:?}`")]
+        value_byte_indices: Range<usize>,
+        /// Range that the value must be within.
+        range: RangeInclusive<u32>,
+    },
+    /// Error when a string is too long.
+    StringTooLong {
+        /// ID of the file containing the invalid value.
+        #[file_id]
+        file_id: usize,
+        /// The value that is too long.
+        value: String,
+        /// Byte begin and end indices where the value is defined.
+        #[primary("exceeds the {limit} character limit.")]
+        value_byte_indices: Range<usize>,
+        /// Maximum length allowed for the string.
+        limit: usize,
+    },
+}
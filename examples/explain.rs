@@ -0,0 +1,58 @@
+//! Template for an `explain` command, similar to `rustc --explain`.
+//!
+//! ```bash
+//! cargo run --example explain --features "derive explain" -- E2
+//! cargo run --example explain --features "derive explain" -- --list
+//! ```
+
+use std::{env, process};
+
+use srcerr::{
+    codespan_reporting::term::termcolor::{ColorChoice, StandardStream},
+    explain::Explainer,
+    ErrorCode,
+};
+
+fn main() {
+    let arg = env::args().nth(1);
+    let explainer = Explainer::new();
+    let mut stdout = StandardStream::stdout(ColorChoice::Auto);
+
+    let result = match arg.as_deref() {
+        None | Some("--list") => explainer
+            .list::<ExplainErrorCode>(&mut stdout)
+            .map_err(Into::into),
+        Some(code) => explainer.explain::<ExplainErrorCode>(&mut stdout, code),
+    };
+
+    if let Err(error) = result {
+        eprintln!("error: {error}");
+        process::exit(1);
+    }
+}
+
+/// Error codes for explain example.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ErrorCode)]
+#[error_code(prefix = "E")]
+pub enum ExplainErrorCode {
+    /// Error when a value is out of range.
+    #[code(1, "Value out of range.")]
+    ValueOutOfRange,
+    /// Error when a string is too long.
+    #[code(2, "String provided is too long.")]
+    #[explanation(
+        "# String provided is too long
+
+A string value exceeds the maximum number of characters allowed for its field.
+
+Erroneous example:
+
+```toml
+name = \"a very long name\"
+```
+
+Shorten the value to within the limit shown in the error, or remove the field
+to use the default value."
+    )]
+    StringTooLong,
+}
@@ -7,12 +7,12 @@ use std::{
 
 use srcerr::{
     codespan_reporting::{
-        diagnostic::{Label, Severity},
+        diagnostic::Severity,
         files::{Error, Files, SimpleFiles},
         term,
         term::termcolor::{ColorChoice, StandardStream},
     },
-    ErrorCode, ErrorDetail, SourceError,
+    ErrorCode, ErrorDetail, Label, SourceError,
 };
 
 // Truncate long lines is pending <https://github.com/brendanzab/codespan/issues/228>
@@ -105,10 +105,8 @@ impl<'files> ErrorDetail<'files> for LongExprContextErrorDetail {
             value_byte_indices,
         } = self;
 
-        vec![
-            Label::primary(*file_id, value_byte_indices.clone())
-                .with_message("expected value to be less than 26"),
-        ]
+        vec![Label::primary(*file_id, value_byte_indices.clone())
+            .with_message("expected value to be less than 26")]
     }
 
     fn notes(&self, _files: &Self::Files) -> Vec<String> {
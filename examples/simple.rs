@@ -6,13 +6,13 @@ use std::{
 
 use srcerr::{
     codespan_reporting::{
-        diagnostic::{Label, Severity},
+        diagnostic::Severity,
         files::{Error, Files, SimpleFiles},
         term,
         term::termcolor::{ColorChoice, StandardStream},
     },
     fmt::Note,
-    ErrorCode, ErrorDetail, SourceError,
+    ErrorCode, ErrorDetail, Label, SourceError, SourceErrors,
 };
 
 const SIMPLE_TOML: &str = include_str!("simple.toml");
@@ -30,15 +30,13 @@ fn main() -> Result<(), Error> {
         .source(file_id)
         .expect("Expected to get file content.");
 
-    let value_out_of_range = value_out_of_range(file_id);
-    let value_out_of_range = value_out_of_range.as_diagnostic(&files);
-    let string_too_long = string_too_long(file_id, content);
-    let string_too_long = string_too_long.as_diagnostic(&files);
+    let mut source_errors = SourceErrors::new();
+    source_errors.push(value_out_of_range(file_id));
+    source_errors.push(string_too_long(file_id, content));
 
     let writer = StandardStream::stderr(ColorChoice::Always);
     let config = term::Config::default();
-    term::emit(&mut writer.lock(), &config, &files, &value_out_of_range)?;
-    term::emit(&mut writer.lock(), &config, &files, &string_too_long)?;
+    source_errors.emit_all(&mut writer.lock(), &config, &files)?;
 
     Ok(())
 }
@@ -154,10 +152,8 @@ impl<'files> ErrorDetail<'files> for SimpleErrorDetail {
                 limit,
                 ..
             } => {
-                vec![
-                    Label::primary(*file_id, value_byte_indices.clone())
-                        .with_message(format!("exceeds the {} character limit.", limit)),
-                ]
+                vec![Label::primary(*file_id, value_byte_indices.clone())
+                    .with_message(format!("exceeds the {} character limit.", limit))]
             }
         }
     }
@@ -2,12 +2,13 @@ use std::{ops::Range, path::Path};
 
 use srcerr::{
     codespan_reporting::{
-        diagnostic::{Label, Severity},
+        diagnostic::Severity,
         files::{Error, Files, SimpleFiles},
         term,
         term::termcolor::{ColorChoice, StandardStream},
     },
-    ErrorCode, ErrorDetail, SourceError,
+    model::{Applicability, Span, Suggestion},
+    ErrorCode, ErrorDetail, Label, SourceError,
 };
 
 const SOURCE_REF_HINT_YAML: &str = include_str!("source_ref_hint.yaml");
@@ -114,4 +115,21 @@ impl<'files> ErrorDetail<'files> for SourceRefHintErrorDetail {
             "`chosen` value must come from one of `available` values",
         )]
     }
+
+    fn suggestions(&self) -> Vec<Suggestion<usize>> {
+        // The intended value is unknown, so the first available value may be
+        // incorrect.
+        self.valid_values
+            .first()
+            .map(|valid_value| {
+                Suggestion::new(
+                    Span::new(self.file_id, self.value_byte_indices.clone()),
+                    format!("\"{}\"", valid_value),
+                    Applicability::MaybeIncorrect,
+                )
+                .with_message(format!("use an available value, such as `{}`", valid_value))
+            })
+            .into_iter()
+            .collect()
+    }
 }
@@ -0,0 +1,30 @@
+//! Error index documentation, listing every error code with its explanation.
+//!
+//! This is enabled by the `"error_index"` feature. [`ErrorIndexGenerator`]
+//! renders an [`ErrorCode`] type's codes from [`ErrorCode::all`] as a Markdown
+//! or HTML page, with one section per error code, anchored by its
+//! [`Code::string`].
+//!
+//! Each section contains the error's description, its default
+//! [`ErrorCode::severity`], and its [`ErrorCode::explanation`]. Example snippets
+//! are written as fenced code blocks within the explanation.
+//!
+//! The index can be kept in sync with the code by writing it from `build.rs`
+//! with [`ErrorIndexGenerator::write`], or by checking it in a test with
+//! [`ErrorIndexGenerator::check`], which fails when the checked-in index is
+//! stale.
+//!
+//! [`Code::string`]: crate::fmt::Code::string
+//! [`ErrorCode`]: crate::ErrorCode
+//! [`ErrorCode::all`]: crate::ErrorCode::all
+//! [`ErrorCode::explanation`]: crate::ErrorCode::explanation
+//! [`ErrorCode::severity`]: crate::ErrorCode::severity
+
+pub use self::{
+    error_index_error::ErrorIndexError, error_index_format::ErrorIndexFormat,
+    error_index_generator::ErrorIndexGenerator,
+};
+
+mod error_index_error;
+mod error_index_format;
+mod error_index_generator;
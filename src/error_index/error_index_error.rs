@@ -0,0 +1,54 @@
+use std::{fmt, io, path::PathBuf};
+
+/// Error when writing or checking an error index.
+#[derive(Debug)]
+pub enum ErrorIndexError {
+    /// The error index file does not match the generated error index.
+    Stale {
+        /// Path to the error index file.
+        path: PathBuf,
+    },
+    /// Failed to read or write the error index file.
+    Io {
+        /// Path to the error index file.
+        path: PathBuf,
+        /// The underlying error.
+        error: io::Error,
+    },
+    /// The error index was written or checked for an error code type whose
+    /// `ErrorCode::all` is empty, so it would list no codes.
+    ErrorCodesNone {
+        /// Name of the error code type.
+        error_code_type: &'static str,
+    },
+}
+
+impl fmt::Display for ErrorIndexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Stale { path } => write!(
+                f,
+                "Error index `{}` is out of date, regenerate it with `ErrorIndexGenerator::write`.",
+                path.display()
+            ),
+            Self::Io { path, .. } => {
+                write!(f, "Failed to access error index `{}`.", path.display())
+            }
+            Self::ErrorCodesNone { error_code_type } => write!(
+                f,
+                "`{}::all` returns no error codes, so the error index would be empty. \
+                Implement `ErrorCode::all`, or use `#[derive(ErrorCode)]`.",
+                error_code_type
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ErrorIndexError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Stale { .. } | Self::ErrorCodesNone { .. } => None,
+            Self::Io { error, .. } => Some(error),
+        }
+    }
+}
@@ -0,0 +1,8 @@
+/// Format of the generated error index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ErrorIndexFormat {
+    /// Markdown document, e.g. for a repository's `docs` directory.
+    Markdown,
+    /// Self-contained HTML document.
+    Html,
+}
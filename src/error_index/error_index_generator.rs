@@ -0,0 +1,450 @@
+use std::{
+    fmt::Write as _,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use pulldown_cmark::{html, Event, HeadingLevel, Parser, Tag, TagEnd};
+
+use crate::{
+    error_index::{ErrorIndexError, ErrorIndexFormat},
+    fmt::Code,
+    formatter::{self, HtmlEscape},
+    ErrorCode,
+};
+
+/// Generates an index of every error code, as Markdown or HTML.
+///
+/// Headings within explanations are demoted by two levels, so that they nest
+/// beneath each error code's section heading.
+///
+/// # Examples
+///
+/// Checking the index in a test, so it is regenerated when error codes change:
+///
+/// ```rust,ignore
+/// use srcerr::error_index::{ErrorIndexFormat, ErrorIndexGenerator};
+///
+/// #[test]
+/// fn error_index_is_up_to_date() {
+///     ErrorIndexGenerator::new()
+///         .with_title("MyApp Errors")
+///         .check::<MyAppErrorCode>("docs/errors.md", ErrorIndexFormat::Markdown)
+///         .unwrap();
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ErrorIndexGenerator {
+    /// Title of the error index.
+    title: String,
+}
+
+impl ErrorIndexGenerator {
+    /// Returns a new `ErrorIndexGenerator`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the title of the error index.
+    #[must_use]
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Returns the error index in the given format.
+    ///
+    /// # Parameters
+    ///
+    /// * `format`: Whether to render Markdown or HTML.
+    pub fn render<Ec>(&self, format: ErrorIndexFormat) -> String
+    where
+        Ec: ErrorCode + 'static,
+    {
+        match format {
+            ErrorIndexFormat::Markdown => self.markdown::<Ec>(),
+            ErrorIndexFormat::Html => self.html::<Ec>(),
+        }
+    }
+
+    /// Returns the error index as a Markdown document.
+    pub fn markdown<Ec>(&self) -> String
+    where
+        Ec: ErrorCode + 'static,
+    {
+        let mut buffer = String::new();
+        self.fmt_markdown::<Ec>(&mut buffer)
+            .expect("Failed to format error index.");
+        buffer
+    }
+
+    /// Returns the error index as a self-contained HTML document.
+    pub fn html<Ec>(&self) -> String
+    where
+        Ec: ErrorCode + 'static,
+    {
+        let mut buffer = String::new();
+        self.fmt_html::<Ec>(&mut buffer)
+            .expect("Failed to format error index.");
+        buffer
+    }
+
+    /// Writes the error index to the file, if its content has changed.
+    ///
+    /// The file is left untouched when it is up to date, so that calling this
+    /// from `build.rs` does not trigger rebuilds.
+    ///
+    /// # Parameters
+    ///
+    /// * `path`: Path to the error index file.
+    /// * `format`: Whether to render Markdown or HTML.
+    ///
+    /// Returns [`ErrorIndexError::ErrorCodesNone`] when [`ErrorCode::all`] is
+    /// empty, as it is by default.
+    pub fn write<Ec>(
+        &self,
+        path: impl AsRef<Path>,
+        format: ErrorIndexFormat,
+    ) -> Result<(), ErrorIndexError>
+    where
+        Ec: ErrorCode + 'static,
+    {
+        let path = path.as_ref();
+        let index = self.render_listed::<Ec>(format)?;
+        if Self::read(path)?.as_deref() == Some(index.as_str()) {
+            return Ok(());
+        }
+
+        fs::write(path, index).map_err(|error| ErrorIndexError::Io {
+            path: path.to_path_buf(),
+            error,
+        })
+    }
+
+    /// Returns an error if the file does not match the generated error index.
+    ///
+    /// A missing file is reported as [`ErrorIndexError::Stale`].
+    ///
+    /// # Parameters
+    ///
+    /// * `path`: Path to the error index file.
+    /// * `format`: Whether to render Markdown or HTML.
+    ///
+    /// Returns [`ErrorIndexError::ErrorCodesNone`] when [`ErrorCode::all`] is
+    /// empty, as it is by default.
+    pub fn check<Ec>(
+        &self,
+        path: impl AsRef<Path>,
+        format: ErrorIndexFormat,
+    ) -> Result<(), ErrorIndexError>
+    where
+        Ec: ErrorCode + 'static,
+    {
+        let path = path.as_ref();
+        let index = self.render_listed::<Ec>(format)?;
+        if Self::read(path)?.as_deref() == Some(index.as_str()) {
+            Ok(())
+        } else {
+            Err(ErrorIndexError::Stale {
+                path: PathBuf::from(path),
+            })
+        }
+    }
+
+    /// Returns the error index, or an error if [`ErrorCode::all`] is empty.
+    fn render_listed<Ec>(&self, format: ErrorIndexFormat) -> Result<String, ErrorIndexError>
+    where
+        Ec: ErrorCode + 'static,
+    {
+        if Ec::all().is_empty() {
+            return Err(ErrorIndexError::ErrorCodesNone {
+                error_code_type: std::any::type_name::<Ec>(),
+            });
+        }
+
+        Ok(self.render::<Ec>(format))
+    }
+
+    /// Writes the error index as Markdown into the buffer.
+    fn fmt_markdown<Ec>(&self, buffer: &mut String) -> Result<(), std::fmt::Error>
+    where
+        Ec: ErrorCode + 'static,
+    {
+        writeln!(buffer, "# {}", self.title)?;
+        writeln!(buffer)?;
+        Ec::all().iter().try_for_each(|error_code| {
+            let code = Code::string(*error_code);
+            writeln!(buffer, "* [{code}](#{code}): {}", error_code.description())
+        })?;
+
+        Ec::all().iter().try_for_each(|error_code| {
+            let code = Code::string(*error_code);
+            writeln!(buffer)?;
+            writeln!(buffer, "<a id=\"{code}\"></a>")?;
+            writeln!(buffer)?;
+            writeln!(buffer, "## {code}")?;
+            writeln!(buffer)?;
+            writeln!(buffer, "{}", error_code.description())?;
+            writeln!(buffer)?;
+            writeln!(
+                buffer,
+                "Default severity: `{}`",
+                formatter::severity_str(error_code.severity())
+            )?;
+
+            if let Some(explanation) = error_code.explanation() {
+                writeln!(buffer)?;
+                markdown_headings_demote(buffer, explanation)?;
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Writes the error index as HTML into the buffer.
+    fn fmt_html<Ec>(&self, buffer: &mut String) -> Result<(), std::fmt::Error>
+    where
+        Ec: ErrorCode + 'static,
+    {
+        let title = HtmlEscape(&self.title);
+        writeln!(buffer, "<!DOCTYPE html>")?;
+        writeln!(buffer, "<html>")?;
+        writeln!(buffer, "<head>")?;
+        writeln!(buffer, "<meta charset=\"utf-8\">")?;
+        writeln!(buffer, "<title>{title}</title>")?;
+        writeln!(buffer, "</head>")?;
+        writeln!(buffer, "<body>")?;
+        writeln!(buffer, "<h1>{title}</h1>")?;
+        writeln!(buffer, "<ul class=\"srcerr-index\">")?;
+        Ec::all().iter().try_for_each(|error_code| {
+            let code = Code::string(*error_code);
+            writeln!(
+                buffer,
+                "<li><a href=\"#{code}\">{code}</a>: {}</li>",
+                HtmlEscape(error_code.description())
+            )
+        })?;
+        writeln!(buffer, "</ul>")?;
+
+        Ec::all().iter().try_for_each(|error_code| {
+            let code = Code::string(*error_code);
+            writeln!(buffer, "<section id=\"{code}\">")?;
+            writeln!(buffer, "<h2><a href=\"#{code}\">{code}</a></h2>")?;
+            writeln!(
+                buffer,
+                "<p class=\"srcerr-description\">{}</p>",
+                HtmlEscape(error_code.description())
+            )?;
+            writeln!(
+                buffer,
+                "<p class=\"srcerr-severity\">Default severity: <code>{}</code></p>",
+                formatter::severity_str(error_code.severity())
+            )?;
+
+            if let Some(explanation) = error_code.explanation() {
+                let events = Parser::new(explanation).map(html_heading_demote);
+                html::push_html(buffer, events);
+            }
+
+            writeln!(buffer, "</section>")
+        })?;
+
+        writeln!(buffer, "</body>")?;
+        writeln!(buffer, "</html>")?;
+
+        Ok(())
+    }
+
+    /// Returns the content of the file, or `None` if it does not exist.
+    fn read(path: &Path) -> Result<Option<String>, ErrorIndexError> {
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(Some(content)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(ErrorIndexError::Io {
+                path: path.to_path_buf(),
+                error,
+            }),
+        }
+    }
+}
+
+impl Default for ErrorIndexGenerator {
+    fn default() -> Self {
+        Self {
+            title: String::from("Error Index"),
+        }
+    }
+}
+
+/// Writes the Markdown with ATX headings demoted by two levels.
+///
+/// Lines within fenced code blocks are left untouched.
+fn markdown_headings_demote(buffer: &mut String, markdown: &str) -> Result<(), std::fmt::Error> {
+    let mut fence: Option<&str> = None;
+    markdown.lines().try_for_each(|line| {
+        let line_trimmed = line.trim_start();
+        match fence {
+            Some(fence_open) => {
+                if line_trimmed.starts_with(fence_open) {
+                    fence = None;
+                }
+            }
+            None if line_trimmed.starts_with("```") => fence = Some("```"),
+            None if line_trimmed.starts_with("~~~") => fence = Some("~~~"),
+            None => {
+                let level = line_trimmed.chars().take_while(|c| *c == '#').count();
+                let is_heading = (1..=6).contains(&level)
+                    && matches!(line_trimmed[level..].chars().next(), None | Some(' '));
+                if is_heading {
+                    let level_demoted = (level + 2).min(6);
+                    return writeln!(
+                        buffer,
+                        "{}{}",
+                        "#".repeat(level_demoted),
+                        &line_trimmed[level..]
+                    );
+                }
+            }
+        }
+        writeln!(buffer, "{line}")
+    })
+}
+
+/// Demotes heading events by two levels.
+fn html_heading_demote(event: Event<'_>) -> Event<'_> {
+    match event {
+        Event::Start(Tag::Heading {
+            level,
+            id,
+            classes,
+            attrs,
+        }) => Event::Start(Tag::Heading {
+            level: heading_level_demote(level),
+            id,
+            classes,
+            attrs,
+        }),
+        Event::End(TagEnd::Heading(level)) => {
+            Event::End(TagEnd::Heading(heading_level_demote(level)))
+        }
+        event => event,
+    }
+}
+
+/// Returns the heading level two levels lower, capped at `<h6>`.
+fn heading_level_demote(level: HeadingLevel) -> HeadingLevel {
+    match level {
+        HeadingLevel::H1 => HeadingLevel::H3,
+        HeadingLevel::H2 => HeadingLevel::H4,
+        HeadingLevel::H3 => HeadingLevel::H5,
+        HeadingLevel::H4 | HeadingLevel::H5 | HeadingLevel::H6 => HeadingLevel::H6,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::ErrorIndexGenerator;
+    use crate::{
+        error_index::{ErrorIndexError, ErrorIndexFormat},
+        test_support::{TestErrorCode, UnlistedErrorCode},
+    };
+
+    #[test]
+    fn markdown_renders_index_and_sections() {
+        let markdown = ErrorIndexGenerator::new()
+            .with_title("Test Errors")
+            .markdown::<TestErrorCode>();
+
+        let expected = [
+            "# Test Errors",
+            "",
+            "* [E1](#E1): `chosen` value is invalid.",
+            "",
+            "<a id=\"E1\"></a>",
+            "",
+            "## E1",
+            "",
+            "`chosen` value is invalid.",
+            "",
+            "Default severity: `error`",
+            "",
+            "The `chosen` value is not one of the `available` values.",
+            "",
+            "Erroneous example:",
+            "",
+            "```yaml",
+            "available:",
+            "  - \"abc\"",
+            "chosen: \"ghi\"",
+            "```",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(expected, markdown);
+    }
+
+    #[test]
+    fn markdown_demotes_headings_outside_code_blocks() {
+        let mut buffer = String::new();
+        super::markdown_headings_demote(&mut buffer, "# Title\n\n```sh\n# comment\n```\n#tag\n")
+            .expect("Expected Markdown to be written.");
+
+        assert_eq!("### Title\n\n```sh\n# comment\n```\n#tag\n", buffer);
+    }
+
+    #[test]
+    fn html_renders_sections_with_code_anchors() {
+        let html = ErrorIndexGenerator::new()
+            .with_title("<Errors>")
+            .html::<TestErrorCode>();
+
+        assert!(html.contains("<title>&lt;Errors&gt;</title>"));
+        assert!(html.contains("<li><a href=\"#E1\">E1</a>: `chosen` value is invalid.</li>"));
+        assert!(html.contains("<section id=\"E1\">"));
+        assert!(
+            html.contains("<p class=\"srcerr-severity\">Default severity: <code>error</code></p>")
+        );
+        assert!(html.contains("<pre><code class=\"language-yaml\">available:"));
+    }
+
+    #[test]
+    fn check_reports_stale_until_written() -> Result<(), Box<dyn std::error::Error>> {
+        let path =
+            std::env::temp_dir().join(format!("srcerr_error_index_{}.md", std::process::id()));
+        let generator = ErrorIndexGenerator::new();
+
+        let error = generator
+            .check::<TestErrorCode>(&path, ErrorIndexFormat::Markdown)
+            .expect_err("Expected missing index to be stale.");
+        assert!(matches!(error, ErrorIndexError::Stale { .. }));
+
+        generator.write::<TestErrorCode>(&path, ErrorIndexFormat::Markdown)?;
+        let check_result = generator.check::<TestErrorCode>(&path, ErrorIndexFormat::Markdown);
+        fs::remove_file(&path)?;
+
+        check_result?;
+        Ok(())
+    }
+
+    #[test]
+    fn write_and_check_return_error_when_error_codes_are_not_listed() {
+        let path = std::env::temp_dir().join(format!(
+            "srcerr_error_index_unlisted_{}.md",
+            std::process::id()
+        ));
+        let generator = ErrorIndexGenerator::new();
+
+        assert!(matches!(
+            generator.write::<UnlistedErrorCode>(&path, ErrorIndexFormat::Markdown),
+            Err(ErrorIndexError::ErrorCodesNone { error_code_type })
+                if error_code_type.ends_with("UnlistedErrorCode")
+        ));
+        assert!(!path.exists());
+        assert!(matches!(
+            generator.check::<UnlistedErrorCode>(&path, ErrorIndexFormat::Markdown),
+            Err(ErrorIndexError::ErrorCodesNone { .. })
+        ));
+    }
+}
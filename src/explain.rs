@@ -0,0 +1,21 @@
+//! Extended explanations of error codes, similar to `rustc --explain`.
+//!
+//! This is enabled by the `"explain"` feature. [`Explainer`] looks up an error
+//! code from what the user typed, and renders its [`ErrorCode::explanation`]
+//! Markdown to the terminal, so that applications can offer:
+//!
+//! ```text
+//! myapp explain E002
+//! ```
+//!
+//! See `examples/explain.rs` for a template of such a command.
+//!
+//! [`ErrorCode::explanation`]: crate::ErrorCode::explanation
+
+pub use self::{
+    explain_error::ExplainError, explainer::Explainer, markdown_renderer::MarkdownRenderer,
+};
+
+mod explain_error;
+mod explainer;
+mod markdown_renderer;
@@ -0,0 +1,48 @@
+use std::{fmt, io};
+
+/// Error when explaining an error code.
+#[derive(Debug)]
+pub enum ExplainError {
+    /// The error code does not exist.
+    CodeUnknown {
+        /// The error code that was requested.
+        code: String,
+    },
+    /// The error code exists, but has no extended explanation.
+    ExplanationNone {
+        /// The error code that was requested.
+        code: String,
+        /// Short description of the error.
+        description: &'static str,
+    },
+    /// Failed to write the explanation.
+    Io(io::Error),
+}
+
+impl fmt::Display for ExplainError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::CodeUnknown { code } => write!(f, "`{code}` is not a valid error code."),
+            Self::ExplanationNone { code, description } => write!(
+                f,
+                "`{code}` has no extended explanation. Description: {description}"
+            ),
+            Self::Io(_) => write!(f, "Failed to write error code explanation."),
+        }
+    }
+}
+
+impl std::error::Error for ExplainError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::CodeUnknown { .. } | Self::ExplanationNone { .. } => None,
+            Self::Io(error) => Some(error),
+        }
+    }
+}
+
+impl From<io::Error> for ExplainError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
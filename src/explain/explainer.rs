@@ -0,0 +1,151 @@
+use std::io;
+
+use codespan_reporting::term::termcolor::WriteColor;
+
+use crate::{explain::ExplainError, fmt::Code, ErrorCode};
+
+use super::MarkdownRenderer;
+
+/// Looks up error codes and renders their extended explanations.
+///
+/// Error codes are looked up from [`ErrorCode::all`], so the error code type
+/// must implement it -- `#[derive(ErrorCode)]` does so automatically.
+#[derive(Clone, Debug, Default)]
+pub struct Explainer {
+    /// Renders the Markdown explanation.
+    markdown_renderer: MarkdownRenderer,
+}
+
+impl Explainer {
+    /// Returns a new `Explainer`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the renderer used to render the Markdown explanation.
+    #[must_use]
+    pub fn with_markdown_renderer(mut self, markdown_renderer: MarkdownRenderer) -> Self {
+        self.markdown_renderer = markdown_renderer;
+        self
+    }
+
+    /// Returns the error code whose string form matches `code`.
+    ///
+    /// The comparison ignores ASCII case, leading zeros, and surrounding
+    /// whitespace, so `"e02"` matches `E002`. See [`Code::parse`].
+    ///
+    /// # Parameters
+    ///
+    /// * `code`: The error code string, e.g. `"E002"`.
+    pub fn find<Ec>(code: &str) -> Option<Ec>
+    where
+        Ec: ErrorCode + 'static,
+    {
+        Code::parse(code).ok()
+    }
+
+    /// Writes the extended explanation of the error code.
+    ///
+    /// # Parameters
+    ///
+    /// * `writer`: Where to write the explanation.
+    /// * `code`: The error code string that the user typed, e.g. `"E002"`.
+    pub fn explain<Ec>(&self, writer: &mut dyn WriteColor, code: &str) -> Result<(), ExplainError>
+    where
+        Ec: ErrorCode + 'static,
+    {
+        let error_code = Self::find::<Ec>(code).ok_or_else(|| ExplainError::CodeUnknown {
+            code: String::from(code.trim()),
+        })?;
+        let explanation =
+            error_code
+                .explanation()
+                .ok_or_else(|| ExplainError::ExplanationNone {
+                    code: Code::string(error_code),
+                    description: error_code.description(),
+                })?;
+
+        self.markdown_renderer.render(writer, explanation)?;
+
+        Ok(())
+    }
+
+    /// Writes every error code with its description, one per line.
+    ///
+    /// # Parameters
+    ///
+    /// * `writer`: Where to write the error codes.
+    pub fn list<Ec>(&self, writer: &mut dyn WriteColor) -> Result<(), io::Error>
+    where
+        Ec: ErrorCode + 'static,
+    {
+        Ec::all().iter().try_for_each(|error_code| {
+            writeln!(
+                writer,
+                "{}: {}",
+                Code::string(*error_code),
+                error_code.description()
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use codespan_reporting::term::termcolor::NoColor;
+
+    use super::Explainer;
+    use crate::{explain::ExplainError, test_support::TestErrorCode};
+
+    #[test]
+    fn find_ignores_case_and_whitespace() {
+        assert_eq!(
+            Some(TestErrorCode::InvalidValue),
+            Explainer::find::<TestErrorCode>(" e1\n")
+        );
+        assert_eq!(None, Explainer::find::<TestErrorCode>("E2"));
+    }
+
+    #[test]
+    fn explain_renders_explanation() -> Result<(), Box<dyn std::error::Error>> {
+        let mut buffer = NoColor::new(Vec::new());
+        Explainer::new().explain::<TestErrorCode>(&mut buffer, "E1")?;
+
+        let expected = [
+            "The `chosen` value is not one of the `available` values.",
+            "",
+            "Erroneous example:",
+            "",
+            "    available:",
+            "      - \"abc\"",
+            "    chosen: \"ghi\"",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(expected, String::from_utf8(buffer.into_inner())?);
+        Ok(())
+    }
+
+    #[test]
+    fn explain_returns_error_for_unknown_code() {
+        let mut buffer = NoColor::new(Vec::new());
+        let error = Explainer::new()
+            .explain::<TestErrorCode>(&mut buffer, "E9")
+            .expect_err("Expected unknown code to be rejected.");
+
+        assert!(matches!(error, ExplainError::CodeUnknown { ref code } if code == "E9"));
+        assert_eq!("`E9` is not a valid error code.", error.to_string());
+    }
+
+    #[test]
+    fn list_writes_codes_and_descriptions() -> Result<(), Box<dyn std::error::Error>> {
+        let mut buffer = NoColor::new(Vec::new());
+        Explainer::new().list::<TestErrorCode>(&mut buffer)?;
+
+        assert_eq!(
+            "E1: `chosen` value is invalid.\n",
+            String::from_utf8(buffer.into_inner())?
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,411 @@
+use std::io;
+
+use codespan_reporting::term::termcolor::{Color, ColorSpec, WriteColor};
+use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+
+/// Renders Markdown explanations to the terminal.
+///
+/// Block elements are separated by blank lines, code blocks are indented by
+/// four spaces, and inline code keeps its backticks. Headings, emphasis, inline
+/// code, and links are coloured when the writer supports colour.
+///
+/// # Examples
+///
+/// ```rust
+/// use srcerr::{codespan_reporting::term::termcolor::NoColor, explain::MarkdownRenderer};
+///
+/// let mut buffer = NoColor::new(Vec::new());
+/// MarkdownRenderer::new()
+///     .render(&mut buffer, "# Heading\n\nUse `a` or *b*.")
+///     .unwrap();
+///
+/// assert_eq!(
+///     "Heading\n\nUse `a` or b.\n",
+///     String::from_utf8(buffer.into_inner()).unwrap()
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct MarkdownRenderer {
+    /// Colour of headings.
+    heading: ColorSpec,
+    /// Colour of `*emphasized*` text.
+    emphasis: ColorSpec,
+    /// Colour of `**strong**` text.
+    strong: ColorSpec,
+    /// Colour of inline code and code blocks.
+    code: ColorSpec,
+    /// Colour of link text.
+    link: ColorSpec,
+}
+
+impl MarkdownRenderer {
+    /// Returns a new `MarkdownRenderer` with the default colours.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the colour of headings.
+    #[must_use]
+    pub fn with_heading(mut self, color_spec: ColorSpec) -> Self {
+        self.heading = color_spec;
+        self
+    }
+
+    /// Sets the colour of `*emphasized*` text.
+    #[must_use]
+    pub fn with_emphasis(mut self, color_spec: ColorSpec) -> Self {
+        self.emphasis = color_spec;
+        self
+    }
+
+    /// Sets the colour of `**strong**` text.
+    #[must_use]
+    pub fn with_strong(mut self, color_spec: ColorSpec) -> Self {
+        self.strong = color_spec;
+        self
+    }
+
+    /// Sets the colour of inline code and code blocks.
+    #[must_use]
+    pub fn with_code(mut self, color_spec: ColorSpec) -> Self {
+        self.code = color_spec;
+        self
+    }
+
+    /// Sets the colour of link text.
+    #[must_use]
+    pub fn with_link(mut self, color_spec: ColorSpec) -> Self {
+        self.link = color_spec;
+        self
+    }
+
+    /// Writes the Markdown to the writer.
+    ///
+    /// # Parameters
+    ///
+    /// * `writer`: Where to write the rendered Markdown.
+    /// * `markdown`: The Markdown to render.
+    pub fn render(&self, writer: &mut dyn WriteColor, markdown: &str) -> Result<(), io::Error> {
+        let mut render_state = RenderState::new(self, writer);
+        Parser::new(markdown).try_for_each(|event| render_state.render_event(event))?;
+        render_state.writer.reset()
+    }
+}
+
+impl Default for MarkdownRenderer {
+    fn default() -> Self {
+        let mut heading = ColorSpec::new();
+        heading.set_bold(true).set_underline(true);
+        let mut emphasis = ColorSpec::new();
+        emphasis.set_italic(true);
+        let mut strong = ColorSpec::new();
+        strong.set_bold(true);
+        let mut code = ColorSpec::new();
+        code.set_fg(Some(Color::Yellow));
+        let mut link = ColorSpec::new();
+        link.set_fg(Some(Color::Blue)).set_underline(true);
+
+        Self {
+            heading,
+            emphasis,
+            strong,
+            code,
+            link,
+        }
+    }
+}
+
+/// Tracks where rendering is up to within the Markdown document.
+struct RenderState<'r, 'w> {
+    /// The colours to render with.
+    markdown_renderer: &'r MarkdownRenderer,
+    /// Where to write the rendered Markdown.
+    writer: &'w mut dyn WriteColor,
+    /// Colours currently applied, innermost last.
+    color_specs: Vec<ColorSpec>,
+    /// Prefixes written at the start of each line, outermost first.
+    indents: Vec<String>,
+    /// Next number for each nested list, `None` for unordered lists.
+    list_numbers: Vec<Option<u64>>,
+    /// Destination URLs of links currently being rendered.
+    link_urls: Vec<String>,
+    /// Whether a blank line is needed before the next block.
+    block_separator: bool,
+    /// Whether the cursor is at the start of a line.
+    line_start: bool,
+}
+
+impl<'r, 'w> RenderState<'r, 'w> {
+    fn new(markdown_renderer: &'r MarkdownRenderer, writer: &'w mut dyn WriteColor) -> Self {
+        Self {
+            markdown_renderer,
+            writer,
+            color_specs: Vec::new(),
+            indents: Vec::new(),
+            list_numbers: Vec::new(),
+            link_urls: Vec::new(),
+            block_separator: false,
+            line_start: true,
+        }
+    }
+
+    fn render_event(&mut self, event: Event<'_>) -> Result<(), io::Error> {
+        match event {
+            Event::Start(tag) => self.render_start(tag),
+            Event::End(tag_end) => self.render_end(tag_end),
+            Event::Text(text) => self.write_text(&text),
+            Event::Code(code) => {
+                self.color_push(self.markdown_renderer.code.clone())?;
+                self.write_text(&format!("`{code}`"))?;
+                self.color_pop()
+            }
+            Event::Html(html) | Event::InlineHtml(html) => self.write_text(&html),
+            Event::SoftBreak | Event::HardBreak => self.write_text("\n"),
+            Event::Rule => {
+                self.block_begin()?;
+                self.write_text("---\n")?;
+                self.block_separator = true;
+                Ok(())
+            }
+            Event::TaskListMarker(checked) => {
+                self.write_text(if checked { "[x] " } else { "[ ] " })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn render_start(&mut self, tag: Tag<'_>) -> Result<(), io::Error> {
+        match tag {
+            Tag::Paragraph => {
+                if self.line_start {
+                    self.block_begin()?;
+                }
+                Ok(())
+            }
+            Tag::Heading { .. } => {
+                self.block_begin()?;
+                self.color_push(self.markdown_renderer.heading.clone())
+            }
+            Tag::BlockQuote(_) => {
+                self.block_begin()?;
+                self.indents.push(String::from("> "));
+                Ok(())
+            }
+            Tag::CodeBlock(_) => {
+                self.block_begin()?;
+                self.indents.push(String::from("    "));
+                self.color_push(self.markdown_renderer.code.clone())
+            }
+            Tag::List(number_first) => {
+                if self.list_numbers.is_empty() {
+                    self.block_begin()?;
+                } else {
+                    self.line_end()?;
+                }
+                self.list_numbers.push(number_first);
+                Ok(())
+            }
+            Tag::Item => {
+                if self.block_separator {
+                    self.write_text("\n")?;
+                    self.block_separator = false;
+                }
+                self.line_end()?;
+                let marker = match self.list_numbers.last_mut() {
+                    Some(Some(number)) => {
+                        let marker = format!("{number}. ");
+                        *number += 1;
+                        marker
+                    }
+                    Some(None) | None => String::from("* "),
+                };
+                self.write_text(&marker)?;
+                self.indents.push(" ".repeat(marker.len()));
+                Ok(())
+            }
+            Tag::Emphasis => self.color_push(self.markdown_renderer.emphasis.clone()),
+            Tag::Strong => self.color_push(self.markdown_renderer.strong.clone()),
+            Tag::Link { dest_url, .. } | Tag::Image { dest_url, .. } => {
+                self.link_urls.push(dest_url.into_string());
+                self.color_push(self.markdown_renderer.link.clone())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn render_end(&mut self, tag_end: TagEnd) -> Result<(), io::Error> {
+        match tag_end {
+            TagEnd::Paragraph => {
+                self.line_end()?;
+                self.block_separator = true;
+                Ok(())
+            }
+            TagEnd::Heading(_) => {
+                self.color_pop()?;
+                self.line_end()?;
+                self.block_separator = true;
+                Ok(())
+            }
+            TagEnd::BlockQuote(_) => {
+                self.indents.pop();
+                self.block_separator = true;
+                Ok(())
+            }
+            TagEnd::CodeBlock => {
+                self.color_pop()?;
+                self.line_end()?;
+                self.indents.pop();
+                self.block_separator = true;
+                Ok(())
+            }
+            TagEnd::List(_) => {
+                self.list_numbers.pop();
+                if self.list_numbers.is_empty() {
+                    self.block_separator = true;
+                }
+                Ok(())
+            }
+            TagEnd::Item => {
+                self.line_end()?;
+                self.indents.pop();
+                Ok(())
+            }
+            TagEnd::Emphasis | TagEnd::Strong => self.color_pop(),
+            TagEnd::Link | TagEnd::Image => {
+                self.color_pop()?;
+                match self.link_urls.pop() {
+                    Some(link_url) if !link_url.is_empty() => {
+                        self.write_text(&format!(" ({link_url})"))
+                    }
+                    _ => Ok(()),
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Starts a block on a new line, after a blank line if one is needed.
+    fn block_begin(&mut self) -> Result<(), io::Error> {
+        self.line_end()?;
+        if self.block_separator {
+            self.write_text("\n")?;
+            self.block_separator = false;
+        }
+        Ok(())
+    }
+
+    /// Ends the current line, if anything has been written on it.
+    fn line_end(&mut self) -> Result<(), io::Error> {
+        if !self.line_start {
+            self.write_text("\n")?;
+        }
+        Ok(())
+    }
+
+    /// Writes text, prefixing each line with the current indentation.
+    fn write_text(&mut self, text: &str) -> Result<(), io::Error> {
+        text.split_inclusive('\n').try_for_each(|line| {
+            let (content, newline) = match line.strip_suffix('\n') {
+                Some(content) => (content, true),
+                None => (line, false),
+            };
+
+            if self.line_start {
+                let indent = self.indents.concat();
+                let indent = if content.is_empty() {
+                    indent.trim_end()
+                } else {
+                    indent.as_str()
+                };
+                self.writer.write_all(indent.as_bytes())?;
+            }
+
+            self.writer.write_all(content.as_bytes())?;
+            self.line_start = newline;
+            if newline {
+                self.writer.write_all(b"\n")?;
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Applies the colour on top of the colours currently applied.
+    fn color_push(&mut self, color_spec: ColorSpec) -> Result<(), io::Error> {
+        let mut color_spec_merged = self.color_specs.last().cloned().unwrap_or_default();
+        if color_spec.fg().is_some() {
+            color_spec_merged.set_fg(color_spec.fg().copied());
+        }
+        if color_spec.bg().is_some() {
+            color_spec_merged.set_bg(color_spec.bg().copied());
+        }
+        if color_spec.bold() {
+            color_spec_merged.set_bold(true);
+        }
+        if color_spec.italic() {
+            color_spec_merged.set_italic(true);
+        }
+        if color_spec.underline() {
+            color_spec_merged.set_underline(true);
+        }
+
+        self.writer.set_color(&color_spec_merged)?;
+        self.color_specs.push(color_spec_merged);
+        Ok(())
+    }
+
+    /// Restores the colours applied before the last [`color_push`].
+    ///
+    /// [`color_push`]: Self::color_push
+    fn color_pop(&mut self) -> Result<(), io::Error> {
+        self.color_specs.pop();
+        match self.color_specs.last() {
+            Some(color_spec) => self.writer.set_color(color_spec),
+            None => self.writer.reset(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use codespan_reporting::term::termcolor::NoColor;
+
+    use super::MarkdownRenderer;
+
+    fn render(markdown: &str) -> String {
+        let mut buffer = NoColor::new(Vec::new());
+        MarkdownRenderer::new()
+            .render(&mut buffer, markdown)
+            .expect("Expected Markdown to render.");
+        String::from_utf8(buffer.into_inner()).expect("Expected output to be UTF-8.")
+    }
+
+    #[test]
+    fn renders_headings_and_paragraphs_separated_by_blank_lines() {
+        assert_eq!(
+            "Heading\n\nFirst line\nsecond line.\n\nNext paragraph.\n",
+            render("# Heading\n\nFirst line\nsecond line.\n\nNext paragraph.")
+        );
+    }
+
+    #[test]
+    fn renders_lists_with_markers() {
+        assert_eq!(
+            "* one\n* two\n  * nested\n\n1. first\n2. second\n",
+            render("* one\n* two\n  * nested\n\n1. first\n2. second\n")
+        );
+    }
+
+    #[test]
+    fn renders_links_with_destination() {
+        assert_eq!(
+            "See the docs (https://docs.rs/srcerr).\n",
+            render("See [the docs](https://docs.rs/srcerr).")
+        );
+    }
+
+    #[test]
+    fn renders_block_quotes_with_prefix() {
+        assert_eq!("> quoted\n> text\n", render("> quoted\n> text"));
+    }
+}
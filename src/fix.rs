@@ -0,0 +1,31 @@
+//! Applies [`Suggestion`]s to fix errors in their files.
+//!
+//! [`Fixer`] collects the [`Applicability::MachineApplicable`] suggestions from
+//! a batch of [`SourceError`]s. Suggestions whose spans overlap a suggestion
+//! collected earlier are not applied, and are reported as
+//! [`Fixer::conflicts`], so the errors can be fixed by running the tool again.
+//!
+//! [`Fixer::apply`] applies the suggestions to each file's source in a single
+//! pass, returning a [`FileFix`] per file with the fixed source, and a unified
+//! diff for dry runs.
+//!
+//! ```rust,ignore
+//! let fixer = Fixer::new().with_source_errors(&source_errors);
+//! for file_fix in fixer.apply(&files)? {
+//!     if dry_run {
+//!         print!("{}", file_fix.diff());
+//!     } else {
+//!         std::fs::write(&file_fix.name, &file_fix.source_fixed)?;
+//!     }
+//! }
+//! ```
+//!
+//! [`Applicability::MachineApplicable`]: crate::model::Applicability::MachineApplicable
+//! [`SourceError`]: crate::SourceError
+//! [`Suggestion`]: crate::model::Suggestion
+
+pub use self::{file_fix::FileFix, fix_error::FixError, fixer::Fixer};
+
+mod file_fix;
+mod fix_error;
+mod fixer;
@@ -0,0 +1,397 @@
+use std::ops::Range;
+
+use crate::{fix::FixError, model::Suggestion};
+
+/// Number of unchanged lines shown around each change in a unified diff.
+const CONTEXT_LINES: usize = 3;
+
+/// Fixed source of a file, with the suggestions applied to it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FileFix<FileId> {
+    /// ID of the file.
+    pub file_id: FileId,
+    /// Name of the file.
+    pub name: String,
+    /// Source of the file before the suggestions are applied.
+    pub source: String,
+    /// Source of the file after the suggestions are applied.
+    pub source_fixed: String,
+    /// Suggestions applied to the file, ordered by their span.
+    pub suggestions: Vec<Suggestion<FileId>>,
+}
+
+impl<FileId> FileFix<FileId> {
+    /// Returns a `FileFix` with the suggestions applied to the source.
+    ///
+    /// Returns an error if a suggestion's range is not within the source, or
+    /// if two suggestions' ranges overlap.
+    ///
+    /// # Parameters
+    ///
+    /// * `file_id`: ID of the file.
+    /// * `name`: Name of the file.
+    /// * `source`: Source of the file before the suggestions are applied.
+    /// * `suggestions`: Suggestions to apply to the file.
+    pub fn new(
+        file_id: FileId,
+        name: String,
+        source: String,
+        mut suggestions: Vec<Suggestion<FileId>>,
+    ) -> Result<Self, FixError> {
+        suggestions
+            .sort_by_key(|suggestion| (suggestion.span.range.start, suggestion.span.range.end));
+        if let Some(suggestion) = suggestions
+            .iter()
+            .find(|suggestion| source.get(suggestion.span.range.clone()).is_none())
+        {
+            return Err(FixError::RangeInvalid {
+                file: name,
+                range: suggestion.span.range.clone(),
+            });
+        }
+        if let Some((first, second)) = suggestions
+            .windows(2)
+            .map(|pair| (&pair[0], &pair[1]))
+            .find(|(first, second)| first.span.range.end > second.span.range.start)
+        {
+            return Err(FixError::SuggestionsOverlap {
+                file: name,
+                first: first.span.range.clone(),
+                second: second.span.range.clone(),
+            });
+        }
+
+        let source_fixed = apply(&source, 0..source.len(), &suggestions);
+
+        Ok(Self {
+            file_id,
+            name,
+            source,
+            source_fixed,
+            suggestions,
+        })
+    }
+
+    /// Returns a unified diff between the source and the fixed source.
+    ///
+    /// The diff is empty when the suggestions do not change the source.
+    pub fn diff(&self) -> String {
+        let line_count = line_count(&self.source);
+        let line_starts = line_starts(&self.source);
+        let line_byte = |line: usize| line_starts.get(line).copied().unwrap_or(self.source.len());
+
+        // Lines changed by the suggestions, joining suggestions on the same lines.
+        let mut changes = Vec::<Change<'_, FileId>>::new();
+        self.suggestions.iter().for_each(|suggestion| {
+            let range = &suggestion.span.range;
+            let line_start = line_index(&self.source, range.start).min(line_count);
+            let line_last = if range.end > range.start {
+                line_index(&self.source, range.end - 1)
+            } else {
+                line_start
+            };
+            let line_end = (line_last + 1).min(line_count);
+
+            match changes.last_mut() {
+                Some(change) if line_start < change.lines.end.max(change.lines.start + 1) => {
+                    change.lines.end = change.lines.end.max(line_end);
+                    change.suggestions.push(suggestion);
+                }
+                _ => changes.push(Change {
+                    lines: line_start..line_end,
+                    suggestions: vec![suggestion],
+                }),
+            }
+        });
+
+        changes.retain(|change| {
+            let bytes = line_byte(change.lines.start)..line_byte(change.lines.end);
+            self.source[bytes.clone()] != apply(&self.source, bytes, &change.suggestions)
+        });
+
+        // Group changes into hunks, joining hunks whose context would overlap.
+        let mut hunks = Vec::<Vec<Change<'_, FileId>>>::new();
+        changes
+            .into_iter()
+            .for_each(|change| match hunks.last_mut() {
+                Some(hunk)
+                    if hunk
+                        .last()
+                        .map(|change_last| {
+                            change.lines.start <= change_last.lines.end + 2 * CONTEXT_LINES
+                        })
+                        .unwrap_or(false) =>
+                {
+                    hunk.push(change)
+                }
+                _ => hunks.push(vec![change]),
+            });
+
+        let mut diff = String::new();
+        let mut line_offset = 0isize;
+        hunks.iter().for_each(|hunk| {
+            let (first, last) = match (hunk.first(), hunk.last()) {
+                (Some(first), Some(last)) => (first, last),
+                _ => return,
+            };
+            let context_start = first.lines.start.saturating_sub(CONTEXT_LINES);
+            let context_end = (last.lines.end + CONTEXT_LINES).min(line_count);
+
+            let mut body = Vec::<String>::new();
+            let mut lines_before = 0;
+            let mut lines_after = 0;
+            let mut line = context_start;
+            hunk.iter().for_each(|change| {
+                let context = push_lines(
+                    &mut body,
+                    ' ',
+                    &self.source[line_byte(line)..line_byte(change.lines.start)],
+                );
+                lines_before += context;
+                lines_after += context;
+
+                let bytes = line_byte(change.lines.start)..line_byte(change.lines.end);
+                let text_before = &self.source[bytes.clone()];
+                let text_after = apply(&self.source, bytes, &change.suggestions);
+                lines_before += push_lines(&mut body, '-', text_before);
+                lines_after += push_lines(&mut body, '+', &text_after);
+
+                line = change.lines.end;
+            });
+            let context = push_lines(
+                &mut body,
+                ' ',
+                &self.source[line_byte(line)..line_byte(context_end)],
+            );
+            lines_before += context;
+            lines_after += context;
+
+            let start_before = hunk_start(context_start, lines_before);
+            let start_after =
+                hunk_start((context_start as isize + line_offset) as usize, lines_after);
+            line_offset += lines_after as isize - lines_before as isize;
+
+            if diff.is_empty() {
+                diff.push_str(&format!("--- a/{}\n+++ b/{}\n", self.name, self.name));
+            }
+            diff.push_str(&format!(
+                "@@ -{},{} +{},{} @@\n",
+                start_before, lines_before, start_after, lines_after
+            ));
+            body.iter().for_each(|line| {
+                diff.push_str(line);
+                diff.push('\n');
+            });
+        });
+
+        diff
+    }
+}
+
+/// Lines changed by one or more suggestions.
+struct Change<'s, FileId> {
+    /// Line indices of the changed lines, `end` is exclusive.
+    lines: Range<usize>,
+    /// Suggestions within the lines.
+    suggestions: Vec<&'s Suggestion<FileId>>,
+}
+
+/// Returns the text in the byte range with the suggestions within it applied.
+fn apply<FileId, S>(source: &str, bytes: Range<usize>, suggestions: &[S]) -> String
+where
+    S: std::borrow::Borrow<Suggestion<FileId>>,
+{
+    let mut text = String::with_capacity(bytes.len());
+    let mut position = bytes.start;
+    suggestions.iter().for_each(|suggestion| {
+        let suggestion = suggestion.borrow();
+        text.push_str(&source[position..suggestion.span.range.start]);
+        text.push_str(&suggestion.replacement);
+        position = suggestion.span.range.end;
+    });
+    text.push_str(&source[position..bytes.end]);
+    text
+}
+
+/// Pushes each line of the text with the marker, returning the number of
+/// lines.
+fn push_lines(body: &mut Vec<String>, marker: char, text: &str) -> usize {
+    let mut count = 0;
+    text.split_inclusive('\n').for_each(|line| {
+        count += 1;
+        match line.strip_suffix('\n') {
+            Some(line) => body.push(format!("{}{}", marker, line.trim_end_matches('\r'))),
+            None => {
+                body.push(format!("{}{}", marker, line));
+                body.push(String::from("\\ No newline at end of file"));
+            }
+        }
+    });
+    count
+}
+
+/// Returns the 1-based start line of a hunk, which is the line before the hunk
+/// when it is empty.
+fn hunk_start(line_index: usize, line_count: usize) -> usize {
+    if line_count == 0 {
+        line_index
+    } else {
+        line_index + 1
+    }
+}
+
+/// Returns the byte index of the start of each line.
+fn line_starts(source: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(source.match_indices('\n').map(|(index, _)| index + 1))
+        .collect()
+}
+
+/// Returns the 0-based index of the line containing the byte index.
+fn line_index(source: &str, byte_index: usize) -> usize {
+    source.as_bytes()[..byte_index]
+        .iter()
+        .filter(|byte| **byte == b'\n')
+        .count()
+}
+
+/// Returns the number of lines in the source.
+fn line_count(source: &str) -> usize {
+    let newline_count = line_index(source, source.len());
+    if source.is_empty() || source.ends_with('\n') {
+        newline_count
+    } else {
+        newline_count + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FileFix;
+    use crate::{
+        fix::FixError,
+        model::{Applicability, Span, Suggestion},
+    };
+
+    fn file_fix(source: &str, suggestions: &[(std::ops::Range<usize>, &str)]) -> FileFix<usize> {
+        let suggestions = suggestions
+            .iter()
+            .map(|(range, replacement)| {
+                Suggestion::new(
+                    Span::new(0, range.clone()),
+                    *replacement,
+                    Applicability::MachineApplicable,
+                )
+            })
+            .collect();
+        FileFix::new(
+            0,
+            String::from("config.yaml"),
+            String::from(source),
+            suggestions,
+        )
+        .expect("Expected suggestions to be within the source.")
+    }
+
+    #[test]
+    fn diff_renders_hunks_with_context() {
+        let source = "a: 1\nb: 2\nc: 3\nd: 4\ne: 5\nf: 6\ng: 7\nh: 8\ni: 9\nj: 10\nk: 11\nl: 12\n";
+        let file_fix = file_fix(
+            source,
+            &[(60..62, "twelve"), (3..4, "one"), (13..14, "three")],
+        );
+
+        let expected = [
+            "--- a/config.yaml",
+            "+++ b/config.yaml",
+            "@@ -1,6 +1,6 @@",
+            "-a: 1",
+            "+a: one",
+            " b: 2",
+            "-c: 3",
+            "+c: three",
+            " d: 4",
+            " e: 5",
+            " f: 6",
+            "@@ -9,4 +9,4 @@",
+            " i: 9",
+            " j: 10",
+            " k: 11",
+            "-l: 12",
+            "+l: twelve",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(expected, file_fix.diff());
+    }
+
+    #[test]
+    fn diff_renders_inserted_and_removed_lines() {
+        let file_fix = file_fix(
+            "a: 1\nb: 2\nc: 3",
+            &[(0..0, "# header\n"), (5..10, ""), (14..14, "\n")],
+        );
+
+        let expected = [
+            "--- a/config.yaml",
+            "+++ b/config.yaml",
+            "@@ -1,3 +1,3 @@",
+            "-a: 1",
+            "+# header",
+            "+a: 1",
+            "-b: 2",
+            "-c: 3",
+            "\\ No newline at end of file",
+            "+c: 3",
+            "",
+        ]
+        .join("\n");
+        assert_eq!("# header\na: 1\nc: 3\n", file_fix.source_fixed);
+        assert_eq!(expected, file_fix.diff());
+    }
+
+    #[test]
+    fn diff_is_empty_when_source_is_unchanged() {
+        let file_fix = file_fix("a: 1\n", &[(3..4, "1")]);
+
+        assert_eq!("", file_fix.diff());
+    }
+
+    #[test]
+    fn new_rejects_range_outside_source() {
+        let result = FileFix::new(
+            0,
+            String::from("config.yaml"),
+            String::from("a: 1\n"),
+            vec![Suggestion::new(
+                Span::new(0, 3..9),
+                "2",
+                Applicability::MachineApplicable,
+            )],
+        );
+
+        assert!(matches!(
+            result,
+            Err(FixError::RangeInvalid { file, range }) if file == "config.yaml" && range == (3..9)
+        ));
+    }
+
+    #[test]
+    fn new_rejects_overlapping_suggestions() {
+        let result = FileFix::new(
+            0,
+            String::from("config.yaml"),
+            String::from("abcdefgh"),
+            vec![
+                Suggestion::new(Span::new(0, 2..3), "Y", Applicability::MachineApplicable),
+                Suggestion::new(Span::new(0, 1..5), "X", Applicability::MachineApplicable),
+            ],
+        );
+
+        assert!(matches!(
+            result,
+            Err(FixError::SuggestionsOverlap { file, first, second })
+                if file == "config.yaml" && first == (1..5) && second == (2..3)
+        ));
+    }
+}
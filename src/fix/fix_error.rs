@@ -0,0 +1,67 @@
+use std::{fmt, ops::Range};
+
+use codespan_reporting::files;
+
+/// Error when applying suggestions to files.
+#[derive(Debug)]
+pub enum FixError {
+    /// The suggestion's range is outside the file, or not on a character
+    /// boundary.
+    RangeInvalid {
+        /// Name of the file.
+        file: String,
+        /// Byte range of the suggestion.
+        range: Range<usize>,
+    },
+    /// Two suggestions for the same file have overlapping ranges.
+    SuggestionsOverlap {
+        /// Name of the file.
+        file: String,
+        /// Byte range of the suggestion that starts first.
+        first: Range<usize>,
+        /// Byte range of the suggestion that overlaps it.
+        second: Range<usize>,
+    },
+    /// Failed to read the file's name or source.
+    Files {
+        /// The underlying error.
+        error: files::Error,
+    },
+}
+
+impl fmt::Display for FixError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::RangeInvalid { file, range } => write!(
+                f,
+                "Suggestion range `{:?}` is not within `{}`, or not on a character boundary.",
+                range, file
+            ),
+            Self::SuggestionsOverlap {
+                file,
+                first,
+                second,
+            } => write!(
+                f,
+                "Suggestion ranges `{:?}` and `{:?}` overlap in `{}`.",
+                first, second, file
+            ),
+            Self::Files { .. } => write!(f, "Failed to read file to fix."),
+        }
+    }
+}
+
+impl std::error::Error for FixError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::RangeInvalid { .. } | Self::SuggestionsOverlap { .. } => None,
+            Self::Files { error } => Some(error),
+        }
+    }
+}
+
+impl From<files::Error> for FixError {
+    fn from(error: files::Error) -> Self {
+        Self::Files { error }
+    }
+}
@@ -0,0 +1,278 @@
+use std::ops::Range;
+
+use codespan_reporting::files::Files;
+
+use crate::{
+    fix::{FileFix, FixError},
+    model::{Applicability, ErrorCode, ErrorDetail, SourceError, Suggestion},
+};
+
+/// Collects machine applicable suggestions, and applies them to their files.
+///
+/// Suggestions are kept in the order they are collected. A suggestion whose
+/// span overlaps a suggestion collected earlier in the same file is recorded
+/// as a conflict instead, and identical suggestions are only applied once.
+///
+/// # Examples
+///
+/// ```rust
+/// use srcerr::{
+///     codespan_reporting::files::SimpleFiles,
+///     fix::Fixer,
+///     model::{Applicability, Span, Suggestion},
+/// };
+///
+/// let mut files = SimpleFiles::new();
+/// let file_id = files.add("config.yaml", "chosen: \"ghi\"\n");
+///
+/// let mut fixer = Fixer::new();
+/// fixer.push(Suggestion::new(
+///     Span::new(file_id, 8..13),
+///     "\"abc\"",
+///     Applicability::MachineApplicable,
+/// ));
+///
+/// let file_fixes = fixer.apply(&files)?;
+/// assert_eq!("chosen: \"abc\"\n", file_fixes[0].source_fixed);
+/// # Ok::<(), srcerr::fix::FixError>(())
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fixer<FileId> {
+    /// Suggestions to apply, in the order they were collected.
+    suggestions: Vec<Suggestion<FileId>>,
+    /// Suggestions that overlap a suggestion collected earlier.
+    conflicts: Vec<Suggestion<FileId>>,
+}
+
+impl<FileId> Fixer<FileId>
+where
+    FileId: Copy + PartialEq,
+{
+    /// Returns a new `Fixer` without any suggestions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Collects the machine applicable suggestions of each source error.
+    #[must_use]
+    pub fn with_source_errors<'a, 'files, Ec, Ed, Fs, I>(mut self, source_errors: I) -> Self
+    where
+        'files: 'a,
+        Ec: ErrorCode + 'a,
+        Ed: ErrorDetail<'files, Files = Fs> + 'a,
+        Fs: Files<'files, FileId = FileId> + 'files,
+        I: IntoIterator<Item = &'a SourceError<'files, Ec, Ed, Fs>>,
+    {
+        source_errors.into_iter().for_each(|source_error| {
+            source_error
+                .detail
+                .suggestions()
+                .into_iter()
+                .for_each(|suggestion| self.push(suggestion));
+        });
+        self
+    }
+
+    /// Collects the suggestion if it is machine applicable.
+    ///
+    /// Suggestions that overlap a suggestion collected earlier are recorded as
+    /// [`conflicts`](Self::conflicts).
+    pub fn push(&mut self, suggestion: Suggestion<FileId>) {
+        if suggestion.applicability != Applicability::MachineApplicable {
+            return;
+        }
+
+        let mut suggestions_same_file = self
+            .suggestions
+            .iter()
+            .filter(|existing| existing.span.file_id == suggestion.span.file_id);
+        if suggestions_same_file
+            .clone()
+            .any(|existing| same_edit(existing, &suggestion))
+        {
+            return;
+        }
+        if suggestions_same_file
+            .any(|existing| ranges_overlap(&existing.span.range, &suggestion.span.range))
+        {
+            self.conflicts.push(suggestion);
+        } else {
+            self.suggestions.push(suggestion);
+        }
+    }
+
+    /// Returns the suggestions that will be applied.
+    pub fn suggestions(&self) -> &[Suggestion<FileId>] {
+        &self.suggestions
+    }
+
+    /// Returns the suggestions that were not collected, because they overlap
+    /// a suggestion collected earlier.
+    pub fn conflicts(&self) -> &[Suggestion<FileId>] {
+        &self.conflicts
+    }
+
+    /// Applies the suggestions, returning the fixed source of each file.
+    ///
+    /// Files are returned in the order their first suggestion was collected.
+    /// The files themselves are not modified.
+    pub fn apply<'files, Fs>(&self, files: &'files Fs) -> Result<Vec<FileFix<FileId>>, FixError>
+    where
+        Fs: Files<'files, FileId = FileId>,
+    {
+        let mut suggestions_by_file = Vec::<(FileId, Vec<Suggestion<FileId>>)>::new();
+        self.suggestions.iter().for_each(|suggestion| {
+            match suggestions_by_file
+                .iter_mut()
+                .find(|(file_id, _)| *file_id == suggestion.span.file_id)
+            {
+                Some((_, suggestions)) => suggestions.push(suggestion.clone()),
+                None => {
+                    suggestions_by_file.push((suggestion.span.file_id, vec![suggestion.clone()]))
+                }
+            }
+        });
+
+        suggestions_by_file
+            .into_iter()
+            .map(|(file_id, suggestions)| {
+                let name = files.name(file_id)?.to_string();
+                let source = String::from(files.source(file_id)?.as_ref());
+                FileFix::new(file_id, name, source, suggestions)
+            })
+            .collect()
+    }
+}
+
+impl<FileId> Default for Fixer<FileId> {
+    fn default() -> Self {
+        Self {
+            suggestions: Vec::new(),
+            conflicts: Vec::new(),
+        }
+    }
+}
+
+/// Returns whether the suggestions make the same edit.
+fn same_edit<FileId>(a: &Suggestion<FileId>, b: &Suggestion<FileId>) -> bool {
+    a.span.range == b.span.range && a.replacement == b.replacement
+}
+
+/// Returns whether applying both edits is ambiguous.
+///
+/// Edits overlap when their ranges share a byte, or when both insert text at
+/// the same position.
+fn ranges_overlap(a: &Range<usize>, b: &Range<usize>) -> bool {
+    let both_insert_at_same_position = a.is_empty() && b.is_empty() && a.start == b.start;
+    (a.start < b.end && b.start < a.end) || both_insert_at_same_position
+}
+
+#[cfg(test)]
+mod tests {
+    use codespan_reporting::{diagnostic::Severity, files::SimpleFiles};
+
+    use super::Fixer;
+    use crate::{
+        model::{Applicability, Span, Suggestion},
+        test_support, SourceErrors,
+    };
+
+    fn suggestion(
+        file_id: usize,
+        range: std::ops::Range<usize>,
+        replacement: &str,
+    ) -> Suggestion<usize> {
+        Suggestion::new(
+            Span::new(file_id, range),
+            replacement,
+            Applicability::MachineApplicable,
+        )
+    }
+
+    #[test]
+    fn push_records_overlapping_suggestions_as_conflicts() {
+        let mut fixer = Fixer::new();
+        fixer.push(suggestion(0, 4..8, "a"));
+        fixer.push(suggestion(0, 6..10, "b"));
+        fixer.push(suggestion(0, 4..8, "a"));
+        fixer.push(suggestion(0, 8..8, "c"));
+        fixer.push(suggestion(0, 8..8, "d"));
+        fixer.push(suggestion(1, 6..10, "e"));
+
+        assert_eq!(
+            &[
+                suggestion(0, 4..8, "a"),
+                suggestion(0, 8..8, "c"),
+                suggestion(1, 6..10, "e"),
+            ],
+            fixer.suggestions()
+        );
+        assert_eq!(
+            &[suggestion(0, 6..10, "b"), suggestion(0, 8..8, "d")],
+            fixer.conflicts()
+        );
+    }
+
+    #[test]
+    fn push_ignores_suggestions_that_are_not_machine_applicable() {
+        let mut fixer = Fixer::new();
+        fixer.push(Suggestion::new(
+            Span::new(0, 0..1),
+            "a",
+            Applicability::MaybeIncorrect,
+        ));
+        fixer.push(Suggestion::new(
+            Span::new(0, 2..3),
+            "<value>",
+            Applicability::HasPlaceholders,
+        ));
+
+        assert!(fixer.suggestions().is_empty());
+    }
+
+    #[test]
+    fn apply_fixes_each_file_in_one_pass() -> Result<(), Box<dyn std::error::Error>> {
+        let mut files = SimpleFiles::new();
+        let file_id_a = files.add("a.yaml", "one: 1\ntwo: 2\nthree: 3\n");
+        let file_id_b = files.add("b.yaml", "x: y");
+
+        let mut fixer = Fixer::new();
+        fixer.push(suggestion(file_id_b, 3..4, "z"));
+        fixer.push(suggestion(file_id_a, 21..22, "3.0"));
+        fixer.push(suggestion(file_id_a, 5..6, "1.0"));
+        fixer.push(suggestion(file_id_a, 0..0, "# numbers\n"));
+
+        let file_fixes = fixer.apply(&files)?;
+
+        assert_eq!(
+            vec![
+                ("b.yaml", "x: z"),
+                ("a.yaml", "# numbers\none: 1.0\ntwo: 2\nthree: 3.0\n")
+            ],
+            file_fixes
+                .iter()
+                .map(|file_fix| (file_fix.name.as_str(), file_fix.source_fixed.as_str()))
+                .collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn with_source_errors_collects_suggestions() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let mut source_error = test_support::source_error_invalid_value(file_id, Severity::Error);
+        source_error
+            .detail
+            .suggestions
+            .push(suggestion(file_id, 44..49, "\"abc\""));
+        let source_errors = SourceErrors::from(vec![source_error.clone(), source_error]);
+
+        let fixer = Fixer::new().with_source_errors(&source_errors);
+        let file_fixes = fixer.apply(&files)?;
+
+        assert_eq!(1, fixer.suggestions().len());
+        assert!(fixer.conflicts().is_empty());
+        assert!(file_fixes[0].source_fixed.contains("chosen: \"abc\""));
+        Ok(())
+    }
+}
@@ -1,6 +1,11 @@
 //! Utility classes for formatting messages.
 
-pub use self::{code::Code, note::Note};
+pub use self::{
+    code::Code, code_parse_error::CodeParseError, conjunction::Conjunction, note::Note,
+};
 
 mod code;
+mod code_parse_error;
+mod conjunction;
+mod edit_distance;
 mod note;
@@ -3,7 +3,7 @@ use std::{
     marker::PhantomData,
 };
 
-use crate::ErrorCode;
+use crate::{fmt::CodeParseError, ErrorCode};
 
 /// Formatting helpers for writing notes with consistent sentences.
 #[derive(Debug)]
@@ -13,6 +13,9 @@ impl<E> Code<E>
 where
     E: ErrorCode,
 {
+    /// Number of digits that error codes are zero padded to.
+    pub const WIDTH: usize = Self::digits(E::ERROR_CODE_MAX);
+
     /// Returns a `String` representing the error code.
     ///
     /// See [`Self::fmt_string`] for a non-allocating version of this.
@@ -35,31 +38,105 @@ where
     /// * `buffer`: The buffer to write into.
     /// * `error_code`: The error code.
     pub fn fmt_string(buffer: &mut String, error_code: E) -> Result<(), fmt::Error> {
-        let digits = Self::digits(E::ERROR_CODE_MAX);
-
         write!(
             buffer,
             "{prefix}{code:0>width$}",
             prefix = E::PREFIX,
             code = error_code.code(),
-            width = digits
+            width = Self::WIDTH
         )
     }
 
+    /// Returns whether the code is within `ERROR_CODE_MAX`.
+    ///
+    /// This is a `const fn`, so it can be used in a compile-time assertion:
+    ///
+    /// ```rust
+    /// # use srcerr::{fmt::Code, ErrorCode};
+    /// # #[derive(Clone, Copy, PartialEq, Eq)]
+    /// # struct MyErrorCode;
+    /// # impl ErrorCode for MyErrorCode {
+    /// #     const ERROR_CODE_MAX: usize = 12;
+    /// #     fn code(self) -> usize { 12 }
+    /// #     fn description(self) -> &'static str { "" }
+    /// # }
+    /// const _: () = assert!(Code::<MyErrorCode>::fits(12));
+    /// ```
+    ///
+    /// Codes above `ERROR_CODE_MAX` are written wider than [`Self::WIDTH`], so
+    /// they are not aligned with other codes. `#[derive(ErrorCode)]` computes
+    /// `ERROR_CODE_MAX` from the codes, so hand-written implementations should
+    /// assert this for each of their codes instead.
+    ///
+    /// # Parameters
+    ///
+    /// * `code`: The error code value.
+    pub const fn fits(code: usize) -> bool {
+        code <= E::ERROR_CODE_MAX
+    }
+
+    /// Returns the error code whose string form is `code`.
+    ///
+    /// This is the inverse of [`Self::string`]. The prefix is compared
+    /// ignoring ASCII case, leading zeros are optional, and surrounding
+    /// whitespace is ignored, so `"E011"`, `"e11"`, and `" E0011 "` all parse
+    /// to the same error code.
+    ///
+    /// Error codes are looked up from [`ErrorCode::all`], so the error code type
+    /// must implement it -- `#[derive(ErrorCode)]` does so automatically.
+    ///
+    /// # Parameters
+    ///
+    /// * `code`: The error code string, e.g. `"E011"`.
+    pub fn parse(code: &str) -> Result<E, CodeParseError>
+    where
+        E: 'static,
+    {
+        let code = code.trim();
+        let digits = code
+            .get(..E::PREFIX.len())
+            .filter(|prefix| prefix.eq_ignore_ascii_case(E::PREFIX))
+            .map(|_| &code[E::PREFIX.len()..])
+            .ok_or_else(|| CodeParseError::PrefixMismatch {
+                code: String::from(code),
+                prefix: E::PREFIX,
+            })?;
+
+        let value = Some(digits)
+            .filter(|digits| !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|digits| digits.parse::<usize>().ok())
+            .ok_or_else(|| CodeParseError::NumberInvalid {
+                code: String::from(code),
+            })?;
+
+        E::all()
+            .iter()
+            .copied()
+            .find(|error_code| error_code.code() == value)
+            .ok_or_else(|| CodeParseError::CodeUnknown {
+                code: String::from(code),
+                value,
+            })
+    }
+
     /// Returns the number of digits that the given max value fits into.
-    pub fn digits(value_max: usize) -> usize {
-        // `Integer::log10` pending: <https://github.com/rust-lang/rust/pull/80918>
-        // `FloatToInt` pending: <https://github.com/rust-lang/rust/issues/67057>
-        //
-        // +1 is because error codes should generally start from 1, not 0.
-        (value_max as f32).log10().floor() as usize + 1
+    ///
+    /// `0` takes one digit.
+    pub const fn digits(value_max: usize) -> usize {
+        let mut digits = 1;
+        let mut value = value_max;
+        while value >= 10 {
+            value /= 10;
+            digits += 1;
+        }
+        digits
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::Code;
-    use crate::ErrorCode;
+    use crate::{fmt::CodeParseError, ErrorCode};
 
     #[test]
     fn zero_pads_error_code_log_10_exact() {
@@ -75,6 +152,108 @@ mod tests {
         assert_eq!("E11", code_string);
     }
 
+    #[test]
+    fn digits_is_exact_around_powers_of_ten() {
+        assert_eq!(1, Code::<Error11Max100>::digits(0));
+        assert_eq!(1, Code::<Error11Max100>::digits(9));
+
+        let mut power = 10usize;
+        let mut digits_expected = 2;
+        loop {
+            assert_eq!(
+                digits_expected - 1,
+                Code::<Error11Max100>::digits(power - 1),
+                "digits({})",
+                power - 1
+            );
+            assert_eq!(
+                digits_expected,
+                Code::<Error11Max100>::digits(power),
+                "digits({power})"
+            );
+            assert_eq!(
+                digits_expected,
+                Code::<Error11Max100>::digits(power + 1),
+                "digits({})",
+                power + 1
+            );
+
+            match power.checked_mul(10) {
+                Some(power_next) => power = power_next,
+                None => break,
+            }
+            digits_expected += 1;
+        }
+
+        assert_eq!(
+            usize::MAX.to_string().len(),
+            Code::<Error11Max100>::digits(usize::MAX)
+        );
+    }
+
+    #[test]
+    fn string_does_not_panic_when_code_exceeds_max() {
+        // Implementations are expected to assert `fits` for each code instead.
+        assert!(!Code::<Error11Max9>::fits(11));
+        assert!(Code::string(Error11Max9).starts_with('E'));
+    }
+
+    #[test]
+    fn width_and_fits_are_const() {
+        const WIDTH: usize = Code::<Error11Max100>::WIDTH;
+        const FITS: bool = Code::<Error11Max99>::fits(99);
+        const FITS_NOT: bool = Code::<Error11Max99>::fits(100);
+
+        assert_eq!(3, WIDTH);
+        assert_eq!((true, false), (FITS, FITS_NOT));
+    }
+
+    #[test]
+    fn parse_tolerates_missing_zero_padding_and_case() {
+        assert_eq!(Ok(Error11Max100), Code::parse("E011"));
+        assert_eq!(Ok(Error11Max100), Code::parse("e11"));
+        assert_eq!(Ok(Error11Max100), Code::parse(" E0011\n"));
+    }
+
+    #[test]
+    fn parse_rejects_prefix_mismatch() {
+        assert_eq!(
+            Err(CodeParseError::PrefixMismatch {
+                code: String::from("W011"),
+                prefix: "E",
+            }),
+            Code::<Error11Max100>::parse("W011")
+        );
+    }
+
+    #[test]
+    fn parse_rejects_invalid_number() {
+        ["E", "E+11", "E1a", "E99999999999999999999999"]
+            .iter()
+            .for_each(|code| {
+                assert_eq!(
+                    Err(CodeParseError::NumberInvalid {
+                        code: String::from(*code),
+                    }),
+                    Code::<Error11Max100>::parse(code)
+                );
+            });
+    }
+
+    #[test]
+    fn parse_rejects_unknown_code() {
+        let error = Code::<Error11Max100>::parse("E012").expect_err("Expected E012 to be unknown.");
+
+        assert_eq!(
+            CodeParseError::CodeUnknown {
+                code: String::from("E012"),
+                value: 12,
+            },
+            error
+        );
+        assert_eq!("`E012` is not a valid error code.", error.to_string());
+    }
+
     #[derive(Clone, Copy, Debug, PartialEq, Eq)]
     struct Error11Max99;
     impl ErrorCode for Error11Max99 {
@@ -101,5 +280,23 @@ mod tests {
         fn description(self) -> &'static str {
             ""
         }
+
+        fn all() -> &'static [Self] {
+            &[Self]
+        }
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    struct Error11Max9;
+    impl ErrorCode for Error11Max9 {
+        const ERROR_CODE_MAX: usize = 9;
+
+        fn code(self) -> usize {
+            11
+        }
+
+        fn description(self) -> &'static str {
+            ""
+        }
     }
 }
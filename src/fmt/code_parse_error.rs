@@ -0,0 +1,45 @@
+use std::fmt;
+
+/// Error when parsing an error code string with [`Code::parse`].
+///
+/// [`Code::parse`]: crate::fmt::Code::parse
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CodeParseError {
+    /// The string does not begin with the error code prefix.
+    PrefixMismatch {
+        /// The error code string that was parsed.
+        code: String,
+        /// The expected prefix, e.g. `"E"`.
+        prefix: &'static str,
+    },
+    /// The characters after the prefix are not a number.
+    NumberInvalid {
+        /// The error code string that was parsed.
+        code: String,
+    },
+    /// The number does not match any error code.
+    CodeUnknown {
+        /// The error code string that was parsed.
+        code: String,
+        /// The parsed number.
+        value: usize,
+    },
+}
+
+impl fmt::Display for CodeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::PrefixMismatch { code, prefix } => write!(
+                f,
+                "`{code}` is not a valid error code, expected it to begin with `{prefix}`."
+            ),
+            Self::NumberInvalid { code } => write!(
+                f,
+                "`{code}` is not a valid error code, expected digits after the prefix."
+            ),
+            Self::CodeUnknown { code, .. } => write!(f, "`{code}` is not a valid error code."),
+        }
+    }
+}
+
+impl std::error::Error for CodeParseError {}
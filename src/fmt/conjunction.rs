@@ -0,0 +1,26 @@
+use std::fmt;
+
+/// Word joining the last item of a list, e.g. "`a`, `b`, or `c`".
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Conjunction {
+    /// Joins items with "and", for lists where every item applies.
+    And,
+    /// Joins items with "or", for lists of alternatives.
+    Or,
+}
+
+impl Conjunction {
+    /// Returns the word for this conjunction.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::And => "and",
+            Self::Or => "or",
+        }
+    }
+}
+
+impl fmt::Display for Conjunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -0,0 +1,60 @@
+/// Returns the Damerau-Levenshtein distance between the strings, counting
+/// insertions, deletions, substitutions, and transpositions of adjacent
+/// characters.
+///
+/// This is the optimal string alignment variant, where each substring is
+/// edited at most once.
+pub(crate) fn damerau_levenshtein(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<char>>();
+    let b = b.chars().collect::<Vec<char>>();
+
+    // Distances for the previous two rows and the current row.
+    let mut row_prev_prev = vec![0; b.len() + 1];
+    let mut row_prev = (0..=b.len()).collect::<Vec<usize>>();
+    let mut row = vec![0; b.len() + 1];
+
+    (1..=a.len()).for_each(|i| {
+        row[0] = i;
+        (1..=b.len()).for_each(|j| {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut distance = (row_prev[j] + 1)
+                .min(row[j - 1] + 1)
+                .min(row_prev[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(row_prev_prev[j - 2] + 1);
+            }
+            row[j] = distance;
+        });
+
+        std::mem::swap(&mut row_prev_prev, &mut row_prev);
+        std::mem::swap(&mut row_prev, &mut row);
+    });
+
+    row_prev[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::damerau_levenshtein;
+
+    #[test]
+    fn counts_insertions_deletions_and_substitutions() {
+        assert_eq!(0, damerau_levenshtein("chosen", "chosen"));
+        assert_eq!(1, damerau_levenshtein("chosen", "chose"));
+        assert_eq!(1, damerau_levenshtein("chose", "chosen"));
+        assert_eq!(1, damerau_levenshtein("chosen", "chasen"));
+        assert_eq!(3, damerau_levenshtein("", "abc"));
+        assert_eq!(3, damerau_levenshtein("kitten", "sitting"));
+    }
+
+    #[test]
+    fn counts_adjacent_transposition_as_one_edit() {
+        assert_eq!(1, damerau_levenshtein("avialable", "available"));
+        assert_eq!(1, damerau_levenshtein("ab", "ba"));
+    }
+
+    #[test]
+    fn counts_characters_not_bytes() {
+        assert_eq!(1, damerau_levenshtein("café", "cafe"));
+    }
+}
@@ -3,11 +3,20 @@ use std::{
     fmt::{self, Write},
 };
 
+use crate::fmt::{edit_distance, Conjunction};
+
 /// Formatting helpers for writing notes with consistent sentences.
 #[derive(Debug)]
 pub struct Note;
 
 impl Note {
+    /// Maximum number of candidates suggested by [`Self::did_you_mean`].
+    pub const DID_YOU_MEAN_MAX: usize = 3;
+
+    /// Maximum number of items written by [`Self::list`], before the rest are
+    /// summarized as "and N more".
+    pub const LIST_MAX: usize = 5;
+
     /// Returns a note suggesting valid expressions.
     ///
     /// See [`Self::fmt_valid_exprs`] for a non-allocating version of this.
@@ -45,6 +54,404 @@ impl Note {
 
         Ok(())
     }
+
+    /// Returns a note suggesting the candidates closest to the input, if any
+    /// are similar enough.
+    ///
+    /// Candidates are ranked by their Damerau-Levenshtein distance to the
+    /// input, and are similar enough when at most one edit is needed for every
+    /// three characters of the input. Up to [`Self::DID_YOU_MEAN_MAX`]
+    /// candidates are suggested, with equally close candidates kept in their
+    /// original order.
+    ///
+    /// * One candidate: "did you mean `abc`?"
+    /// * Many candidates: "did you mean `abc`, `abd`, or `abe`?"
+    ///
+    /// See [`Self::fmt_did_you_mean`] for a non-allocating version of this.
+    ///
+    /// # Parameters
+    ///
+    /// * `input`: The value the user provided.
+    /// * `candidates`: Valid values the user may have meant.
+    pub fn did_you_mean<'s>(
+        input: &str,
+        candidates: impl Iterator<Item = Cow<'s, str>>,
+    ) -> Result<Option<String>, fmt::Error> {
+        let mut buffer = String::with_capacity(128);
+        let written = Self::fmt_did_you_mean(&mut buffer, input, candidates)?;
+
+        Ok(Some(buffer).filter(|_| written))
+    }
+
+    /// Writes a note suggesting the candidates closest to the input into the
+    /// buffer, returning whether any candidate was similar enough.
+    ///
+    /// Nothing is written when no candidate is similar enough, or when the
+    /// input is empty. See [`Self::did_you_mean`] for how candidates are
+    /// ranked, and for a version that allocates a `String`.
+    ///
+    /// # Parameters
+    ///
+    /// * `buffer`: The buffer to write into.
+    /// * `input`: The value the user provided.
+    /// * `candidates`: Valid values the user may have meant.
+    pub fn fmt_did_you_mean<'s>(
+        buffer: &mut String,
+        input: &str,
+        candidates: impl Iterator<Item = Cow<'s, str>>,
+    ) -> Result<bool, fmt::Error> {
+        // Any one character candidate is within one edit of an empty input.
+        if input.is_empty() {
+            return Ok(false);
+        }
+
+        let input_len = input.chars().count();
+        let distance_max = input_len.max(3) / 3;
+
+        // Closest candidates, ordered by distance.
+        let mut closest: [Option<(usize, Cow<'s, str>)>; Self::DID_YOU_MEAN_MAX] =
+            Default::default();
+        candidates
+            .filter(|candidate| candidate != input)
+            .filter(|candidate| candidate.chars().count().abs_diff(input_len) <= distance_max)
+            .for_each(|candidate| {
+                let distance = edit_distance::damerau_levenshtein(input, &candidate);
+                if distance > distance_max {
+                    return;
+                }
+                let index = closest.iter().position(|closest_candidate| {
+                    closest_candidate
+                        .as_ref()
+                        .map(|(closest_distance, _)| distance < *closest_distance)
+                        .unwrap_or(true)
+                });
+                if let Some(index) = index {
+                    closest[index..].rotate_right(1);
+                    closest[index] = Some((distance, candidate));
+                }
+            });
+
+        if closest[0].is_none() {
+            return Ok(false);
+        }
+
+        write!(buffer, "did you mean ")?;
+        Self::fmt_list(
+            buffer,
+            closest.iter().flatten().map(|(_, candidate)| candidate),
+            Conjunction::Or,
+        )?;
+        write!(buffer, "?")?;
+
+        Ok(true)
+    }
+
+    /// Returns a note that a value is outside a range, e.g. "expected a value
+    /// between 1 and 10".
+    ///
+    /// See [`Self::fmt_value_between`] for a non-allocating version of this.
+    ///
+    /// # Parameters
+    ///
+    /// * `min`: The smallest valid value.
+    /// * `max`: The largest valid value.
+    pub fn value_between(
+        min: impl fmt::Display,
+        max: impl fmt::Display,
+    ) -> Result<String, fmt::Error> {
+        let mut buffer = String::with_capacity(64);
+        Self::fmt_value_between(&mut buffer, min, max)?;
+
+        Ok(buffer)
+    }
+
+    /// Writes a note that a value is outside a range into the buffer.
+    ///
+    /// See [`Self::value_between`] for a version that allocates a `String`.
+    ///
+    /// # Parameters
+    ///
+    /// * `buffer`: The buffer to write into.
+    /// * `min`: The smallest valid value.
+    /// * `max`: The largest valid value.
+    pub fn fmt_value_between(
+        buffer: &mut String,
+        min: impl fmt::Display,
+        max: impl fmt::Display,
+    ) -> Result<(), fmt::Error> {
+        write!(buffer, "expected a value between {min} and {max}")
+    }
+
+    /// Returns a note that a value has the wrong type, e.g. "expected type
+    /// `integer`, found `string`".
+    ///
+    /// See [`Self::fmt_type_mismatch`] for a non-allocating version of this.
+    ///
+    /// # Parameters
+    ///
+    /// * `expected`: Name of the expected type.
+    /// * `found`: Name of the type that was found.
+    pub fn type_mismatch(
+        expected: impl fmt::Display,
+        found: impl fmt::Display,
+    ) -> Result<String, fmt::Error> {
+        let mut buffer = String::with_capacity(64);
+        Self::fmt_type_mismatch(&mut buffer, expected, found)?;
+
+        Ok(buffer)
+    }
+
+    /// Writes a note that a value has the wrong type into the buffer.
+    ///
+    /// See [`Self::type_mismatch`] for a version that allocates a `String`.
+    ///
+    /// # Parameters
+    ///
+    /// * `buffer`: The buffer to write into.
+    /// * `expected`: Name of the expected type.
+    /// * `found`: Name of the type that was found.
+    pub fn fmt_type_mismatch(
+        buffer: &mut String,
+        expected: impl fmt::Display,
+        found: impl fmt::Display,
+    ) -> Result<(), fmt::Error> {
+        write!(buffer, "expected type `{expected}`, found `{found}`")
+    }
+
+    /// Returns a note that a value is too long, e.g. "value must be at most 8
+    /// characters (found 12)".
+    ///
+    /// See [`Self::fmt_length_max`] for a non-allocating version of this.
+    ///
+    /// # Parameters
+    ///
+    /// * `max`: The maximum number of characters.
+    /// * `found`: The number of characters in the value.
+    pub fn length_max(max: usize, found: usize) -> Result<String, fmt::Error> {
+        let mut buffer = String::with_capacity(64);
+        Self::fmt_length_max(&mut buffer, max, found)?;
+
+        Ok(buffer)
+    }
+
+    /// Writes a note that a value is too long into the buffer.
+    ///
+    /// See [`Self::length_max`] for a version that allocates a `String`.
+    ///
+    /// # Parameters
+    ///
+    /// * `buffer`: The buffer to write into.
+    /// * `max`: The maximum number of characters.
+    /// * `found`: The number of characters in the value.
+    pub fn fmt_length_max(buffer: &mut String, max: usize, found: usize) -> Result<(), fmt::Error> {
+        write!(buffer, "value must be at most ")?;
+        Self::fmt_quantity(buffer, max, "character", "characters")?;
+        write!(buffer, " (found {found})")
+    }
+
+    /// Returns a note that a value is too short, e.g. "value must be at least
+    /// 1 character (found 0)".
+    ///
+    /// See [`Self::fmt_length_min`] for a non-allocating version of this.
+    ///
+    /// # Parameters
+    ///
+    /// * `min`: The minimum number of characters.
+    /// * `found`: The number of characters in the value.
+    pub fn length_min(min: usize, found: usize) -> Result<String, fmt::Error> {
+        let mut buffer = String::with_capacity(64);
+        Self::fmt_length_min(&mut buffer, min, found)?;
+
+        Ok(buffer)
+    }
+
+    /// Writes a note that a value is too short into the buffer.
+    ///
+    /// See [`Self::length_min`] for a version that allocates a `String`.
+    ///
+    /// # Parameters
+    ///
+    /// * `buffer`: The buffer to write into.
+    /// * `min`: The minimum number of characters.
+    /// * `found`: The number of characters in the value.
+    pub fn fmt_length_min(buffer: &mut String, min: usize, found: usize) -> Result<(), fmt::Error> {
+        write!(buffer, "value must be at least ")?;
+        Self::fmt_quantity(buffer, min, "character", "characters")?;
+        write!(buffer, " (found {found})")
+    }
+
+    /// Returns a note that a key is missing, e.g. "key `port` is required
+    /// when `host` is set".
+    ///
+    /// See [`Self::fmt_required_when`] for a non-allocating version of this.
+    ///
+    /// # Parameters
+    ///
+    /// * `key`: The key that is required.
+    /// * `key_set`: The key whose presence requires `key`.
+    pub fn required_when(
+        key: impl fmt::Display,
+        key_set: impl fmt::Display,
+    ) -> Result<String, fmt::Error> {
+        let mut buffer = String::with_capacity(64);
+        Self::fmt_required_when(&mut buffer, key, key_set)?;
+
+        Ok(buffer)
+    }
+
+    /// Writes a note that a key is missing into the buffer.
+    ///
+    /// See [`Self::required_when`] for a version that allocates a `String`.
+    ///
+    /// # Parameters
+    ///
+    /// * `buffer`: The buffer to write into.
+    /// * `key`: The key that is required.
+    /// * `key_set`: The key whose presence requires `key`.
+    pub fn fmt_required_when(
+        buffer: &mut String,
+        key: impl fmt::Display,
+        key_set: impl fmt::Display,
+    ) -> Result<(), fmt::Error> {
+        write!(buffer, "key `{key}` is required when `{key_set}` is set")
+    }
+
+    /// Returns a note that values cannot be used together, e.g. "these values
+    /// conflict: `a` and `b`".
+    ///
+    /// See [`Self::fmt_conflicts`] for a non-allocating version of this.
+    ///
+    /// # Parameters
+    ///
+    /// * `values`: The conflicting values.
+    pub fn conflicts<I>(values: I) -> Result<String, fmt::Error>
+    where
+        I: Iterator,
+        I::Item: fmt::Display,
+    {
+        let mut buffer = String::with_capacity(128);
+        Self::fmt_conflicts(&mut buffer, values)?;
+
+        Ok(buffer)
+    }
+
+    /// Writes a note that values cannot be used together into the buffer.
+    ///
+    /// See [`Self::conflicts`] for a version that allocates a `String`.
+    ///
+    /// # Parameters
+    ///
+    /// * `buffer`: The buffer to write into.
+    /// * `values`: The conflicting values.
+    pub fn fmt_conflicts<I>(buffer: &mut String, values: I) -> Result<(), fmt::Error>
+    where
+        I: Iterator,
+        I::Item: fmt::Display,
+    {
+        write!(buffer, "these values conflict: ")?;
+        Self::fmt_list(buffer, values, Conjunction::And)
+    }
+
+    /// Returns the items as a list in backticks, e.g. "`a`, `b`, or `c`".
+    ///
+    /// * Two items are joined without a comma: "`a` or `b`".
+    /// * Items after [`Self::LIST_MAX`] are summarized: "`a`, `b`, `c`, `d`,
+    ///   `e`, and 37 more".
+    ///
+    /// See [`Self::fmt_list`] for a non-allocating version of this.
+    ///
+    /// # Parameters
+    ///
+    /// * `items`: The items to list.
+    /// * `conjunction`: Word joining the last item.
+    pub fn list<I>(items: I, conjunction: Conjunction) -> Result<String, fmt::Error>
+    where
+        I: Iterator,
+        I::Item: fmt::Display,
+    {
+        let mut buffer = String::with_capacity(128);
+        Self::fmt_list(&mut buffer, items, conjunction)?;
+
+        Ok(buffer)
+    }
+
+    /// Writes the items as a list in backticks into the buffer.
+    ///
+    /// See [`Self::list`] for how the items are joined, and for a version that
+    /// allocates a `String`.
+    ///
+    /// # Parameters
+    ///
+    /// * `buffer`: The buffer to write into.
+    /// * `items`: The items to list.
+    /// * `conjunction`: Word joining the last item.
+    pub fn fmt_list<I>(
+        buffer: &mut String,
+        items: I,
+        conjunction: Conjunction,
+    ) -> Result<(), fmt::Error>
+    where
+        I: Iterator,
+        I::Item: fmt::Display,
+    {
+        let mut items = items.peekable();
+        let mut index = 0;
+        while let Some(item) = items.next() {
+            if index == Self::LIST_MAX {
+                let remaining = 1 + items.count();
+                return write!(buffer, ", and {remaining} more");
+            }
+
+            let is_last = items.peek().is_none();
+            match index {
+                0 => {}
+                1 if is_last => write!(buffer, " {conjunction} ")?,
+                _ if is_last => write!(buffer, ", {conjunction} ")?,
+                _ => write!(buffer, ", ")?,
+            }
+            write!(buffer, "`{item}`")?;
+            index += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the count with the singular or plural noun, e.g. "1 character"
+    /// or "3 characters".
+    ///
+    /// See [`Self::fmt_quantity`] for a non-allocating version of this.
+    ///
+    /// # Parameters
+    ///
+    /// * `count`: The number of things.
+    /// * `singular`: The noun when `count` is 1.
+    /// * `plural`: The noun for every other count.
+    pub fn quantity(count: usize, singular: &str, plural: &str) -> Result<String, fmt::Error> {
+        let mut buffer = String::with_capacity(32);
+        Self::fmt_quantity(&mut buffer, count, singular, plural)?;
+
+        Ok(buffer)
+    }
+
+    /// Writes the count with the singular or plural noun into the buffer.
+    ///
+    /// See [`Self::quantity`] for a version that allocates a `String`.
+    ///
+    /// # Parameters
+    ///
+    /// * `buffer`: The buffer to write into.
+    /// * `count`: The number of things.
+    /// * `singular`: The noun when `count` is 1.
+    /// * `plural`: The noun for every other count.
+    pub fn fmt_quantity(
+        buffer: &mut String,
+        count: usize,
+        singular: &str,
+        plural: &str,
+    ) -> Result<(), fmt::Error> {
+        let noun = if count == 1 { singular } else { plural };
+        write!(buffer, "{count} {noun}")
+    }
 }
 
 #[cfg(test)]
@@ -52,6 +459,7 @@ mod tests {
     use std::{borrow::Cow, fmt};
 
     use super::Note;
+    use crate::fmt::Conjunction;
 
     #[test]
     fn valid_exprs_does_not_have_comma_when_one_suggestion() -> Result<(), fmt::Error> {
@@ -72,4 +480,170 @@ mod tests {
         assert_eq!("expected value to be one of: `abc`, `def`", note);
         Ok(())
     }
+
+    #[test]
+    fn did_you_mean_suggests_closest_candidate() -> Result<(), fmt::Error> {
+        let candidates = vec![
+            Cow::Borrowed("available"),
+            Cow::Borrowed("chosen"),
+            Cow::Borrowed("server"),
+        ];
+
+        let note = Note::did_you_mean("avialable", candidates.into_iter())?;
+
+        assert_eq!(Some("did you mean `available`?"), note.as_deref());
+        Ok(())
+    }
+
+    #[test]
+    fn did_you_mean_lists_closest_candidates_in_order() -> Result<(), fmt::Error> {
+        let candidates = vec![
+            Cow::Borrowed("service"),
+            Cow::Borrowed("servers"),
+            Cow::Borrowed("serve"),
+            Cow::Borrowed("server"),
+            Cow::Borrowed("sever"),
+        ];
+
+        let note = Note::did_you_mean("servre", candidates.into_iter())?;
+
+        assert_eq!(
+            Some("did you mean `serve`, `server`, or `service`?"),
+            note.as_deref()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn did_you_mean_joins_two_candidates_with_or() -> Result<(), fmt::Error> {
+        let candidates = vec![
+            Cow::Borrowed("abc"),
+            Cow::Borrowed("abd"),
+            Cow::Borrowed("xyz"),
+        ];
+
+        let note = Note::did_you_mean("abe", candidates.into_iter())?;
+
+        assert_eq!(Some("did you mean `abc` or `abd`?"), note.as_deref());
+        Ok(())
+    }
+
+    #[test]
+    fn did_you_mean_returns_none_when_no_candidate_is_similar() -> Result<(), fmt::Error> {
+        let candidates = vec![Cow::Borrowed("abc"), Cow::Borrowed("def")];
+
+        let note = Note::did_you_mean("ghi", candidates.into_iter())?;
+
+        assert_eq!(None, note);
+        Ok(())
+    }
+
+    #[test]
+    fn did_you_mean_returns_none_when_input_is_empty() -> Result<(), fmt::Error> {
+        let candidates = vec![Cow::Borrowed("a"), Cow::Borrowed("b")];
+
+        let note = Note::did_you_mean("", candidates.into_iter())?;
+
+        assert_eq!(None, note);
+        Ok(())
+    }
+
+    #[test]
+    fn fmt_did_you_mean_writes_nothing_when_no_candidate_is_similar() -> Result<(), fmt::Error> {
+        let mut buffer = String::from("invalid value");
+
+        let written = Note::fmt_did_you_mean(&mut buffer, "ghi", std::iter::empty())?;
+
+        assert!(!written);
+        assert_eq!("invalid value", buffer);
+        Ok(())
+    }
+
+    #[test]
+    fn value_between_returns_range_sentence() -> Result<(), fmt::Error> {
+        assert_eq!(
+            "expected a value between 1 and 10",
+            Note::value_between(1, 10)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn type_mismatch_returns_expected_and_found_types() -> Result<(), fmt::Error> {
+        assert_eq!(
+            "expected type `integer`, found `string`",
+            Note::type_mismatch("integer", "string")?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn length_max_and_min_pluralize_characters() -> Result<(), fmt::Error> {
+        assert_eq!(
+            "value must be at most 8 characters (found 12)",
+            Note::length_max(8, 12)?
+        );
+        assert_eq!(
+            "value must be at least 1 character (found 0)",
+            Note::length_min(1, 0)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn required_when_names_both_keys() -> Result<(), fmt::Error> {
+        assert_eq!(
+            "key `port` is required when `host` is set",
+            Note::required_when("port", "host")?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn conflicts_joins_values_with_and() -> Result<(), fmt::Error> {
+        assert_eq!(
+            "these values conflict: `verbose`, `quiet`, and `silent`",
+            Note::conflicts(vec!["verbose", "quiet", "silent"].into_iter())?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn list_joins_items_naturally() -> Result<(), fmt::Error> {
+        assert_eq!("", Note::list(std::iter::empty::<&str>(), Conjunction::Or)?);
+        assert_eq!("`a`", Note::list(std::iter::once("a"), Conjunction::Or)?);
+        assert_eq!(
+            "`a` or `b`",
+            Note::list(vec!["a", "b"].into_iter(), Conjunction::Or)?
+        );
+        assert_eq!(
+            "`a`, `b`, and `c`",
+            Note::list(vec!["a", "b", "c"].into_iter(), Conjunction::And)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn list_truncates_items_after_list_max() -> Result<(), fmt::Error> {
+        let note = Note::list(1..=42, Conjunction::Or)?;
+
+        assert_eq!("`1`, `2`, `3`, `4`, `5`, and 37 more", note);
+        Ok(())
+    }
+
+    #[test]
+    fn list_does_not_truncate_list_max_items() -> Result<(), fmt::Error> {
+        let note = Note::list(1..=Note::LIST_MAX, Conjunction::Or)?;
+
+        assert_eq!("`1`, `2`, `3`, `4`, or `5`", note);
+        Ok(())
+    }
+
+    #[test]
+    fn quantity_pluralizes_noun() -> Result<(), fmt::Error> {
+        assert_eq!("0 keys", Note::quantity(0, "key", "keys")?);
+        assert_eq!("1 key", Note::quantity(1, "key", "keys")?);
+        assert_eq!("2 keys", Note::quantity(2, "key", "keys")?);
+        Ok(())
+    }
 }
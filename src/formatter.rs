@@ -1,16 +1,47 @@
 //! Formatters for source errors.
 
+use codespan_reporting::diagnostic::Severity;
+
 pub use self::{
-    plain_text_formatter::PlainTextFormatter, source_error_formatter::SourceErrorFormatter,
-    styler::Styler,
+    column_base::ColumnBase, column_unit::ColumnUnit,
+    github_actions_formatter::GithubActionsFormatter, html_formatter::HtmlFormatter,
+    plain_text_formatter::PlainTextFormatter, short_formatter::ShortFormatter,
+    source_error_formatter::SourceErrorFormatter, styler::Styler,
 };
 
+#[cfg(feature = "error_index")]
+pub(crate) use self::html_formatter::HtmlEscape;
+
+mod column_base;
+mod column_unit;
+mod github_actions_formatter;
+mod html_formatter;
 mod plain_text_formatter;
+mod short_formatter;
 mod source_error_formatter;
 mod styler;
 
+#[cfg(feature = "annotate_snippets")]
+pub use self::annotate_snippets_formatter::AnnotateSnippetsFormatter;
 #[cfg(feature = "ansi_color")]
 pub use self::ansi_color_formatter::AnsiColorFormatter;
+#[cfg(feature = "ariadne")]
+pub use self::ariadne_formatter::AriadneFormatter;
 
+#[cfg(feature = "annotate_snippets")]
+mod annotate_snippets_formatter;
 #[cfg(feature = "ansi_color")]
 mod ansi_color_formatter;
+#[cfg(feature = "ariadne")]
+mod ariadne_formatter;
+
+/// Returns the lower case name of the severity.
+pub(crate) fn severity_str(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Bug => "bug",
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note => "note",
+        Severity::Help => "help",
+    }
+}
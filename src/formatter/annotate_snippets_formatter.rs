@@ -0,0 +1,355 @@
+use std::io;
+
+use annotate_snippets::{AnnotationKind, Group, Level, Renderer, Snippet};
+use codespan_reporting::{
+    diagnostic::Severity,
+    files::{self, Files},
+};
+
+use crate::{
+    fmt::Code,
+    formatter::SourceErrorFormatter,
+    model::{ErrorCode, ErrorDetail, Label, LabelStyle, SourceError, Trailer},
+};
+
+/// Renders source errors with [`annotate_snippets`], in the style of `rustc`.
+///
+/// * The level is `error` for [`Severity::Bug`] and [`Severity::Error`],
+///   `warning` for [`Severity::Warning`], `note` for [`Severity::Note`], and
+///   `help` for [`Severity::Help`].
+/// * Each file with labels is rendered as a snippet. Primary labels are
+///   underlined with `^` in the error's level, and secondary labels are
+///   underlined with `-` as context.
+/// * Each trailer is rendered as a footer, with `note:`, `help:`, or
+///   `warning:` levels. [`Trailer::SeeAlso`] links are rendered as `note:`
+///   footers, and suggestions as `help:` footers.
+///
+/// Colours are disabled by default, and can be enabled with
+/// [`with_renderer`](Self::with_renderer).
+#[derive(Clone, Debug)]
+pub struct AnnotateSnippetsFormatter {
+    /// Renders the message.
+    renderer: Renderer,
+}
+
+impl AnnotateSnippetsFormatter {
+    /// Returns a new `AnnotateSnippetsFormatter` without colour.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the renderer used to render the errors, e.g.
+    /// [`Renderer::styled`].
+    #[must_use]
+    pub fn with_renderer(mut self, renderer: Renderer) -> Self {
+        self.renderer = renderer;
+        self
+    }
+}
+
+impl Default for AnnotateSnippetsFormatter {
+    fn default() -> Self {
+        Self {
+            renderer: Renderer::plain(),
+        }
+    }
+}
+
+impl SourceErrorFormatter for AnnotateSnippetsFormatter {
+    fn fmt<'files, Ec, Ed, Fs>(
+        &self,
+        writer: &mut dyn io::Write,
+        files: &'files Fs,
+        source_error: &SourceError<'files, Ec, Ed, Fs>,
+    ) -> Result<(), files::Error>
+    where
+        Ec: ErrorCode,
+        Ed: ErrorDetail<'files, Files = Fs>,
+        Fs: Files<'files>,
+    {
+        let level = level(source_error.severity);
+        let code = Code::string(source_error.code);
+        let labels = source_error.detail.labels();
+        let suggestion_helps = source_error
+            .detail
+            .suggestions()
+            .iter()
+            .map(|suggestion| suggestion.help(files).map(Trailer::Help))
+            .collect::<Result<Vec<Trailer>, files::Error>>()?;
+        let footers = source_error
+            .detail
+            .trailers(files)
+            .into_iter()
+            .chain(suggestion_helps)
+            .map(|trailer| match trailer {
+                Trailer::Note(note) => (Level::NOTE, note),
+                Trailer::Help(help) => (Level::HELP, help),
+                Trailer::Warning(warning) => (Level::WARNING, warning),
+                Trailer::SeeAlso(_) => (Level::NOTE, trailer.to_string()),
+            })
+            .collect::<Vec<(Level<'_>, String)>>();
+
+        // Snippets borrow their source, so sources are gathered per file first.
+        let mut sources = Vec::<(Fs::FileId, String, String, Vec<&Label<Fs::FileId>>)>::new();
+        labels.iter().try_for_each(|label| {
+            let source_index = match sources
+                .iter()
+                .position(|(file_id, _, _, _)| *file_id == label.span.file_id)
+            {
+                Some(source_index) => source_index,
+                None => {
+                    let name = files.name(label.span.file_id)?.to_string();
+                    let source = String::from(files.source(label.span.file_id)?.as_ref());
+                    sources.push((label.span.file_id, name, source, Vec::new()));
+                    sources.len() - 1
+                }
+            };
+
+            // annotate-snippets panics on spans outside the source.
+            let (_, _, source, file_labels) = &mut sources[source_index];
+            if source.get(label.span.range.clone()).is_none() {
+                return Err(files::Error::IndexTooLarge {
+                    given: label.span.range.end,
+                    max: source.len(),
+                });
+            }
+            file_labels.push(label);
+
+            Ok(())
+        })?;
+
+        let snippets = sources.iter().map(|(_, name, source, file_labels)| {
+            Snippet::source(source.as_str())
+                .path(name.as_str())
+                .fold(true)
+                .annotations(file_labels.iter().map(|label| {
+                    let annotation_kind = match label.style {
+                        LabelStyle::Primary => AnnotationKind::Primary,
+                        LabelStyle::Secondary => AnnotationKind::Context,
+                    };
+                    annotation_kind
+                        .span(label.span.range.clone())
+                        .label(label.message.as_str())
+                }))
+        });
+        let messages = footers
+            .iter()
+            .map(|(footer_level, footer)| footer_level.clone().message(footer.as_str()));
+        let group = Group::with_title(
+            level
+                .primary_title(source_error.code.description())
+                .id(code.as_str()),
+        )
+        .elements(snippets)
+        .elements(messages);
+
+        writeln!(writer, "{}", self.renderer.render(&[group]))?;
+
+        Ok(())
+    }
+}
+
+/// Returns the annotate-snippets level for the severity.
+fn level(severity: Severity) -> Level<'static> {
+    match severity {
+        Severity::Bug | Severity::Error => Level::ERROR,
+        Severity::Warning => Level::WARNING,
+        Severity::Note => Level::NOTE,
+        Severity::Help => Level::HELP,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use annotate_snippets::{
+        renderer::{AnsiColor, Effects, Style},
+        Renderer,
+    };
+    use codespan_reporting::{diagnostic::Severity, files};
+
+    use super::AnnotateSnippetsFormatter;
+    use crate::{formatter::SourceErrorFormatter, test_support};
+
+    #[test]
+    fn renders_source_error_with_annotate_snippets() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_invalid_value(file_id, Severity::Error);
+
+        let mut buffer = Vec::new();
+        AnnotateSnippetsFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+
+        let expected = [
+            "error[E1]: `chosen` value is invalid.",
+            " --> examples/source_ref_hint.yaml:6:9",
+            "  |",
+            "2 | / available:",
+            "3 | |   - \"abc\"",
+            "4 | |   - \"def\"",
+            "  | |_________- defined here",
+            "5 |",
+            "6 |   chosen: \"ghi\"",
+            "  |           ^^^^^ invalid value specified",
+            "  |",
+            "  = note: `chosen` value must come from one of `available` values",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(expected, String::from_utf8(buffer)?);
+        Ok(())
+    }
+
+    #[test]
+    fn renders_primary_label_with_caret_for_warnings() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_invalid_value(file_id, Severity::Warning);
+
+        let mut buffer = Vec::new();
+        AnnotateSnippetsFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+
+        let expected = [
+            "warning[E1]: `chosen` value is invalid.",
+            " --> examples/source_ref_hint.yaml:6:9",
+            "  |",
+            "2 | / available:",
+            "3 | |   - \"abc\"",
+            "4 | |   - \"def\"",
+            "  | |_________- defined here",
+            "5 |",
+            "6 |   chosen: \"ghi\"",
+            "  |           ^^^^^ invalid value specified",
+            "  |",
+            "  = note: `chosen` value must come from one of `available` values",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(expected, String::from_utf8(buffer)?);
+        Ok(())
+    }
+
+    #[test]
+    fn renders_source_error_with_styled_renderer() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_invalid_value(file_id, Severity::Warning);
+        // Some default styles differ on Windows, so they are set explicitly.
+        let renderer = Renderer::styled()
+            .warning(AnsiColor::Yellow.on_default().effects(Effects::BOLD))
+            .line_num(AnsiColor::BrightBlue.on_default().effects(Effects::BOLD))
+            .context(AnsiColor::BrightBlue.on_default().effects(Effects::BOLD))
+            .emphasis(Style::new().effects(Effects::BOLD));
+
+        let mut buffer = Vec::new();
+        AnnotateSnippetsFormatter::new()
+            .with_renderer(renderer)
+            .fmt(&mut buffer, &files, &source_error)?;
+
+        let expected = [
+            "{b}{y}warning[E1]{r}{b}: `chosen` value is invalid.{r}",
+            " {b}{u}--> {r}examples/source_ref_hint.yaml:6:9",
+            "  {b}{u}|{r}",
+            "{b}{u}2{r} {b}{u}|{r} {b}{u}/{r} available:",
+            "{b}{u}3{r} {b}{u}|{r} {b}{u}|{r}   - \"abc\"",
+            "{b}{u}4{r} {b}{u}|{r} {b}{u}|{r}   - \"def\"",
+            "  {b}{u}|{r} {b}{u}|_________-{r} {b}{u}defined here{r}",
+            "{b}{u}5{r} {b}{u}|{r}",
+            "{b}{u}6{r} {b}{u}|{r}   chosen: \"ghi\"",
+            "  {b}{u}|{r}           {b}{y}^^^^^{r} {b}{y}invalid value specified{r}",
+            "  {b}{u}|{r}",
+            "  {b}{u}= {r}{b}note{r}: `chosen` value must come from one of `available` values",
+            "",
+        ]
+        .join("\n")
+        .replace("{b}", "\u{1b}[1m")
+        .replace("{y}", "\u{1b}[33m")
+        .replace("{u}", "\u{1b}[94m")
+        .replace("{r}", "\u{1b}[0m");
+        assert_eq!(expected, String::from_utf8(buffer)?);
+        Ok(())
+    }
+
+    #[test]
+    fn renders_title_level_per_severity() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+
+        let titles = [Severity::Bug, Severity::Note, Severity::Help]
+            .iter()
+            .map(|severity| {
+                let source_error = test_support::source_error_invalid_value(file_id, *severity);
+                let mut buffer = Vec::new();
+                AnnotateSnippetsFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+                let output = String::from_utf8(buffer)?;
+                Ok(output.lines().next().map(String::from))
+            })
+            .collect::<Result<Vec<Option<String>>, Box<dyn std::error::Error>>>()?;
+
+        assert_eq!(
+            vec![
+                Some(String::from("error[E1]: `chosen` value is invalid.")),
+                Some(String::from("note[E1]: `chosen` value is invalid.")),
+                Some(String::from("help[E1]: `chosen` value is invalid.")),
+            ],
+            titles
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn renders_secondary_labels_when_none_are_primary() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_secondary_only(file_id, Severity::Error);
+
+        let mut buffer = Vec::new();
+        AnnotateSnippetsFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+
+        let expected = [
+            "error[E1]: `chosen` value is invalid.",
+            " --> examples/source_ref_hint.yaml:2:1",
+            "  |",
+            "2 | / available:",
+            "3 | |   - \"abc\"",
+            "4 | |   - \"def\"",
+            "  | |_________- defined here",
+            "5 |",
+            "6 |   chosen: \"ghi\"",
+            "  |           ----- invalid value specified",
+            "  |",
+            "  = note: `chosen` value must come from one of `available` values",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(expected, String::from_utf8(buffer)?);
+        Ok(())
+    }
+
+    #[test]
+    fn renders_title_and_notes_for_unlabelled_error() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_unlabelled(file_id, Severity::Error);
+
+        let mut buffer = Vec::new();
+        AnnotateSnippetsFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+
+        let expected = [
+            "error[E1]: `chosen` value is invalid.",
+            "  |",
+            "  = note: `chosen` value must come from one of `available` values",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(expected, String::from_utf8(buffer)?);
+        Ok(())
+    }
+
+    #[test]
+    fn returns_error_when_label_range_exceeds_source() {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_range_invalid(file_id, Severity::Error);
+
+        let mut buffer = Vec::new();
+        let result = AnnotateSnippetsFormatter::new().fmt(&mut buffer, &files, &source_error);
+
+        assert!(matches!(
+            result,
+            Err(files::Error::IndexTooLarge { given: 400, .. })
+        ));
+    }
+}
@@ -0,0 +1,191 @@
+use std::io;
+
+use codespan_reporting::{
+    files::{self, Files},
+    term::{self, termcolor::Ansi},
+};
+
+use crate::{
+    formatter::{SourceErrorFormatter, Styler},
+    model::{ErrorCode, ErrorDetail, SourceError},
+};
+
+/// Renders source errors as text coloured with ANSI escape codes.
+///
+/// This always emits escape codes -- callers are responsible for checking
+/// whether the output stream supports them.
+#[derive(Clone, Debug, Default)]
+pub struct AnsiColorFormatter {
+    /// Configuration for how to render the errors.
+    config: term::Config,
+}
+
+impl AnsiColorFormatter {
+    /// Returns a new `AnsiColorFormatter` with the default configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the configuration used to render the errors.
+    #[must_use]
+    pub fn with_config(mut self, config: term::Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Sets the colours used to render the errors.
+    #[must_use]
+    pub fn with_styler(mut self, styler: Styler) -> Self {
+        self.config.styles = styler.into_styles();
+        self
+    }
+
+    /// Returns the configuration used to render the errors.
+    pub fn config(&self) -> &term::Config {
+        &self.config
+    }
+}
+
+impl SourceErrorFormatter for AnsiColorFormatter {
+    fn fmt<'files, Ec, Ed, Fs>(
+        &self,
+        writer: &mut dyn io::Write,
+        files: &'files Fs,
+        source_error: &SourceError<'files, Ec, Ed, Fs>,
+    ) -> Result<(), files::Error>
+    where
+        Ec: ErrorCode,
+        Ed: ErrorDetail<'files, Files = Fs>,
+        Fs: Files<'files>,
+    {
+        let mut writer = Ansi::new(writer);
+        term::emit(
+            &mut writer,
+            &self.config,
+            files,
+            &source_error.as_diagnostic(files),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use codespan_reporting::{
+        diagnostic::Severity,
+        term::termcolor::{Color, ColorSpec},
+    };
+
+    use super::AnsiColorFormatter;
+    use crate::{
+        formatter::{SourceErrorFormatter, Styler},
+        test_support,
+    };
+
+    #[test]
+    fn renders_source_error_with_ansi_escape_codes() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_invalid_value(file_id, Severity::Error);
+
+        let mut buffer = Vec::new();
+        AnsiColorFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+
+        let output = String::from_utf8(buffer)?;
+        assert!(output.starts_with("\u{1b}[0m\u{1b}[1m\u{1b}[38;5;9merror[E1]"));
+        assert!(output.contains("invalid value specified"));
+        Ok(())
+    }
+
+    #[test]
+    fn renders_header_color_per_severity() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+
+        [
+            (Severity::Bug, "\u{1b}[0m\u{1b}[1m\u{1b}[38;5;9mbug[E1]"),
+            (
+                Severity::Warning,
+                "\u{1b}[0m\u{1b}[1m\u{1b}[38;5;11mwarning[E1]",
+            ),
+            (Severity::Note, "\u{1b}[0m\u{1b}[1m\u{1b}[38;5;10mnote[E1]"),
+            (Severity::Help, "\u{1b}[0m\u{1b}[1m\u{1b}[38;5;14mhelp[E1]"),
+        ]
+        .iter()
+        .try_for_each(|(severity, header)| {
+            let source_error = test_support::source_error_invalid_value(file_id, *severity);
+            let mut buffer = Vec::new();
+            AnsiColorFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+
+            let output = String::from_utf8(buffer)?;
+            assert!(output.starts_with(header), "{:?}", output);
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn renders_secondary_labels_when_none_are_primary() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_secondary_only(file_id, Severity::Error);
+
+        let mut buffer = Vec::new();
+        AnsiColorFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+
+        let output = String::from_utf8(buffer)?;
+        assert!(output.contains(" examples/source_ref_hint.yaml:2:1\n"));
+        assert!(output.contains(
+            "\u{1b}[0m\u{1b}[34m-----\u{1b}[0m \u{1b}[0m\u{1b}[34minvalid value specified"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn renders_header_and_notes_for_unlabelled_error() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_unlabelled(file_id, Severity::Error);
+
+        let mut buffer = Vec::new();
+        AnsiColorFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+
+        let expected = [
+            "\u{1b}[0m\u{1b}[1m\u{1b}[38;5;9merror[E1]\u{1b}[0m\u{1b}[1m: \
+             `chosen` value is invalid.\u{1b}[0m",
+            " \u{1b}[0m\u{1b}[34m=\u{1b}[0m \
+             `chosen` value must come from one of `available` values",
+            "",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(expected, String::from_utf8(buffer)?);
+        Ok(())
+    }
+
+    #[test]
+    fn renders_label_past_end_of_file_at_end_of_file() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_range_invalid(file_id, Severity::Error);
+
+        let mut buffer = Vec::new();
+        AnsiColorFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+
+        let output = String::from_utf8(buffer)?;
+        assert!(output.contains(" examples/source_ref_hint.yaml:7:1\n"));
+        assert!(output.contains("\u{1b}[0m\u{1b}[31minvalid value specified"));
+        Ok(())
+    }
+
+    #[test]
+    fn renders_source_error_with_styler_colors() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_invalid_value(file_id, Severity::Error);
+        let mut header_error = ColorSpec::new();
+        header_error.set_fg(Some(Color::Magenta));
+        let styler = Styler::new().with_header(Severity::Error, header_error);
+
+        let mut buffer = Vec::new();
+        AnsiColorFormatter::new()
+            .with_styler(styler)
+            .fmt(&mut buffer, &files, &source_error)?;
+
+        let output = String::from_utf8(buffer)?;
+        assert!(output.starts_with("\u{1b}[0m\u{1b}[35merror[E1]"));
+        Ok(())
+    }
+}
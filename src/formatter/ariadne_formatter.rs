@@ -0,0 +1,329 @@
+use std::{io, ops::Range};
+
+use ariadne::{Config, IndexType, Label, Report, ReportKind};
+use codespan_reporting::{
+    diagnostic::Severity,
+    files::{self, Files},
+};
+
+use crate::{
+    fmt::Code,
+    formatter::SourceErrorFormatter,
+    model::{ErrorCode, ErrorDetail, LabelStyle, SourceError, Trailer},
+};
+
+/// Renders source errors with [`ariadne`].
+///
+/// * The report kind is `Error` for [`Severity::Bug`] and [`Severity::Error`],
+///   `Warning` for [`Severity::Warning`], and `Advice` for [`Severity::Note`]
+///   and [`Severity::Help`].
+/// * The report is located at the first primary label, or the first label if
+///   none are primary.
+/// * Primary labels are ordered before secondary labels.
+/// * Help trailers and suggestions are rendered as ariadne help, and other
+///   trailers as ariadne notes. Errors without labels have their notes written
+///   beneath the header.
+/// * Labels outside their source return [`files::Error::IndexTooLarge`].
+///
+/// Colours are disabled by default, and can be enabled with
+/// [`with_config`](Self::with_config).
+#[derive(Clone, Copy, Debug)]
+pub struct AriadneFormatter {
+    /// Configuration for how to render the errors.
+    config: Config,
+}
+
+impl AriadneFormatter {
+    /// Returns a new `AriadneFormatter` without colour.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the configuration used to render the errors.
+    ///
+    /// The [`IndexType`] is always [`IndexType::Byte`], as label ranges are
+    /// byte ranges.
+    #[must_use]
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+}
+
+impl Default for AriadneFormatter {
+    fn default() -> Self {
+        Self {
+            config: Config::default().with_color(false),
+        }
+    }
+}
+
+impl SourceErrorFormatter for AriadneFormatter {
+    fn fmt<'files, Ec, Ed, Fs>(
+        &self,
+        writer: &mut dyn io::Write,
+        files: &'files Fs,
+        source_error: &SourceError<'files, Ec, Ed, Fs>,
+    ) -> Result<(), files::Error>
+    where
+        Ec: ErrorCode,
+        Ed: ErrorDetail<'files, Files = Fs>,
+        Fs: Files<'files>,
+    {
+        let mut labels = source_error.detail.labels();
+        labels.sort_by_key(|label| label.style == LabelStyle::Secondary);
+
+        // ariadne identifies sources by name, so sources are gathered per file.
+        let mut sources = Vec::<(Fs::FileId, String, String)>::new();
+        let spans = labels
+            .iter()
+            .map(|label| {
+                let source_index = match sources
+                    .iter()
+                    .position(|(file_id, _, _)| *file_id == label.span.file_id)
+                {
+                    Some(source_index) => source_index,
+                    None => {
+                        let name = files.name(label.span.file_id)?.to_string();
+                        let source = String::from(files.source(label.span.file_id)?.as_ref());
+                        sources.push((label.span.file_id, name, source));
+                        sources.len() - 1
+                    }
+                };
+
+                // ariadne drops labels outside the source, along with the notes.
+                let (_, name, source) = &sources[source_index];
+                if source.get(label.span.range.clone()).is_none() {
+                    return Err(files::Error::IndexTooLarge {
+                        given: label.span.range.end,
+                        max: source.len(),
+                    });
+                }
+                Ok((name.clone(), label.span.range.clone()))
+            })
+            .collect::<Result<Vec<(String, Range<usize>)>, files::Error>>()?;
+
+        let span = spans
+            .first()
+            .cloned()
+            .unwrap_or_else(|| (String::new(), 0..0));
+        let mut report_builder = Report::build(report_kind(source_error.severity), span)
+            .with_config(self.config.with_index_type(IndexType::Byte))
+            .with_code(Code::string(source_error.code))
+            .with_message(source_error.code.description())
+            .with_labels(spans.into_iter().zip(labels.iter()).enumerate().map(
+                |(order, (span, label))| {
+                    let ariadne_label = Label::new(span).with_order(order as i32);
+                    if label.message.is_empty() {
+                        ariadne_label
+                    } else {
+                        ariadne_label.with_message(&label.message)
+                    }
+                },
+            ));
+        let suggestion_helps = source_error
+            .detail
+            .suggestions()
+            .iter()
+            .map(|suggestion| suggestion.help(files).map(Trailer::Help))
+            .collect::<Result<Vec<Trailer>, files::Error>>()?;
+        let footers = source_error
+            .detail
+            .trailers(files)
+            .into_iter()
+            .chain(suggestion_helps)
+            .map(|trailer| match trailer {
+                Trailer::Help(help) => (FooterKind::Help, help),
+                Trailer::Note(note) => (FooterKind::Note, note),
+                Trailer::Warning(_) | Trailer::SeeAlso(_) => {
+                    (FooterKind::Note, trailer.to_string())
+                }
+            })
+            .collect::<Vec<(FooterKind, String)>>();
+        footers
+            .iter()
+            .for_each(|(footer_kind, footer)| match footer_kind {
+                FooterKind::Help => report_builder.add_help(footer),
+                FooterKind::Note => report_builder.add_note(footer),
+            });
+
+        let cache = ariadne::sources(sources.into_iter().map(|(_, name, source)| (name, source)));
+        report_builder.finish().write(cache, &mut *writer)?;
+
+        // ariadne writes notes beneath the last source snippet, so they are
+        // written here for errors without labels.
+        if labels.is_empty() {
+            footers.iter().try_for_each(|(footer_kind, footer)| {
+                let prefix = footer_kind.prefix();
+                footer
+                    .lines()
+                    .enumerate()
+                    .try_for_each(|(line_index, line)| {
+                        if line_index == 0 {
+                            writeln!(writer, "{}{}", prefix, line)
+                        } else {
+                            writeln!(writer, "{:width$}{}", "", line, width = prefix.len())
+                        }
+                    })
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Whether a trailer is rendered as ariadne help or as a note.
+#[derive(Clone, Copy, Debug)]
+enum FooterKind {
+    Help,
+    Note,
+}
+
+impl FooterKind {
+    /// Returns the prefix ariadne writes before the footer.
+    fn prefix(self) -> &'static str {
+        match self {
+            Self::Help => "Help: ",
+            Self::Note => "Note: ",
+        }
+    }
+}
+
+/// Returns the ariadne report kind for the severity.
+fn report_kind(severity: Severity) -> ReportKind<'static> {
+    match severity {
+        Severity::Bug | Severity::Error => ReportKind::Error,
+        Severity::Warning => ReportKind::Warning,
+        Severity::Note | Severity::Help => ReportKind::Advice,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use codespan_reporting::{diagnostic::Severity, files};
+
+    use super::AriadneFormatter;
+    use crate::{formatter::SourceErrorFormatter, model::Trailer, test_support};
+
+    #[test]
+    fn renders_source_error_with_ariadne() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_invalid_value(file_id, Severity::Error);
+
+        let mut buffer = Vec::new();
+        AriadneFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+
+        let expected = [
+            "[E1] Error: `chosen` value is invalid.",
+            "   ╭─[ examples/source_ref_hint.yaml:6:9 ]",
+            "   │",
+            " 2 │ ╭─▶ available:",
+            "   ┆ ┆   ",
+            " 4 │ ├─▶   - \"def\"",
+            "   │ │               ",
+            "   │ ╰─────────────── defined here",
+            "   │ ",
+            " 6 │     chosen: \"ghi\"",
+            "   │             ──┬──  ",
+            "   │               ╰──── invalid value specified",
+            "   │ │   ",
+            "   │ │   Note: `chosen` value must come from one of `available` values",
+            "───╯",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(expected, String::from_utf8(buffer)?);
+        Ok(())
+    }
+
+    #[test]
+    fn renders_report_kind_per_severity() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+
+        let headers = [
+            Severity::Bug,
+            Severity::Warning,
+            Severity::Note,
+            Severity::Help,
+        ]
+        .iter()
+        .map(|severity| {
+            let source_error = test_support::source_error_invalid_value(file_id, *severity);
+            let mut buffer = Vec::new();
+            AriadneFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+            let output = String::from_utf8(buffer)?;
+            Ok(output.lines().next().map(String::from))
+        })
+        .collect::<Result<Vec<Option<String>>, Box<dyn std::error::Error>>>()?;
+
+        assert_eq!(
+            vec![
+                Some(String::from("[E1] Error: `chosen` value is invalid.")),
+                Some(String::from("[E1] Warning: `chosen` value is invalid.")),
+                Some(String::from("[E1] Advice: `chosen` value is invalid.")),
+                Some(String::from("[E1] Advice: `chosen` value is invalid.")),
+            ],
+            headers
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn renders_secondary_labels_at_first_label_when_none_are_primary(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_secondary_only(file_id, Severity::Error);
+
+        let mut buffer = Vec::new();
+        AriadneFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+
+        let output = String::from_utf8(buffer)?;
+        assert!(output.contains("   ╭─[ examples/source_ref_hint.yaml:6:9 ]\n"));
+        assert!(output.contains("╰──── invalid value specified\n"));
+        assert!(output.contains("Note: `chosen` value must come from one of `available` values\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn renders_header_and_notes_for_unlabelled_error() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let mut source_error = test_support::source_error_unlabelled(file_id, Severity::Error);
+        source_error
+            .detail
+            .trailers
+            .push(Trailer::Help(String::from("use one of:\n\"abc\"\n\"def\"")));
+
+        let mut buffer = Vec::new();
+        AriadneFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+
+        let expected = [
+            "[E1] Error: `chosen` value is invalid.",
+            "Note: `chosen` value must come from one of `available` values",
+            "Help: use one of:",
+            "      \"abc\"",
+            "      \"def\"",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(expected, String::from_utf8(buffer)?);
+        Ok(())
+    }
+
+    #[test]
+    fn returns_error_when_label_range_exceeds_source() {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_range_invalid(file_id, Severity::Error);
+
+        let mut buffer = Vec::new();
+        let result = AriadneFormatter::new().fmt(&mut buffer, &files, &source_error);
+
+        assert!(matches!(
+            result,
+            Err(files::Error::IndexTooLarge {
+                given: 400,
+                max: 50
+            })
+        ));
+        assert!(buffer.is_empty());
+    }
+}
@@ -0,0 +1,9 @@
+/// Whether columns are counted from `0` or `1`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ColumnBase {
+    /// The first column is `0`.
+    Zero,
+    /// The first column is `1`, as used by most compilers and editors.
+    #[default]
+    One,
+}
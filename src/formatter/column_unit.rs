@@ -0,0 +1,10 @@
+/// What a column counts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ColumnUnit {
+    /// Columns count Unicode scalar values, consistent with the columns
+    /// rendered by `codespan-reporting`.
+    #[default]
+    Char,
+    /// Columns count bytes from the start of the line.
+    Byte,
+}
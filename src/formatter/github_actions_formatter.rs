@@ -0,0 +1,276 @@
+use std::{fmt, io};
+
+use codespan_reporting::{
+    diagnostic::Severity,
+    files::{self, Files},
+};
+
+use crate::{
+    fmt::Code,
+    formatter::SourceErrorFormatter,
+    model::{ErrorCode, ErrorDetail, LabelStyle, LineColumn, SourceError, Trailer},
+};
+
+/// Renders source errors as GitHub Actions workflow commands.
+///
+/// Each primary label is written as one command, which GitHub displays as an
+/// annotation on the pull request:
+///
+/// ```text
+/// ::error file=config.yaml,line=6,col=9,endLine=6,endColumn=14,title=E1::`chosen` value is invalid.
+/// ```
+///
+/// * [`Severity::Bug`] and [`Severity::Error`] are written as `::error`.
+/// * [`Severity::Warning`] is written as `::warning`.
+/// * [`Severity::Note`] and [`Severity::Help`] are written as `::notice`.
+///
+/// The message holds the error code description, followed by the label
+/// message and notes on separate lines. Columns are 1-based and count Unicode
+/// scalar values. Errors without primary labels are written without a
+/// location.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GithubActionsFormatter;
+
+impl GithubActionsFormatter {
+    /// Returns a new `GithubActionsFormatter`.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl SourceErrorFormatter for GithubActionsFormatter {
+    fn fmt<'files, Ec, Ed, Fs>(
+        &self,
+        writer: &mut dyn io::Write,
+        files: &'files Fs,
+        source_error: &SourceError<'files, Ec, Ed, Fs>,
+    ) -> Result<(), files::Error>
+    where
+        Ec: ErrorCode,
+        Ed: ErrorDetail<'files, Files = Fs>,
+        Fs: Files<'files>,
+    {
+        let command = command(source_error.severity);
+        let title = Property(Code::string(source_error.code));
+        let labels = source_error.detail.labels();
+        let trailers = source_error
+            .detail
+            .trailers(files)
+            .iter()
+            .map(Trailer::to_string)
+            .collect::<Vec<String>>();
+        let message = |label_message: &str| {
+            let mut message = String::from(source_error.code.description());
+            std::iter::once(label_message)
+                .filter(|label_message| !label_message.is_empty())
+                .chain(trailers.iter().map(String::as_str))
+                .for_each(|line| {
+                    message.push('\n');
+                    message.push_str(line);
+                });
+            Data(message)
+        };
+
+        let mut labels_primary = labels
+            .iter()
+            .filter(|label| label.style == LabelStyle::Primary)
+            .peekable();
+        if labels_primary.peek().is_none() {
+            writeln!(writer, "::{command} title={title}::{}", message(""))?;
+            return Ok(());
+        }
+
+        labels_primary.try_for_each(|label| {
+            let file = Property(
+                files
+                    .name(label.span.file_id)?
+                    .to_string()
+                    .replace('\\', "/"),
+            );
+            let start = LineColumn::new(files, label.span.file_id, label.span.range.start)?;
+            let end = LineColumn::new(files, label.span.file_id, label.span.range.end)?;
+
+            writeln!(
+                writer,
+                "::{command} file={file},line={},col={},endLine={},endColumn={},title={title}::{}",
+                start.line,
+                start.column,
+                end.line,
+                end.column,
+                message(&label.message),
+            )?;
+
+            Ok(())
+        })
+    }
+}
+
+/// Returns the workflow command for the severity.
+fn command(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Bug | Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note | Severity::Help => "notice",
+    }
+}
+
+/// Escapes the message of a workflow command.
+struct Data(String);
+
+impl fmt::Display for Data {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.chars().try_for_each(|c| match c {
+            '%' => f.write_str("%25"),
+            '\r' => f.write_str("%0D"),
+            '\n' => f.write_str("%0A"),
+            _ => fmt::Write::write_char(f, c),
+        })
+    }
+}
+
+/// Escapes a property value of a workflow command.
+struct Property(String);
+
+impl fmt::Display for Property {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.chars().try_for_each(|c| match c {
+            '%' => f.write_str("%25"),
+            '\r' => f.write_str("%0D"),
+            '\n' => f.write_str("%0A"),
+            ':' => f.write_str("%3A"),
+            ',' => f.write_str("%2C"),
+            _ => fmt::Write::write_char(f, c),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use codespan_reporting::diagnostic::Severity;
+
+    use super::GithubActionsFormatter;
+    use crate::{
+        formatter::SourceErrorFormatter,
+        model::Trailer,
+        test_support::{self, TestErrorCode, TestErrorDetail, TestFiles},
+        Label, SourceError,
+    };
+
+    #[test]
+    fn renders_workflow_command_per_primary_label() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_invalid_value(file_id, Severity::Error);
+
+        let mut buffer = Vec::new();
+        GithubActionsFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+
+        assert_eq!(
+            "::error file=examples/source_ref_hint.yaml,line=6,col=9,endLine=6,endColumn=14,\
+             title=E1::`chosen` value is invalid.%0Ainvalid value specified%0A\
+             `chosen` value must come from one of `available` values\n",
+            String::from_utf8(buffer)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn maps_severity_to_command() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+
+        [
+            (Severity::Bug, "::error "),
+            (Severity::Error, "::error "),
+            (Severity::Warning, "::warning "),
+            (Severity::Note, "::notice "),
+            (Severity::Help, "::notice "),
+        ]
+        .iter()
+        .try_for_each(|(severity, command_expected)| {
+            let source_error = test_support::source_error_invalid_value(file_id, *severity);
+
+            let mut buffer = Vec::new();
+            GithubActionsFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+
+            assert!(
+                String::from_utf8(buffer)?.starts_with(command_expected),
+                "Expected {:?} to be written as `{}`.",
+                severity,
+                command_expected
+            );
+            Ok::<_, Box<dyn std::error::Error>>(())
+        })
+    }
+
+    #[test]
+    fn writes_errors_without_primary_labels_without_file() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_secondary_only(file_id, Severity::Error);
+
+        let mut buffer = Vec::new();
+        GithubActionsFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+
+        assert_eq!(
+            "::error title=E1::`chosen` value is invalid.%0A\
+             `chosen` value must come from one of `available` values\n",
+            String::from_utf8(buffer)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn writes_label_past_end_of_file_at_end_of_file() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_range_invalid(file_id, Severity::Error);
+
+        let mut buffer = Vec::new();
+        GithubActionsFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+
+        assert_eq!(
+            "::error file=examples/source_ref_hint.yaml,line=7,col=1,endLine=7,endColumn=1,\
+             title=E1::`chosen` value is invalid.%0Ainvalid value specified%0A\
+             `chosen` value must come from one of `available` values\n",
+            String::from_utf8(buffer)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn escapes_properties_and_writes_errors_without_labels(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut files = TestFiles::new();
+        let file_id = files.add("dir,1/a:b.yaml", "a: 1\n");
+        let source_error_labelled = SourceError::new(
+            TestErrorCode::InvalidValue,
+            TestErrorDetail {
+                labels: vec![Label::primary(file_id, 3..4).with_message("100%")],
+                trailers: Vec::new(),
+                suggestions: Vec::new(),
+            },
+            Severity::Warning,
+        );
+        let source_error_unlabelled = SourceError::new(
+            TestErrorCode::InvalidValue,
+            TestErrorDetail {
+                labels: Vec::new(),
+                trailers: vec![Trailer::Note(String::from("a\r\nb"))],
+                suggestions: Vec::new(),
+            },
+            Severity::Error,
+        );
+
+        let mut buffer = Vec::new();
+        GithubActionsFormatter::new().fmt(&mut buffer, &files, &source_error_labelled)?;
+        GithubActionsFormatter::new().fmt(&mut buffer, &files, &source_error_unlabelled)?;
+
+        let expected = [
+            "::warning file=dir%2C1/a%3Ab.yaml,line=1,col=4,endLine=1,endColumn=5,title=E1::\
+             `chosen` value is invalid.%0A100%25",
+            "::error title=E1::`chosen` value is invalid.%0Aa%0D%0Ab",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(expected, String::from_utf8(buffer)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,590 @@
+use std::{collections::BTreeSet, fmt, io, ops::Range};
+
+use codespan_reporting::{
+    diagnostic::Diagnostic,
+    files::{self, Files},
+};
+
+use crate::{
+    formatter::{self, SourceErrorFormatter},
+    model::{ErrorCode, ErrorDetail, Label, LabelStyle, SourceError, SourceErrors},
+};
+
+/// Renders source errors as HTML.
+///
+/// Each error is rendered as a `<div class="srcerr">` fragment containing the
+/// header, source snippets with line numbers, and notes. Labelled source is
+/// wrapped in `<span class="srcerr-label-primary">` or
+/// `<span class="srcerr-label-secondary">`, which are styled by
+/// [`HtmlFormatter::CSS`].
+///
+/// Use [`HtmlFormatter::fmt_page`] to render a self-contained HTML document.
+#[derive(Clone, Debug)]
+pub struct HtmlFormatter {
+    /// Title of the page rendered by [`HtmlFormatter::fmt_page`].
+    title: String,
+}
+
+impl HtmlFormatter {
+    /// Stylesheet for the rendered HTML.
+    pub const CSS: &'static str = include_str!("html_formatter.css");
+
+    /// Returns a new `HtmlFormatter`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the title of the page rendered by [`HtmlFormatter::fmt_page`].
+    #[must_use]
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Writes a self-contained HTML document containing the source errors and
+    /// the stylesheet.
+    ///
+    /// # Parameters
+    ///
+    /// * `writer`: Where to write the HTML document.
+    /// * `files`: Files that the errors refer to.
+    /// * `source_errors`: The errors to render.
+    pub fn fmt_page<'files, Ec, Ed, Fs>(
+        &self,
+        writer: &mut dyn io::Write,
+        files: &'files Fs,
+        source_errors: &SourceErrors<'files, Ec, Ed, Fs>,
+    ) -> Result<(), files::Error>
+    where
+        Ec: ErrorCode,
+        Ed: ErrorDetail<'files, Files = Fs>,
+        Fs: Files<'files>,
+    {
+        writeln!(writer, "<!DOCTYPE html>")?;
+        writeln!(writer, "<html>")?;
+        writeln!(writer, "<head>")?;
+        writeln!(writer, "<meta charset=\"utf-8\">")?;
+        writeln!(writer, "<title>{}</title>", HtmlEscape(&self.title))?;
+        writeln!(writer, "<style>")?;
+        write!(writer, "{}", Self::CSS)?;
+        writeln!(writer, "</style>")?;
+        writeln!(writer, "</head>")?;
+        writeln!(writer, "<body>")?;
+        self.fmt_all(writer, files, source_errors)?;
+        writeln!(writer, "</body>")?;
+        writeln!(writer, "</html>")?;
+
+        Ok(())
+    }
+
+    /// Writes the diagnostic as an HTML fragment.
+    fn fmt_diagnostic<'files, Fs>(
+        writer: &mut dyn io::Write,
+        files: &'files Fs,
+        diagnostic: &Diagnostic<Fs::FileId>,
+    ) -> Result<(), files::Error>
+    where
+        Fs: Files<'files>,
+    {
+        let severity = formatter::severity_str(diagnostic.severity);
+        writeln!(writer, "<div class=\"srcerr srcerr-{severity}\">")?;
+        write!(
+            writer,
+            "<p class=\"srcerr-header\"><span class=\"srcerr-severity\">{severity}</span>"
+        )?;
+        if let Some(code) = diagnostic.code.as_ref() {
+            write!(
+                writer,
+                "<span class=\"srcerr-code\">[{}]</span>",
+                HtmlEscape(code)
+            )?;
+        }
+        writeln!(
+            writer,
+            ": <span class=\"srcerr-message\">{}</span></p>",
+            HtmlEscape(&diagnostic.message)
+        )?;
+
+        let labels = diagnostic
+            .labels
+            .iter()
+            .cloned()
+            .map(Label::from)
+            .collect::<Vec<Label<Fs::FileId>>>();
+        let mut labels_by_file = Vec::<(Fs::FileId, Vec<&Label<Fs::FileId>>)>::new();
+        labels.iter().for_each(|label| {
+            match labels_by_file
+                .iter_mut()
+                .find(|(file_id, _)| *file_id == label.span.file_id)
+            {
+                Some((_, labels)) => labels.push(label),
+                None => labels_by_file.push((label.span.file_id, vec![label])),
+            }
+        });
+        labels_by_file
+            .iter()
+            .try_for_each(|(file_id, labels)| Self::fmt_snippet(writer, files, *file_id, labels))?;
+
+        if !diagnostic.notes.is_empty() {
+            writeln!(writer, "<ul class=\"srcerr-notes\">")?;
+            diagnostic
+                .notes
+                .iter()
+                .try_for_each(|note| writeln!(writer, "<li>{}</li>", HtmlEscape(note)))?;
+            writeln!(writer, "</ul>")?;
+        }
+
+        writeln!(writer, "</div>")?;
+
+        Ok(())
+    }
+
+    /// Writes the source lines of a file covered by the labels.
+    fn fmt_snippet<'files, Fs>(
+        writer: &mut dyn io::Write,
+        files: &'files Fs,
+        file_id: Fs::FileId,
+        labels: &[&Label<Fs::FileId>],
+    ) -> Result<(), files::Error>
+    where
+        Fs: Files<'files>,
+    {
+        let source = files.source(file_id)?;
+        let source = source.as_ref();
+        let label_location = labels
+            .iter()
+            .find(|label| label.style == LabelStyle::Primary)
+            .or_else(|| labels.first())
+            .map(|label| label.span.range.start)
+            .unwrap_or(0);
+        let location = files.location(file_id, label_location)?;
+        writeln!(
+            writer,
+            "<p class=\"srcerr-location\">{}:{}:{}</p>",
+            HtmlEscape(&files.name(file_id)?.to_string()),
+            location.line_number,
+            location.column_number
+        )?;
+
+        let label_lines = labels
+            .iter()
+            .map(|label| {
+                let line_start = files.line_index(file_id, label.span.range.start)?;
+                let line_end = files.line_index(file_id, label_end_byte(&label.span.range))?;
+                Ok((line_start, line_end))
+            })
+            .collect::<Result<Vec<_>, files::Error>>()?;
+        let mut line_indices = label_lines
+            .iter()
+            .flat_map(|(line_start, line_end)| *line_start..=*line_end)
+            .collect::<BTreeSet<usize>>();
+        // Show single line gaps, as the line takes the same space as the gap marker.
+        let line_gaps = line_indices
+            .iter()
+            .filter(|line_index| {
+                !line_indices.contains(&(**line_index + 1))
+                    && line_indices.contains(&(**line_index + 2))
+            })
+            .map(|line_index| line_index + 1)
+            .collect::<Vec<usize>>();
+        line_indices.extend(line_gaps);
+
+        writeln!(writer, "<table class=\"srcerr-snippet\">")?;
+        let mut line_index_previous = None;
+        line_indices.iter().try_for_each(|line_index| {
+            let line_index = *line_index;
+            if let Some(line_index_previous) = line_index_previous {
+                if line_index > line_index_previous + 1 {
+                    writeln!(
+                        writer,
+                        "<tr class=\"srcerr-gap\"><td class=\"srcerr-line-number\">…</td>\
+                        <td class=\"srcerr-source\"></td></tr>"
+                    )?;
+                }
+            }
+            line_index_previous = Some(line_index);
+
+            let line_range = files.line_range(file_id, line_index)?;
+            let line_range = trim_line_ending(source, line_range);
+            write!(
+                writer,
+                "<tr><td class=\"srcerr-line-number\">{}</td><td class=\"srcerr-source\"><code>",
+                files.line_number(file_id, line_index)?
+            )?;
+            Self::fmt_line_segments(writer, source, line_range, labels)?;
+            writeln!(writer, "</code></td></tr>")?;
+
+            labels
+                .iter()
+                .zip(label_lines.iter())
+                .filter(|(label, (_, line_end))| {
+                    *line_end == line_index && !label.message.is_empty()
+                })
+                .try_for_each(|(label, _)| {
+                    writeln!(
+                        writer,
+                        "<tr class=\"srcerr-label-message\"><td class=\"srcerr-line-number\"></td>\
+                        <td class=\"srcerr-source\"><span class=\"{}\">{}</span></td></tr>",
+                        label_class(label.style),
+                        HtmlEscape(&label.message)
+                    )
+                })?;
+
+            Ok::<(), files::Error>(())
+        })?;
+        writeln!(writer, "</table>")?;
+
+        Ok(())
+    }
+
+    /// Writes the line, wrapping labelled segments in `<span>`s.
+    ///
+    /// Where labels overlap, primary labels take precedence over secondary
+    /// labels.
+    fn fmt_line_segments<FileId>(
+        writer: &mut dyn io::Write,
+        source: &str,
+        line_range: Range<usize>,
+        labels: &[&Label<FileId>],
+    ) -> io::Result<()> {
+        let mut boundaries = labels
+            .iter()
+            .flat_map(|label| [label.span.range.start, label.span.range.end])
+            .filter(|boundary| line_range.start < *boundary && *boundary < line_range.end)
+            .collect::<Vec<usize>>();
+        boundaries.push(line_range.start);
+        boundaries.push(line_range.end);
+        boundaries.sort_unstable();
+        boundaries.dedup();
+
+        boundaries.windows(2).try_for_each(|window| {
+            let (start, end) = (window[0], window[1]);
+            let segment = source.get(start..end).unwrap_or("");
+            let label_style = labels
+                .iter()
+                .filter(|label| label.span.range.start <= start && end <= label.span.range.end)
+                .map(|label| label.style)
+                .min_by_key(|label_style| match label_style {
+                    LabelStyle::Primary => 0,
+                    LabelStyle::Secondary => 1,
+                });
+
+            match label_style {
+                Some(label_style) => write!(
+                    writer,
+                    "<span class=\"{}\">{}</span>",
+                    label_class(label_style),
+                    HtmlEscape(segment)
+                ),
+                None => write!(writer, "{}", HtmlEscape(segment)),
+            }
+        })
+    }
+}
+
+impl Default for HtmlFormatter {
+    fn default() -> Self {
+        Self {
+            title: String::from("Errors"),
+        }
+    }
+}
+
+impl SourceErrorFormatter for HtmlFormatter {
+    fn fmt<'files, Ec, Ed, Fs>(
+        &self,
+        writer: &mut dyn io::Write,
+        files: &'files Fs,
+        source_error: &SourceError<'files, Ec, Ed, Fs>,
+    ) -> Result<(), files::Error>
+    where
+        Ec: ErrorCode,
+        Ed: ErrorDetail<'files, Files = Fs>,
+        Fs: Files<'files>,
+    {
+        Self::fmt_diagnostic(writer, files, &source_error.as_diagnostic(files))
+    }
+
+    fn fmt_all<'files, Ec, Ed, Fs>(
+        &self,
+        writer: &mut dyn io::Write,
+        files: &'files Fs,
+        source_errors: &SourceErrors<'files, Ec, Ed, Fs>,
+    ) -> Result<(), files::Error>
+    where
+        Ec: ErrorCode,
+        Ed: ErrorDetail<'files, Files = Fs>,
+        Fs: Files<'files>,
+    {
+        source_errors
+            .iter()
+            .try_for_each(|source_error| self.fmt(writer, files, source_error))?;
+
+        if let Some(summary) = source_errors.summary() {
+            writeln!(
+                writer,
+                "<p class=\"srcerr-summary\">{}</p>",
+                HtmlEscape(&summary)
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the CSS class for the label style.
+fn label_class(label_style: LabelStyle) -> &'static str {
+    match label_style {
+        LabelStyle::Primary => "srcerr-label-primary",
+        LabelStyle::Secondary => "srcerr-label-secondary",
+    }
+}
+
+/// Returns the byte index of the last character covered by the range.
+fn label_end_byte(range: &Range<usize>) -> usize {
+    if range.end > range.start {
+        range.end - 1
+    } else {
+        range.start
+    }
+}
+
+/// Returns the line range without the trailing `\n` or `\r\n`.
+fn trim_line_ending(source: &str, line_range: Range<usize>) -> Range<usize> {
+    let line = source.get(line_range.clone()).unwrap_or("");
+    let line_trimmed = line.trim_end_matches(&['\n', '\r'][..]);
+    line_range.start..line_range.start + line_trimmed.len()
+}
+
+/// Escapes `&`, `<`, `>`, `"` and `'` when displayed.
+pub(crate) struct HtmlEscape<'s>(pub(crate) &'s str);
+
+impl<'s> fmt::Display for HtmlEscape<'s> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.chars().try_for_each(|c| match c {
+            '&' => f.write_str("&amp;"),
+            '<' => f.write_str("&lt;"),
+            '>' => f.write_str("&gt;"),
+            '"' => f.write_str("&quot;"),
+            '\'' => f.write_str("&#39;"),
+            _ => fmt::Write::write_char(f, c),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use codespan_reporting::diagnostic::Severity;
+
+    use super::HtmlFormatter;
+    use crate::{
+        formatter::SourceErrorFormatter,
+        model::Trailer,
+        test_support::{self, TestErrorCode, TestErrorDetail},
+        Label, SourceError, SourceErrors,
+    };
+
+    #[test]
+    fn renders_source_error_as_html_fragment() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_invalid_value(file_id, Severity::Error);
+
+        let mut buffer = Vec::new();
+        HtmlFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+
+        let expected = [
+            "<div class=\"srcerr srcerr-error\">",
+            "<p class=\"srcerr-header\"><span class=\"srcerr-severity\">error</span>\
+             <span class=\"srcerr-code\">[E1]</span>: \
+             <span class=\"srcerr-message\">`chosen` value is invalid.</span></p>",
+            "<p class=\"srcerr-location\">examples/source_ref_hint.yaml:6:9</p>",
+            "<table class=\"srcerr-snippet\">",
+            "<tr><td class=\"srcerr-line-number\">2</td><td class=\"srcerr-source\"><code>\
+             <span class=\"srcerr-label-secondary\">available:</span></code></td></tr>",
+            "<tr><td class=\"srcerr-line-number\">3</td><td class=\"srcerr-source\"><code>\
+             <span class=\"srcerr-label-secondary\">  - &quot;abc&quot;</span></code></td></tr>",
+            "<tr><td class=\"srcerr-line-number\">4</td><td class=\"srcerr-source\"><code>\
+             <span class=\"srcerr-label-secondary\">  - &quot;def&quot;</span></code></td></tr>",
+            "<tr class=\"srcerr-label-message\"><td class=\"srcerr-line-number\"></td>\
+             <td class=\"srcerr-source\">\
+             <span class=\"srcerr-label-secondary\">defined here</span></td></tr>",
+            "<tr><td class=\"srcerr-line-number\">5</td><td class=\"srcerr-source\"><code>\
+             </code></td></tr>",
+            "<tr><td class=\"srcerr-line-number\">6</td><td class=\"srcerr-source\"><code>\
+             chosen: <span class=\"srcerr-label-primary\">&quot;ghi&quot;</span></code></td></tr>",
+            "<tr class=\"srcerr-label-message\"><td class=\"srcerr-line-number\"></td>\
+             <td class=\"srcerr-source\">\
+             <span class=\"srcerr-label-primary\">invalid value specified</span></td></tr>",
+            "</table>",
+            "<ul class=\"srcerr-notes\">",
+            "<li>`chosen` value must come from one of `available` values</li>",
+            "</ul>",
+            "</div>",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(expected, String::from_utf8(buffer)?);
+        Ok(())
+    }
+
+    #[test]
+    fn renders_severity_class_and_header() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+
+        [
+            (Severity::Bug, "bug"),
+            (Severity::Warning, "warning"),
+            (Severity::Note, "note"),
+            (Severity::Help, "help"),
+        ]
+        .iter()
+        .try_for_each(|(severity, severity_name)| {
+            let source_error = test_support::source_error_invalid_value(file_id, *severity);
+            let mut buffer = Vec::new();
+            HtmlFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+
+            let output = String::from_utf8(buffer)?;
+            assert!(output.starts_with(&format!(
+                "<div class=\"srcerr srcerr-{name}\">\n\
+                 <p class=\"srcerr-header\"><span class=\"srcerr-severity\">{name}</span>",
+                name = severity_name
+            )));
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn renders_secondary_labels_when_none_are_primary() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_secondary_only(file_id, Severity::Error);
+
+        let mut buffer = Vec::new();
+        HtmlFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+
+        let output = String::from_utf8(buffer)?;
+        assert!(
+            output.contains("<p class=\"srcerr-location\">examples/source_ref_hint.yaml:6:9</p>")
+        );
+        assert!(output.contains(
+            "chosen: <span class=\"srcerr-label-secondary\">&quot;ghi&quot;</span></code>"
+        ));
+        assert!(!output.contains("srcerr-label-primary"));
+        Ok(())
+    }
+
+    #[test]
+    fn renders_header_and_notes_for_unlabelled_error() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_unlabelled(file_id, Severity::Error);
+
+        let mut buffer = Vec::new();
+        HtmlFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+
+        let expected = [
+            "<div class=\"srcerr srcerr-error\">",
+            "<p class=\"srcerr-header\"><span class=\"srcerr-severity\">error</span>\
+             <span class=\"srcerr-code\">[E1]</span>: \
+             <span class=\"srcerr-message\">`chosen` value is invalid.</span></p>",
+            "<ul class=\"srcerr-notes\">",
+            "<li>`chosen` value must come from one of `available` values</li>",
+            "</ul>",
+            "</div>",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(expected, String::from_utf8(buffer)?);
+        Ok(())
+    }
+
+    #[test]
+    fn renders_label_past_end_of_file_at_end_of_file() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_range_invalid(file_id, Severity::Error);
+
+        let mut buffer = Vec::new();
+        HtmlFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+
+        let output = String::from_utf8(buffer)?;
+        assert!(output.contains(
+            "<p class=\"srcerr-location\">examples/source_ref_hint.yaml:7:1</p>\n\
+             <table class=\"srcerr-snippet\">\n\
+             <tr><td class=\"srcerr-line-number\">7</td><td class=\"srcerr-source\"><code>\
+             </code></td></tr>"
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn renders_gap_marker_between_distant_lines() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let detail = TestErrorDetail {
+            labels: vec![
+                Label::primary(file_id, 44..49),
+                Label::secondary(file_id, 0..3),
+            ],
+            trailers: Vec::new(),
+            suggestions: Vec::new(),
+        };
+        let source_error = SourceError::new(TestErrorCode::InvalidValue, detail, Severity::Error);
+
+        let mut buffer = Vec::new();
+        HtmlFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+
+        let output = String::from_utf8(buffer)?;
+        assert!(output.contains(
+            "<span class=\"srcerr-label-secondary\">---</span></code></td></tr>\n\
+             <tr class=\"srcerr-gap\"><td class=\"srcerr-line-number\">…</td>\
+             <td class=\"srcerr-source\"></td></tr>\n\
+             <tr><td class=\"srcerr-line-number\">6</td>"
+        ));
+        assert!(!output.contains("srcerr-notes"));
+        Ok(())
+    }
+
+    #[test]
+    fn escapes_messages_notes_and_source() -> Result<(), Box<dyn std::error::Error>> {
+        let mut files = test_support::TestFiles::new();
+        let file_id = files.add("<file>", "a: \"<b>&\"\n");
+        let detail = TestErrorDetail {
+            labels: vec![Label::primary(file_id, 3..9).with_message("not <b>")],
+            trailers: vec![Trailer::Note(String::from("use 'c' & \"d\""))],
+            suggestions: Vec::new(),
+        };
+        let source_error = SourceError::new(TestErrorCode::InvalidValue, detail, Severity::Warning);
+
+        let mut buffer = Vec::new();
+        HtmlFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+
+        let output = String::from_utf8(buffer)?;
+        assert!(output.contains("<p class=\"srcerr-location\">&lt;file&gt;:1:4</p>"));
+        assert!(output.contains(
+            "<code>a: <span class=\"srcerr-label-primary\">&quot;&lt;b&gt;&amp;&quot;</span></code>"
+        ));
+        assert!(output.contains("<span class=\"srcerr-label-primary\">not &lt;b&gt;</span>"));
+        assert!(output.contains("<li>use &#39;c&#39; &amp; &quot;d&quot;</li>"));
+        Ok(())
+    }
+
+    #[test]
+    fn renders_page_with_stylesheet_and_summary() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_errors = SourceErrors::from(vec![test_support::source_error_invalid_value(
+            file_id,
+            Severity::Error,
+        )]);
+
+        let mut buffer = Vec::new();
+        HtmlFormatter::new()
+            .with_title("Validation <Results>")
+            .fmt_page(&mut buffer, &files, &source_errors)?;
+
+        let output = String::from_utf8(buffer)?;
+        assert!(output.starts_with("<!DOCTYPE html>\n<html>\n"));
+        assert!(output.contains("<title>Validation &lt;Results&gt;</title>"));
+        assert!(output.contains(HtmlFormatter::CSS));
+        assert!(output.contains("<div class=\"srcerr srcerr-error\">"));
+        assert!(output.contains("<p class=\"srcerr-summary\">1 error emitted</p>"));
+        assert!(output.ends_with("</body>\n</html>\n"));
+        Ok(())
+    }
+}
@@ -0,0 +1,314 @@
+use std::io;
+
+use codespan_reporting::{
+    files::{self, Files},
+    term::{self, termcolor::NoColor},
+};
+
+use crate::{
+    formatter::SourceErrorFormatter,
+    model::{ErrorCode, ErrorDetail, SourceError},
+};
+
+/// Renders source errors as text without colour.
+///
+/// This is suitable for writing to log files, or terminals that do not
+/// support ANSI escape codes.
+#[derive(Clone, Debug, Default)]
+pub struct PlainTextFormatter {
+    /// Configuration for how to render the errors.
+    config: term::Config,
+}
+
+impl PlainTextFormatter {
+    /// Returns a new `PlainTextFormatter` with the default configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the configuration used to render the errors.
+    ///
+    /// The [`styles`] in the configuration are not used by this formatter.
+    ///
+    /// [`styles`]: codespan_reporting::term::Config::styles
+    #[must_use]
+    pub fn with_config(mut self, config: term::Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Returns the configuration used to render the errors.
+    pub fn config(&self) -> &term::Config {
+        &self.config
+    }
+}
+
+impl SourceErrorFormatter for PlainTextFormatter {
+    fn fmt<'files, Ec, Ed, Fs>(
+        &self,
+        writer: &mut dyn io::Write,
+        files: &'files Fs,
+        source_error: &SourceError<'files, Ec, Ed, Fs>,
+    ) -> Result<(), files::Error>
+    where
+        Ec: ErrorCode,
+        Ed: ErrorDetail<'files, Files = Fs>,
+        Fs: Files<'files>,
+    {
+        let mut writer = NoColor::new(writer);
+        term::emit(
+            &mut writer,
+            &self.config,
+            files,
+            &source_error.as_diagnostic(files),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use codespan_reporting::diagnostic::Severity;
+
+    use super::PlainTextFormatter;
+    use crate::{
+        formatter::SourceErrorFormatter,
+        model::{Applicability, Span, Suggestion, Trailer},
+        test_support::{self, TestSourceError},
+        SourceErrors,
+    };
+
+    #[test]
+    fn renders_source_error_without_color() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_invalid_value(file_id, Severity::Error);
+
+        let mut buffer = Vec::new();
+        PlainTextFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+
+        let expected = [
+            "error[E1]: `chosen` value is invalid.",
+            "  ┌─ examples/source_ref_hint.yaml:6:9",
+            "  │  ",
+            "2 │ ╭ available:",
+            "3 │ │   - \"abc\"",
+            "4 │ │   - \"def\"",
+            "  │ ╰─────────' defined here",
+            "5 │   ",
+            "6 │   chosen: \"ghi\"",
+            "  │           ^^^^^ invalid value specified",
+            "  │  ",
+            "  = `chosen` value must come from one of `available` values",
+            "",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(expected, String::from_utf8(buffer)?);
+        Ok(())
+    }
+
+    #[test]
+    fn renders_secondary_labels_when_none_are_primary() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_secondary_only(file_id, Severity::Error);
+
+        let mut buffer = Vec::new();
+        PlainTextFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+
+        let expected = [
+            "error[E1]: `chosen` value is invalid.",
+            "  ┌─ examples/source_ref_hint.yaml:2:1",
+            "  │  ",
+            "2 │ ╭ available:",
+            "3 │ │   - \"abc\"",
+            "4 │ │   - \"def\"",
+            "  │ ╰─────────' defined here",
+            "5 │   ",
+            "6 │   chosen: \"ghi\"",
+            "  │           ----- invalid value specified",
+            "  │  ",
+            "  = `chosen` value must come from one of `available` values",
+            "",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(expected, String::from_utf8(buffer)?);
+        Ok(())
+    }
+
+    #[test]
+    fn renders_header_and_notes_for_unlabelled_error() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_unlabelled(file_id, Severity::Error);
+
+        let mut buffer = Vec::new();
+        PlainTextFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+
+        let expected = [
+            "error[E1]: `chosen` value is invalid.",
+            " = `chosen` value must come from one of `available` values",
+            "",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(expected, String::from_utf8(buffer)?);
+        Ok(())
+    }
+
+    #[test]
+    fn renders_label_past_end_of_file_at_end_of_file() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_range_invalid(file_id, Severity::Error);
+
+        let mut buffer = Vec::new();
+        PlainTextFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+
+        let expected = [
+            "error[E1]: `chosen` value is invalid.",
+            "  ┌─ examples/source_ref_hint.yaml:7:1",
+            "  │",
+            "7 │ ",
+            "  │   invalid value specified",
+            "  │",
+            "  = `chosen` value must come from one of `available` values",
+            "",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(expected, String::from_utf8(buffer)?);
+        Ok(())
+    }
+
+    #[test]
+    fn renders_severity_in_header() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+
+        let headers = [
+            Severity::Bug,
+            Severity::Warning,
+            Severity::Note,
+            Severity::Help,
+        ]
+        .iter()
+        .map(|severity| {
+            let source_error = test_support::source_error_invalid_value(file_id, *severity);
+            let mut buffer = Vec::new();
+            PlainTextFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+            let output = String::from_utf8(buffer)?;
+            Ok(output.lines().next().map(String::from))
+        })
+        .collect::<Result<Vec<Option<String>>, Box<dyn std::error::Error>>>()?;
+
+        assert_eq!(
+            vec![
+                Some(String::from("bug[E1]: `chosen` value is invalid.")),
+                Some(String::from("warning[E1]: `chosen` value is invalid.")),
+                Some(String::from("note[E1]: `chosen` value is invalid.")),
+                Some(String::from("help[E1]: `chosen` value is invalid.")),
+            ],
+            headers
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn renders_trailers_with_prefixes() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let mut source_error = test_support::source_error_invalid_value(file_id, Severity::Error);
+        source_error.detail.trailers.extend([
+            Trailer::Help(String::from("use one of `\"abc\"`, `\"def\"`")),
+            Trailer::Warning(String::from("`available` values are case sensitive")),
+            Trailer::SeeAlso(String::from("https://example.com/E1")),
+        ]);
+
+        let mut buffer = Vec::new();
+        PlainTextFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+
+        let output = String::from_utf8(buffer)?;
+        let trailers = output
+            .lines()
+            .filter(|line| line.starts_with("  = "))
+            .collect::<Vec<&str>>();
+        assert_eq!(
+            vec![
+                "  = `chosen` value must come from one of `available` values",
+                "  = help: use one of `\"abc\"`, `\"def\"`",
+                "  = warning: `available` values are case sensitive",
+                "  = see also: https://example.com/E1",
+            ],
+            trailers
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn renders_suggestions_as_help_with_diff() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let mut source_error = test_support::source_error_invalid_value(file_id, Severity::Error);
+        source_error.detail.suggestions.push(
+            Suggestion::new(
+                Span::new(file_id, 44..49),
+                "\"abc\"",
+                Applicability::MaybeIncorrect,
+            )
+            .with_message("use one of the `available` values"),
+        );
+
+        let mut buffer = Vec::new();
+        PlainTextFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+
+        let expected = [
+            "error[E1]: `chosen` value is invalid.",
+            "  ┌─ examples/source_ref_hint.yaml:6:9",
+            "  │  ",
+            "2 │ ╭ available:",
+            "3 │ │   - \"abc\"",
+            "4 │ │   - \"def\"",
+            "  │ ╰─────────' defined here",
+            "5 │   ",
+            "6 │   chosen: \"ghi\"",
+            "  │           ^^^^^ invalid value specified",
+            "  │  ",
+            "  = `chosen` value must come from one of `available` values",
+            "  = help: use one of the `available` values",
+            "    6 - chosen: \"ghi\"",
+            "    6 + chosen: \"abc\"",
+            "",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(expected, String::from_utf8(buffer)?);
+        Ok(())
+    }
+
+    #[test]
+    fn renders_all_source_errors_with_summary() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_errors = vec![
+            test_support::source_error_invalid_value(file_id, Severity::Error),
+            test_support::source_error_invalid_value(file_id, Severity::Warning),
+        ]
+        .into_iter()
+        .collect::<SourceErrors<'_, _, _, _>>();
+
+        let mut buffer = Vec::new();
+        PlainTextFormatter::new().fmt_all(&mut buffer, &files, &source_errors)?;
+
+        let output = String::from_utf8(buffer)?;
+        assert!(output.starts_with("error[E1]: `chosen` value is invalid."));
+        assert!(output.contains("warning[E1]: `chosen` value is invalid."));
+        assert!(output.ends_with("1 error, 1 warning emitted\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn renders_nothing_for_empty_source_errors() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, _file_id) = test_support::files();
+        let source_errors = SourceErrors::<'_, _, _, _>::from(Vec::<TestSourceError>::new());
+
+        let mut buffer = Vec::new();
+        PlainTextFormatter::new().fmt_all(&mut buffer, &files, &source_errors)?;
+
+        assert!(buffer.is_empty());
+        Ok(())
+    }
+}
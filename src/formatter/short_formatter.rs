@@ -0,0 +1,323 @@
+use std::io;
+
+use codespan_reporting::files::{self, Files};
+
+use crate::{
+    fmt::Code,
+    formatter::{self, ColumnBase, ColumnUnit, SourceErrorFormatter},
+    model::{ErrorCode, ErrorDetail, Label, LabelStyle, SourceError},
+};
+
+/// Renders each source error on one line, in the style of `gcc`.
+///
+/// ```text
+/// examples/source_ref_hint.yaml:6:9: error[E1]: `chosen` value is invalid.
+/// ```
+///
+/// The location is taken from the first primary label, and is omitted when the
+/// error has no primary label. This is suitable for grep-able logs and editor
+/// quickfix lists.
+///
+/// When [`with_secondary_labels`] is enabled, each secondary label is written
+/// beneath the error as an indented `note:` line:
+///
+/// ```text
+///     examples/source_ref_hint.yaml:2:1: note: defined here
+/// ```
+///
+/// [`with_secondary_labels`]: Self::with_secondary_labels
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ShortFormatter {
+    /// Whether columns are counted from `0` or `1`.
+    column_base: ColumnBase,
+    /// Whether columns count characters or bytes.
+    column_unit: ColumnUnit,
+    /// Whether to write secondary labels as `note:` lines.
+    secondary_labels: bool,
+}
+
+impl ShortFormatter {
+    /// Returns a new `ShortFormatter` with 1-based character columns.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether columns are counted from `0` or `1`.
+    #[must_use]
+    pub fn with_column_base(mut self, column_base: ColumnBase) -> Self {
+        self.column_base = column_base;
+        self
+    }
+
+    /// Sets whether columns count characters or bytes.
+    #[must_use]
+    pub fn with_column_unit(mut self, column_unit: ColumnUnit) -> Self {
+        self.column_unit = column_unit;
+        self
+    }
+
+    /// Sets whether to write secondary labels as indented `note:` lines.
+    #[must_use]
+    pub fn with_secondary_labels(mut self, secondary_labels: bool) -> Self {
+        self.secondary_labels = secondary_labels;
+        self
+    }
+
+    /// Returns the `path:line:column` location of the label's start.
+    fn location<'files, Fs>(
+        &self,
+        files: &'files Fs,
+        label: &Label<Fs::FileId>,
+    ) -> Result<String, files::Error>
+    where
+        Fs: Files<'files>,
+    {
+        let name = files.name(label.span.file_id)?;
+        let location = files.location(label.span.file_id, label.span.range.start)?;
+        let column_number = match self.column_unit {
+            ColumnUnit::Char => location.column_number,
+            ColumnUnit::Byte => {
+                let line_range = files.line_range(label.span.file_id, location.line_number - 1)?;
+                label.span.range.start - line_range.start + 1
+            }
+        };
+        let column = match self.column_base {
+            ColumnBase::Zero => column_number - 1,
+            ColumnBase::One => column_number,
+        };
+
+        Ok(format!("{name}:{}:{column}", location.line_number))
+    }
+}
+
+impl SourceErrorFormatter for ShortFormatter {
+    fn fmt<'files, Ec, Ed, Fs>(
+        &self,
+        writer: &mut dyn io::Write,
+        files: &'files Fs,
+        source_error: &SourceError<'files, Ec, Ed, Fs>,
+    ) -> Result<(), files::Error>
+    where
+        Ec: ErrorCode,
+        Ed: ErrorDetail<'files, Files = Fs>,
+        Fs: Files<'files>,
+    {
+        let labels = source_error.detail.labels();
+        let severity = formatter::severity_str(source_error.severity);
+        let code = Code::string(source_error.code);
+        let description = source_error.code.description();
+
+        match labels
+            .iter()
+            .find(|label| label.style == LabelStyle::Primary)
+        {
+            Some(label) => {
+                let location = self.location(files, label)?;
+                writeln!(writer, "{location}: {severity}[{code}]: {description}")?;
+            }
+            None => writeln!(writer, "{severity}[{code}]: {description}")?,
+        }
+
+        if self.secondary_labels {
+            labels
+                .iter()
+                .filter(|label| label.style == LabelStyle::Secondary)
+                .try_for_each(|label| {
+                    let location = self.location(files, label)?;
+                    writeln!(writer, "    {location}: note: {}", label.message)?;
+                    Ok::<(), files::Error>(())
+                })?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use codespan_reporting::diagnostic::Severity;
+
+    use super::ShortFormatter;
+    use crate::{
+        formatter::{ColumnBase, ColumnUnit, SourceErrorFormatter},
+        test_support::{self, TestErrorCode, TestErrorDetail, TestFiles},
+        Label, SourceError, SourceErrors,
+    };
+
+    #[test]
+    fn renders_one_line_per_source_error() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_errors = SourceErrors::from(vec![
+            test_support::source_error_invalid_value(file_id, Severity::Error),
+            test_support::source_error_invalid_value(file_id, Severity::Warning),
+        ]);
+
+        let mut buffer = Vec::new();
+        ShortFormatter::new().fmt_all(&mut buffer, &files, &source_errors)?;
+
+        let expected = [
+            "examples/source_ref_hint.yaml:6:9: error[E1]: `chosen` value is invalid.",
+            "examples/source_ref_hint.yaml:6:9: warning[E1]: `chosen` value is invalid.",
+            "1 error, 1 warning emitted",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(expected, String::from_utf8(buffer)?);
+        Ok(())
+    }
+
+    #[test]
+    fn renders_secondary_labels_as_notes() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_invalid_value(file_id, Severity::Error);
+
+        let mut buffer = Vec::new();
+        ShortFormatter::new().with_secondary_labels(true).fmt(
+            &mut buffer,
+            &files,
+            &source_error,
+        )?;
+
+        let expected = [
+            "examples/source_ref_hint.yaml:6:9: error[E1]: `chosen` value is invalid.",
+            "    examples/source_ref_hint.yaml:2:1: note: defined here",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(expected, String::from_utf8(buffer)?);
+        Ok(())
+    }
+
+    #[test]
+    fn renders_severity_per_source_error() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_errors = [Severity::Bug, Severity::Note, Severity::Help]
+            .iter()
+            .map(|severity| test_support::source_error_invalid_value(file_id, *severity))
+            .collect::<Vec<_>>();
+
+        let mut buffer = Vec::new();
+        source_errors.iter().try_for_each(|source_error| {
+            ShortFormatter::new().fmt(&mut buffer, &files, source_error)
+        })?;
+
+        let expected = [
+            "examples/source_ref_hint.yaml:6:9: bug[E1]: `chosen` value is invalid.",
+            "examples/source_ref_hint.yaml:6:9: note[E1]: `chosen` value is invalid.",
+            "examples/source_ref_hint.yaml:6:9: help[E1]: `chosen` value is invalid.",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(expected, String::from_utf8(buffer)?);
+        Ok(())
+    }
+
+    #[test]
+    fn omits_location_when_no_label_is_primary() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error_secondary_only =
+            test_support::source_error_secondary_only(file_id, Severity::Error);
+        let source_error_unlabelled =
+            test_support::source_error_unlabelled(file_id, Severity::Error);
+
+        let mut buffer = Vec::new();
+        let short_formatter = ShortFormatter::new().with_secondary_labels(true);
+        short_formatter.fmt(&mut buffer, &files, &source_error_secondary_only)?;
+        short_formatter.fmt(&mut buffer, &files, &source_error_unlabelled)?;
+
+        let expected = [
+            "error[E1]: `chosen` value is invalid.",
+            "    examples/source_ref_hint.yaml:6:9: note: invalid value specified",
+            "    examples/source_ref_hint.yaml:2:1: note: defined here",
+            "error[E1]: `chosen` value is invalid.",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(expected, String::from_utf8(buffer)?);
+        Ok(())
+    }
+
+    #[test]
+    fn renders_label_past_end_of_file_at_end_of_file() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_range_invalid(file_id, Severity::Error);
+
+        let mut buffer = Vec::new();
+        ShortFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+
+        assert_eq!(
+            "examples/source_ref_hint.yaml:7:1: error[E1]: `chosen` value is invalid.\n",
+            String::from_utf8(buffer)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn renders_columns_with_configured_base_and_unit() -> Result<(), Box<dyn std::error::Error>> {
+        let mut files = TestFiles::new();
+        // `é` is 2 bytes, so the value starts at character column 4 and byte column 5.
+        let file_id = files.add("file.yaml", "a: 1\né: bad\n");
+        let source_error = SourceError::new(
+            TestErrorCode::InvalidValue,
+            TestErrorDetail {
+                labels: vec![Label::primary(file_id, 9..12)],
+                trailers: Vec::new(),
+                suggestions: Vec::new(),
+            },
+            Severity::Error,
+        );
+
+        let render = |short_formatter: ShortFormatter| {
+            let mut buffer = Vec::new();
+            short_formatter
+                .fmt(&mut buffer, &files, &source_error)
+                .expect("Expected source error to be rendered.");
+            String::from_utf8(buffer).expect("Expected output to be UTF-8.")
+        };
+
+        assert_eq!(
+            "file.yaml:2:4: error[E1]: `chosen` value is invalid.\n",
+            render(ShortFormatter::new())
+        );
+        assert_eq!(
+            "file.yaml:2:3: error[E1]: `chosen` value is invalid.\n",
+            render(ShortFormatter::new().with_column_base(ColumnBase::Zero))
+        );
+        assert_eq!(
+            "file.yaml:2:5: error[E1]: `chosen` value is invalid.\n",
+            render(ShortFormatter::new().with_column_unit(ColumnUnit::Byte))
+        );
+        assert_eq!(
+            "file.yaml:2:4: error[E1]: `chosen` value is invalid.\n",
+            render(
+                ShortFormatter::new()
+                    .with_column_base(ColumnBase::Zero)
+                    .with_column_unit(ColumnUnit::Byte)
+            )
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn renders_source_error_without_labels() -> Result<(), Box<dyn std::error::Error>> {
+        let files = TestFiles::new();
+        let source_error = SourceError::new(
+            TestErrorCode::InvalidValue,
+            TestErrorDetail {
+                labels: Vec::new(),
+                trailers: Vec::new(),
+                suggestions: Vec::new(),
+            },
+            Severity::Bug,
+        );
+
+        let mut buffer = Vec::new();
+        ShortFormatter::new().fmt(&mut buffer, &files, &source_error)?;
+
+        assert_eq!(
+            "bug[E1]: `chosen` value is invalid.\n",
+            String::from_utf8(buffer)?
+        );
+        Ok(())
+    }
+}
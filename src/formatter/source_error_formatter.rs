@@ -0,0 +1,60 @@
+use std::io;
+
+use codespan_reporting::files::{self, Files};
+
+use crate::model::{ErrorCode, ErrorDetail, SourceError, SourceErrors};
+
+/// Renders [`SourceError`]s to a writer.
+///
+/// Implementations decide the output format, such as plain text or ANSI
+/// colored text.
+pub trait SourceErrorFormatter {
+    /// Writes the source error to the writer.
+    ///
+    /// # Parameters
+    ///
+    /// * `writer`: Where to write the rendered error.
+    /// * `files`: Files that the error refers to.
+    /// * `source_error`: The error to render.
+    fn fmt<'files, Ec, Ed, Fs>(
+        &self,
+        writer: &mut dyn io::Write,
+        files: &'files Fs,
+        source_error: &SourceError<'files, Ec, Ed, Fs>,
+    ) -> Result<(), files::Error>
+    where
+        Ec: ErrorCode,
+        Ed: ErrorDetail<'files, Files = Fs>,
+        Fs: Files<'files>;
+
+    /// Writes every source error to the writer, followed by a summary line.
+    ///
+    /// Nothing is written when `source_errors` is empty.
+    ///
+    /// # Parameters
+    ///
+    /// * `writer`: Where to write the rendered errors.
+    /// * `files`: Files that the errors refer to.
+    /// * `source_errors`: The errors to render.
+    fn fmt_all<'files, Ec, Ed, Fs>(
+        &self,
+        writer: &mut dyn io::Write,
+        files: &'files Fs,
+        source_errors: &SourceErrors<'files, Ec, Ed, Fs>,
+    ) -> Result<(), files::Error>
+    where
+        Ec: ErrorCode,
+        Ed: ErrorDetail<'files, Files = Fs>,
+        Fs: Files<'files>,
+    {
+        source_errors
+            .iter()
+            .try_for_each(|source_error| self.fmt(writer, files, source_error))?;
+
+        if let Some(summary) = source_errors.summary() {
+            writeln!(writer, "{summary}")?;
+        }
+
+        Ok(())
+    }
+}
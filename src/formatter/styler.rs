@@ -0,0 +1,166 @@
+use codespan_reporting::{
+    diagnostic::Severity,
+    term::{
+        termcolor::{Color, ColorSpec},
+        Styles,
+    },
+};
+
+use crate::model::LabelStyle;
+
+/// Colours used when rendering source errors.
+///
+/// This wraps codespan-reporting's [`Styles`], providing a builder to theme
+/// parts of the rendered error.
+///
+/// # Examples
+///
+/// ```rust
+/// use srcerr::{
+///     codespan_reporting::{
+///         diagnostic::Severity,
+///         term::termcolor::{Color, ColorSpec},
+///     },
+///     formatter::Styler,
+/// };
+///
+/// let mut header_error = ColorSpec::new();
+/// header_error.set_fg(Some(Color::Magenta)).set_bold(true);
+///
+/// let styler = Styler::new()
+///     .with_header(Severity::Error, header_error.clone())
+///     .with_accent(Color::Cyan);
+///
+/// assert_eq!(&header_error, styler.styles().header(Severity::Error));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Styler {
+    /// The colours for each part of the rendered error.
+    styles: Styles,
+}
+
+impl Styler {
+    /// Returns a new `Styler` with the default colours.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the colour of the header for errors of the given severity.
+    #[must_use]
+    pub fn with_header(mut self, severity: Severity, color_spec: ColorSpec) -> Self {
+        let header = match severity {
+            Severity::Bug => &mut self.styles.header_bug,
+            Severity::Error => &mut self.styles.header_error,
+            Severity::Warning => &mut self.styles.header_warning,
+            Severity::Note => &mut self.styles.header_note,
+            Severity::Help => &mut self.styles.header_help,
+        };
+        *header = color_spec;
+        self
+    }
+
+    /// Sets the colour of the header message.
+    #[must_use]
+    pub fn with_header_message(mut self, color_spec: ColorSpec) -> Self {
+        self.styles.header_message = color_spec;
+        self
+    }
+
+    /// Sets the colour of labels with the given severity and style.
+    ///
+    /// Secondary labels share a colour regardless of severity.
+    #[must_use]
+    pub fn with_label(
+        mut self,
+        severity: Severity,
+        label_style: LabelStyle,
+        color_spec: ColorSpec,
+    ) -> Self {
+        let label = match (label_style, severity) {
+            (LabelStyle::Primary, Severity::Bug) => &mut self.styles.primary_label_bug,
+            (LabelStyle::Primary, Severity::Error) => &mut self.styles.primary_label_error,
+            (LabelStyle::Primary, Severity::Warning) => &mut self.styles.primary_label_warning,
+            (LabelStyle::Primary, Severity::Note) => &mut self.styles.primary_label_note,
+            (LabelStyle::Primary, Severity::Help) => &mut self.styles.primary_label_help,
+            (LabelStyle::Secondary, _) => &mut self.styles.secondary_label,
+        };
+        *label = color_spec;
+        self
+    }
+
+    /// Sets the colour of line numbers, the source border, note bullets and
+    /// secondary labels.
+    #[must_use]
+    pub fn with_accent(mut self, color: Color) -> Self {
+        let mut color_spec = ColorSpec::new();
+        color_spec.set_fg(Some(color));
+
+        self.styles.line_number = color_spec.clone();
+        self.styles.source_border = color_spec.clone();
+        self.styles.note_bullet = color_spec.clone();
+        self.styles.secondary_label = color_spec;
+        self
+    }
+
+    /// Returns the colours for each part of the rendered error.
+    pub fn styles(&self) -> &Styles {
+        &self.styles
+    }
+
+    /// Returns the underlying [`Styles`].
+    pub fn into_styles(self) -> Styles {
+        self.styles
+    }
+}
+
+impl From<Styles> for Styler {
+    fn from(styles: Styles) -> Self {
+        Self { styles }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use codespan_reporting::{
+        diagnostic::{self, Severity},
+        term::termcolor::{Color, ColorSpec},
+    };
+
+    use super::Styler;
+    use crate::model::LabelStyle;
+
+    #[test]
+    fn with_label_sets_primary_label_for_severity() {
+        let mut color_spec = ColorSpec::new();
+        color_spec.set_fg(Some(Color::Magenta));
+
+        let styler =
+            Styler::new().with_label(Severity::Warning, LabelStyle::Primary, color_spec.clone());
+
+        assert_eq!(
+            &color_spec,
+            styler
+                .styles()
+                .label(Severity::Warning, diagnostic::LabelStyle::Primary)
+        );
+        assert_ne!(
+            &color_spec,
+            styler
+                .styles()
+                .label(Severity::Error, diagnostic::LabelStyle::Primary)
+        );
+    }
+
+    #[test]
+    fn with_accent_sets_line_number_border_and_secondary_label() {
+        let styler = Styler::new().with_accent(Color::Green);
+
+        let mut color_spec = ColorSpec::new();
+        color_spec.set_fg(Some(Color::Green));
+        let styles = styler.styles();
+        assert_eq!(color_spec, styles.line_number);
+        assert_eq!(color_spec, styles.source_border);
+        assert_eq!(color_spec, styles.note_bullet);
+        assert_eq!(color_spec, styles.secondary_label);
+    }
+}
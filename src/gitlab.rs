@@ -0,0 +1,12 @@
+//! GitLab Code Quality export of source errors.
+//!
+//! Errors can be shown in merge requests through a [Code Quality report].
+//!
+//! This module requires the `"json"` feature, as the report is serialized with
+//! `serde_json`. There is no separate `"gitlab"` feature.
+//!
+//! [Code Quality report]: https://docs.gitlab.com/ee/ci/testing/code_quality.html#code-quality-report-format
+
+pub use self::code_quality_exporter::CodeQualityExporter;
+
+mod code_quality_exporter;
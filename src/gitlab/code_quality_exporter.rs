@@ -0,0 +1,367 @@
+use codespan_reporting::{
+    diagnostic::Severity,
+    files::{self, Files},
+};
+use serde_json::{json, Value};
+
+use crate::{
+    fmt::Code,
+    model::{ErrorCode, ErrorDetail, LabelStyle, LineColumn, SourceErrors, Trailer},
+};
+
+/// Exports source errors as a GitLab Code Quality report.
+///
+/// This requires the `"json"` feature.
+///
+/// Each primary label of a [`SourceError`] becomes an issue. Errors without
+/// primary labels become an issue at their first label, and errors without
+/// labels are skipped, as an issue must have a file path.
+///
+///
+/// * `check_name` is the [`Code::string`] of the error code.
+/// * `description` is the error code description, followed by the label message
+///   and notes on separate lines.
+/// * `severity` is derived from the [`Severity`].
+/// * `location` holds the file name with 1-based line and column positions,
+///   where columns count Unicode scalar values.
+/// * `fingerprint` is a hash of the code, file, description, and the text of
+///   the label's first line, so that GitLab can track the issue across
+///   pipelines. Positions are not hashed, so the fingerprint does not change
+///   when lines are added above the issue.
+///
+/// [`SourceError`]: crate::SourceError
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CodeQualityExporter;
+
+impl CodeQualityExporter {
+    /// Returns a new `CodeQualityExporter`.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Returns the Code Quality report as a JSON string.
+    ///
+    /// # Parameters
+    ///
+    /// * `files`: Files that the errors refer to.
+    /// * `source_errors`: The errors to export.
+    pub fn to_string<'files, Ec, Ed, Fs>(
+        &self,
+        files: &'files Fs,
+        source_errors: &SourceErrors<'files, Ec, Ed, Fs>,
+    ) -> Result<String, files::Error>
+    where
+        Ec: ErrorCode,
+        Ed: ErrorDetail<'files, Files = Fs>,
+        Fs: Files<'files>,
+    {
+        self.to_value(files, source_errors)
+            .map(|code_quality_report| code_quality_report.to_string())
+    }
+
+    /// Returns the Code Quality report as a JSON value.
+    ///
+    /// # Parameters
+    ///
+    /// * `files`: Files that the errors refer to.
+    /// * `source_errors`: The errors to export.
+    pub fn to_value<'files, Ec, Ed, Fs>(
+        &self,
+        files: &'files Fs,
+        source_errors: &SourceErrors<'files, Ec, Ed, Fs>,
+    ) -> Result<Value, files::Error>
+    where
+        Ec: ErrorCode,
+        Ed: ErrorDetail<'files, Files = Fs>,
+        Fs: Files<'files>,
+    {
+        let mut issues = Vec::new();
+        source_errors.iter().try_for_each(|source_error| {
+            let check_name = Code::string(source_error.code);
+            let trailers = source_error
+                .detail
+                .trailers(files)
+                .iter()
+                .map(Trailer::to_string)
+                .collect::<Vec<String>>();
+
+            // Errors without primary labels are reported at their first label.
+            let labels = source_error.detail.labels();
+            let has_primary = labels
+                .iter()
+                .any(|label| label.style == LabelStyle::Primary);
+            labels
+                .iter()
+                .enumerate()
+                .filter(|(label_index, label)| {
+                    if has_primary {
+                        label.style == LabelStyle::Primary
+                    } else {
+                        *label_index == 0
+                    }
+                })
+                .try_for_each(|(_, label)| {
+                    let path = files
+                        .name(label.span.file_id)?
+                        .to_string()
+                        .replace('\\', "/");
+                    let begin = LineColumn::new(files, label.span.file_id, label.span.range.start)?;
+                    let end = LineColumn::new(files, label.span.file_id, label.span.range.end)?;
+
+                    let mut description = String::from(source_error.code.description());
+                    std::iter::once(&label.message)
+                        .filter(|label_message| !label_message.is_empty())
+                        .chain(trailers.iter())
+                        .for_each(|line| {
+                            description.push('\n');
+                            description.push_str(line);
+                        });
+
+                    let line_range = files.line_range(
+                        label.span.file_id,
+                        files.line_index(label.span.file_id, label.span.range.start)?,
+                    )?;
+                    let source = files.source(label.span.file_id)?;
+                    let line = source.as_ref().get(line_range.clone()).ok_or(
+                        files::Error::IndexTooLarge {
+                            given: line_range.end,
+                            max: source.as_ref().len(),
+                        },
+                    )?;
+                    let fingerprint = fingerprint(&[
+                        check_name.as_bytes(),
+                        path.as_bytes(),
+                        description.as_bytes(),
+                        line.trim().as_bytes(),
+                    ]);
+
+                    issues.push(json!({
+                        "description": description,
+                        "check_name": check_name,
+                        "fingerprint": fingerprint,
+                        "severity": severity(source_error.severity),
+                        "location": {
+                            "path": path,
+                            "positions": {
+                                "begin": { "line": begin.line, "column": begin.column },
+                                "end": { "line": end.line, "column": end.column },
+                            },
+                        },
+                    }));
+
+                    Ok::<(), files::Error>(())
+                })
+        })?;
+
+        Ok(Value::Array(issues))
+    }
+}
+
+/// Returns the Code Quality `severity` for the severity.
+fn severity(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Bug => "critical",
+        Severity::Error => "major",
+        Severity::Warning => "minor",
+        Severity::Note | Severity::Help => "info",
+    }
+}
+
+/// Returns a stable hex encoded FNV-1a hash of the parts.
+///
+/// `DefaultHasher` is not used, as its output may change between Rust
+/// releases, which would make GitLab report every issue as new.
+fn fingerprint(parts: &[&[u8]]) -> String {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0100_0000_01b3;
+
+    let hash = parts.iter().fold(OFFSET_BASIS, |hash, part| {
+        // Separate parts so that `["ab", "c"]` and `["a", "bc"]` differ.
+        let hash = part.iter().fold(hash, |hash, byte| {
+            (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+        });
+        (hash ^ 0xff).wrapping_mul(PRIME)
+    });
+
+    format!("{hash:016x}")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Range;
+
+    use codespan_reporting::diagnostic::Severity;
+    use serde_json::json;
+
+    use super::{fingerprint, CodeQualityExporter};
+    use crate::{
+        test_support::{self, TestErrorCode, TestErrorDetail, TestFiles, TestSourceError},
+        Label, SourceError, SourceErrors,
+    };
+
+    #[test]
+    fn to_value_returns_issue_per_primary_label() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_errors = SourceErrors::from(vec![test_support::source_error_invalid_value(
+            file_id,
+            Severity::Error,
+        )]);
+
+        let mut code_quality_report =
+            CodeQualityExporter::new().to_value(&files, &source_errors)?;
+        let fingerprint = code_quality_report[0]
+            .as_object_mut()
+            .and_then(|issue| issue.remove("fingerprint"));
+
+        assert_eq!(
+            json!([{
+                "description": "`chosen` value is invalid.\n\
+                    invalid value specified\n\
+                    `chosen` value must come from one of `available` values",
+                "check_name": "E1",
+                "severity": "major",
+                "location": {
+                    "path": "examples/source_ref_hint.yaml",
+                    "positions": {
+                        "begin": { "line": 6, "column": 9 },
+                        "end": { "line": 6, "column": 14 },
+                    },
+                },
+            }]),
+            code_quality_report
+        );
+        // Pinned, as GitLab matches issues across pipelines by fingerprint.
+        assert_eq!(Some(json!("94b1340fbd855245")), fingerprint);
+        Ok(())
+    }
+
+    #[test]
+    fn to_value_fingerprint_is_unchanged_when_lines_are_added_above(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut files = TestFiles::new();
+        let file_id = files.add("config.yaml", "chosen: \"ghi\"\n");
+        let file_id_shifted = files.add("config.yaml", "# comment\n\nchosen: \"ghi\"\n");
+        let source_error = |file_id: usize, range: Range<usize>| {
+            let detail = TestErrorDetail {
+                labels: vec![Label::primary(file_id, range)],
+                trailers: Vec::new(),
+                suggestions: Vec::new(),
+            };
+            SourceError::new(TestErrorCode::InvalidValue, detail, Severity::Error)
+        };
+        let source_errors = SourceErrors::from(vec![
+            source_error(file_id, 8..13),
+            source_error(file_id_shifted, 19..24),
+        ]);
+
+        let code_quality_report = CodeQualityExporter::new().to_value(&files, &source_errors)?;
+
+        assert_eq!(
+            code_quality_report[0]["fingerprint"],
+            code_quality_report[1]["fingerprint"]
+        );
+        assert_ne!(
+            code_quality_report[0]["location"],
+            code_quality_report[1]["location"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn to_value_reports_first_label_when_none_are_primary() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let (files, file_id) = test_support::files();
+        let source_errors = SourceErrors::from(vec![
+            test_support::source_error_secondary_only(file_id, Severity::Error),
+            test_support::source_error_unlabelled(file_id, Severity::Error),
+        ]);
+
+        let code_quality_report = CodeQualityExporter::new().to_value(&files, &source_errors)?;
+
+        assert_eq!(Some(1), code_quality_report.as_array().map(Vec::len));
+        assert_eq!(
+            json!({
+                "begin": { "line": 6, "column": 9 },
+                "end": { "line": 6, "column": 14 },
+            }),
+            code_quality_report[0]["location"]["positions"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn to_value_clamps_label_past_end_of_file() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_errors = SourceErrors::from(vec![test_support::source_error_range_invalid(
+            file_id,
+            Severity::Error,
+        )]);
+
+        let code_quality_report = CodeQualityExporter::new().to_value(&files, &source_errors)?;
+
+        assert_eq!(
+            json!({
+                "begin": { "line": 7, "column": 1 },
+                "end": { "line": 7, "column": 1 },
+            }),
+            code_quality_report[0]["location"]["positions"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn to_value_maps_severity() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_errors = [
+            Severity::Bug,
+            Severity::Error,
+            Severity::Warning,
+            Severity::Note,
+            Severity::Help,
+        ]
+        .iter()
+        .map(|severity| test_support::source_error_invalid_value(file_id, *severity))
+        .collect::<SourceErrors<'_, _, _, _>>();
+
+        let code_quality_report = CodeQualityExporter::new().to_value(&files, &source_errors)?;
+
+        let severities = code_quality_report
+            .as_array()
+            .map(|issues| {
+                issues
+                    .iter()
+                    .map(|issue| issue["severity"].clone())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        assert_eq!(
+            vec![
+                json!("critical"),
+                json!("major"),
+                json!("minor"),
+                json!("info"),
+                json!("info"),
+            ],
+            severities
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn to_string_returns_empty_array_for_no_errors() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, _file_id) = test_support::files();
+        let source_errors = SourceErrors::from(Vec::<TestSourceError>::new());
+
+        assert_eq!(
+            "[]",
+            CodeQualityExporter::new().to_string(&files, &source_errors)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn fingerprint_is_stable_and_separates_parts() {
+        assert_eq!(fingerprint(&[b"ab", b"c"]), fingerprint(&[b"ab", b"c"]));
+        assert_ne!(fingerprint(&[b"ab", b"c"]), fingerprint(&[b"a", b"bc"]));
+    }
+}
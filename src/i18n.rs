@@ -0,0 +1,45 @@
+//! Translated error messages, using [Fluent].
+//!
+//! This is enabled by the `"i18n"` feature. Messages are looked up by the
+//! error code string, e.g. `E002`. The message value is the error code
+//! description, and its attributes hold the label messages and trailers:
+//!
+//! ```ftl
+//! E002 = `chosen` value is invalid.
+//!     .invalid = value `{ $value }` is not one of the available values
+//!     .defined = defined here
+//!     .note-available = `chosen` value must come from one of `available` values
+//! ```
+//!
+//! [`ErrorDetailI18n`] maps each label and trailer of an error to one of these
+//! attributes, with typed arguments. [`Localizer`] holds a bundle per locale,
+//! and renders a [`SourceError`] as a `Diagnostic` in the locale chosen at
+//! render time:
+//!
+//! ```rust,ignore
+//! let localizer = Localizer::new()
+//!     .with_ftl(langid_en, include_str!("../i18n/en.ftl"))?
+//!     .with_ftl(langid_fr, include_str!("../i18n/fr.ftl"))?;
+//! let diagnostic = localizer.as_diagnostic(&locale, &files, &source_error);
+//! ```
+//!
+//! Messages missing from the locale's bundle fall back to the English bundle,
+//! then to the English text from the [`ErrorCode`] and [`ErrorDetail`].
+//! [`Localizer::missing_messages`] lists the messages each bundle lacks, so a
+//! test can check that every error code is translated.
+//!
+//! [Fluent]: https://projectfluent.org/
+//! [`ErrorCode`]: crate::ErrorCode
+//! [`ErrorDetail`]: crate::ErrorDetail
+//! [`SourceError`]: crate::SourceError
+
+pub use self::{
+    error_detail_i18n::ErrorDetailI18n, i18n_error::I18nError, i18n_message::I18nMessage,
+    localizer::Localizer, missing_message::MissingMessage,
+};
+
+mod error_detail_i18n;
+mod i18n_error;
+mod i18n_message;
+mod localizer;
+mod missing_message;
@@ -0,0 +1,33 @@
+use fluent_bundle::FluentArgs;
+
+use crate::{i18n::I18nMessage, ErrorDetail};
+
+/// Maps an [`ErrorDetail`]'s messages to translated message attributes.
+///
+/// Each method defaults to no translations, so the English text is used for
+/// anything that is not mapped.
+pub trait ErrorDetailI18n<'files>: ErrorDetail<'files> {
+    /// Returns the arguments to format the error code description with.
+    fn description_args(&self) -> FluentArgs<'_> {
+        FluentArgs::new()
+    }
+
+    /// Returns the translated message for each label, in the same order as
+    /// [`ErrorDetail::labels`].
+    ///
+    /// Labels mapped to `None`, or beyond the end of the list, keep their
+    /// English message.
+    fn label_messages(&self) -> Vec<Option<I18nMessage<'_>>> {
+        Vec::new()
+    }
+
+    /// Returns the translated message for each trailer, in the same order as
+    /// [`ErrorDetail::trailers`].
+    ///
+    /// Trailers mapped to `None`, or beyond the end of the list, keep their
+    /// English message.
+    fn trailer_messages(&self, files: &Self::Files) -> Vec<Option<I18nMessage<'_>>> {
+        let _ = files;
+        Vec::new()
+    }
+}
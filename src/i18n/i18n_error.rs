@@ -0,0 +1,45 @@
+use std::fmt;
+
+use fluent_bundle::FluentError;
+use unic_langid::LanguageIdentifier;
+
+/// Error when adding or checking translations in a [`Localizer`].
+///
+/// [`Localizer`]: crate::i18n::Localizer
+#[derive(Debug)]
+pub enum I18nError {
+    /// The Fluent resource has syntax errors, or redefines messages.
+    FtlInvalid {
+        /// Locale of the resource.
+        locale: LanguageIdentifier,
+        /// Errors from parsing or adding the resource.
+        errors: Vec<FluentError>,
+    },
+    /// Translations were checked for an error code type whose
+    /// `ErrorCode::all` is empty, so no codes could be checked.
+    ErrorCodesNone {
+        /// Name of the error code type.
+        error_code_type: &'static str,
+    },
+}
+
+impl fmt::Display for I18nError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::FtlInvalid { locale, errors } => {
+                write!(f, "Failed to add `{}` translations:", locale)?;
+                errors
+                    .iter()
+                    .try_for_each(|error| write!(f, "\n* {}", error))
+            }
+            Self::ErrorCodesNone { error_code_type } => write!(
+                f,
+                "`{}::all` returns no error codes, so translations cannot be checked. \
+                Implement `ErrorCode::all`, or use `#[derive(ErrorCode)]`.",
+                error_code_type
+            ),
+        }
+    }
+}
+
+impl std::error::Error for I18nError {}
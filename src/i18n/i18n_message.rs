@@ -0,0 +1,47 @@
+use std::borrow::Cow;
+
+use fluent_bundle::{FluentArgs, FluentValue};
+
+/// Reference to a translated message attribute, with its arguments.
+///
+/// The attribute belongs to the message whose ID is the error code string.
+#[derive(Debug, Default)]
+pub struct I18nMessage<'args> {
+    /// Name of the attribute, e.g. `"invalid"` for `.invalid = ..`.
+    pub attribute: Cow<'static, str>,
+    /// Arguments to format the message with.
+    pub args: FluentArgs<'args>,
+}
+
+impl<'args> I18nMessage<'args> {
+    /// Returns a new `I18nMessage` without arguments.
+    ///
+    /// # Parameters
+    ///
+    /// * `attribute`: Name of the attribute, e.g. `"invalid"`.
+    pub fn new(attribute: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            attribute: attribute.into(),
+            args: FluentArgs::new(),
+        }
+    }
+
+    /// Adds an argument to format the message with.
+    ///
+    /// Numbers are formatted and matched against plural categories by the
+    /// locale, so pass them as numbers rather than strings.
+    ///
+    /// # Parameters
+    ///
+    /// * `key`: Name of the argument, e.g. `"value"` for `{ $value }`.
+    /// * `value`: Value of the argument.
+    #[must_use]
+    pub fn with_arg(
+        mut self,
+        key: impl Into<Cow<'args, str>>,
+        value: impl Into<FluentValue<'args>>,
+    ) -> Self {
+        self.args.set(key, value);
+        self
+    }
+}
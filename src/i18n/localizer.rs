@@ -0,0 +1,513 @@
+use std::fmt;
+
+use codespan_reporting::{diagnostic::Diagnostic, files::Files};
+use fluent_bundle::{FluentArgs, FluentBundle, FluentError, FluentResource};
+use unic_langid::{langid, LanguageIdentifier};
+
+use crate::{
+    fmt::Code,
+    i18n::{ErrorDetailI18n, I18nError, MissingMessage},
+    model::{ErrorCode, SourceError, Trailer},
+};
+
+/// Looks up translated messages, and renders source errors in a locale.
+///
+/// Each locale has a bundle of messages. Messages are looked up in the bundle
+/// of the requested locale, then bundles with the same language, then the
+/// fallback locale's bundles, which default to `en`.
+pub struct Localizer {
+    /// Bundle of messages for each locale.
+    bundles: Vec<FluentBundle<FluentResource>>,
+    /// Locale used when a message is not translated.
+    fallback: LanguageIdentifier,
+}
+
+impl Localizer {
+    /// Returns a new `Localizer` without translations.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the locale used when a message is not translated, `en` by default.
+    #[must_use]
+    pub fn with_fallback(mut self, fallback: LanguageIdentifier) -> Self {
+        self.fallback = fallback;
+        self
+    }
+
+    /// Adds messages in Fluent syntax for the locale.
+    ///
+    /// Unicode isolation marks are not inserted around arguments, as they are
+    /// visible in many terminals.
+    ///
+    /// # Parameters
+    ///
+    /// * `locale`: Locale of the messages.
+    /// * `ftl`: Messages in Fluent syntax.
+    pub fn with_ftl(mut self, locale: LanguageIdentifier, ftl: &str) -> Result<Self, I18nError> {
+        let resource = FluentResource::try_new(String::from(ftl)).map_err(|(_, errors)| {
+            I18nError::FtlInvalid {
+                locale: locale.clone(),
+                errors: errors.into_iter().map(FluentError::from).collect(),
+            }
+        })?;
+
+        let bundle_index = self
+            .bundles
+            .iter()
+            .position(|bundle| bundle.locales.first() == Some(&locale));
+        let bundle = match bundle_index {
+            Some(bundle_index) => &mut self.bundles[bundle_index],
+            None => {
+                let mut bundle = FluentBundle::new(vec![locale.clone()]);
+                bundle.set_use_isolating(false);
+                self.bundles.push(bundle);
+                let bundle_index = self.bundles.len() - 1;
+                &mut self.bundles[bundle_index]
+            }
+        };
+        bundle
+            .add_resource(resource)
+            .map_err(|errors| I18nError::FtlInvalid { locale, errors })?;
+
+        Ok(self)
+    }
+
+    /// Adds a bundle of messages, for bundles that need custom functions or
+    /// formatters.
+    ///
+    /// The bundle's locale is the first of its `locales`. Bundles are looked up
+    /// in the order they are added.
+    #[must_use]
+    pub fn with_bundle(mut self, bundle: FluentBundle<FluentResource>) -> Self {
+        self.bundles.push(bundle);
+        self
+    }
+
+    /// Returns the message or attribute formatted for the locale, or `None`
+    /// if no bundle translates it.
+    ///
+    /// A bundle whose message fails to format, e.g. when an argument is
+    /// missing, is skipped in favour of the next bundle.
+    ///
+    /// # Parameters
+    ///
+    /// * `locale`: Locale to format the message for.
+    /// * `id`: ID of the message, e.g. `"E002"`.
+    /// * `attribute`: Name of the attribute, or `None` for the message value.
+    /// * `args`: Arguments to format the message with.
+    pub fn message(
+        &self,
+        locale: &LanguageIdentifier,
+        id: &str,
+        attribute: Option<&str>,
+        args: Option<&FluentArgs>,
+    ) -> Option<String> {
+        self.bundles_for(locale).find_map(|bundle| {
+            let message = bundle.get_message(id)?;
+            let pattern = match attribute {
+                Some(attribute) => message.get_attribute(attribute)?.value(),
+                None => message.value()?,
+            };
+
+            let mut errors = Vec::new();
+            let value = bundle.format_pattern(pattern, args, &mut errors);
+            if errors.is_empty() {
+                Some(value.into_owned())
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Returns a `Diagnostic` built from the error, with its messages
+    /// translated for the locale.
+    ///
+    /// This is [`SourceError::as_diagnostic`] with the description, label
+    /// messages, and trailers replaced by their translations, where present.
+    /// Trailer prefixes such as `help:`, and suggestion diffs, are not
+    /// translated.
+    ///
+    /// # Parameters
+    ///
+    /// * `locale`: Locale to render the error in.
+    /// * `files`: Files that the error refers to.
+    /// * `source_error`: The error to render.
+    pub fn as_diagnostic<'files, Ec, Ed, Fs>(
+        &self,
+        locale: &LanguageIdentifier,
+        files: &'files Fs,
+        source_error: &SourceError<'files, Ec, Ed, Fs>,
+    ) -> Diagnostic<Fs::FileId>
+    where
+        Ec: ErrorCode,
+        Ed: ErrorDetailI18n<'files, Files = Fs>,
+        Fs: Files<'files>,
+    {
+        let mut diagnostic = source_error.as_diagnostic(files);
+        let detail = &source_error.detail;
+        let id = Code::string(source_error.code);
+
+        let description_args = detail.description_args();
+        if let Some(description) = self.message(locale, &id, None, Some(&description_args)) {
+            diagnostic.message = description;
+        }
+
+        let label_messages = detail.label_messages();
+        diagnostic
+            .labels
+            .iter_mut()
+            .zip(label_messages.iter())
+            .for_each(|(label, label_message)| {
+                if let Some(message) = label_message.as_ref().and_then(|label_message| {
+                    self.message(
+                        locale,
+                        &id,
+                        Some(&label_message.attribute),
+                        Some(&label_message.args),
+                    )
+                }) {
+                    label.message = message;
+                }
+            });
+
+        // Notes begin with the trailers, followed by suggestion helps.
+        let trailer_messages = detail.trailer_messages(files);
+        diagnostic
+            .notes
+            .iter_mut()
+            .zip(
+                detail
+                    .trailers(files)
+                    .into_iter()
+                    .zip(trailer_messages.iter()),
+            )
+            .for_each(|(note, (trailer, trailer_message))| {
+                if let Some(message) = trailer_message.as_ref().and_then(|trailer_message| {
+                    self.message(
+                        locale,
+                        &id,
+                        Some(&trailer_message.attribute),
+                        Some(&trailer_message.args),
+                    )
+                }) {
+                    *note = trailer_translated(trailer, message).to_string();
+                }
+            });
+
+        diagnostic
+    }
+
+    /// Returns the messages and attributes that each bundle does not
+    /// translate, for every error code in [`ErrorCode::all`].
+    ///
+    /// A bundle is missing an attribute when the fallback locale's bundle has
+    /// the attribute for that error code. This is intended for tests:
+    ///
+    /// ```rust,ignore
+    /// assert_eq!(Vec::<MissingMessage>::new(), localizer.missing_messages::<MyErrorCode>()?);
+    /// ```
+    ///
+    /// Returns [`I18nError::ErrorCodesNone`] when [`ErrorCode::all`] is empty,
+    /// as it is by default, since no codes would be checked. Implement it, or
+    /// use `#[derive(ErrorCode)]`, which generates it.
+    pub fn missing_messages<Ec>(&self) -> Result<Vec<MissingMessage>, I18nError>
+    where
+        Ec: ErrorCode + 'static,
+    {
+        if Ec::all().is_empty() {
+            return Err(I18nError::ErrorCodesNone {
+                error_code_type: std::any::type_name::<Ec>(),
+            });
+        }
+
+        let bundle_fallback = self.bundles_for(&self.fallback).next();
+
+        let mut missing_messages = Vec::new();
+        self.bundles.iter().for_each(|bundle| {
+            let locale = bundle.locales.first().cloned().unwrap_or_default();
+            Ec::all().iter().for_each(|code| {
+                let id = Code::string(*code);
+                let message = match bundle.get_message(&id) {
+                    Some(message) if message.value().is_some() => message,
+                    _ => {
+                        missing_messages.push(MissingMessage {
+                            locale: locale.clone(),
+                            id,
+                            attribute: None,
+                        });
+                        return;
+                    }
+                };
+
+                let message_fallback =
+                    bundle_fallback.and_then(|bundle_fallback| bundle_fallback.get_message(&id));
+                message_fallback
+                    .iter()
+                    .flat_map(|message_fallback| message_fallback.attributes())
+                    .filter(|attribute| message.get_attribute(attribute.id()).is_none())
+                    .for_each(|attribute| {
+                        missing_messages.push(MissingMessage {
+                            locale: locale.clone(),
+                            id: id.clone(),
+                            attribute: Some(String::from(attribute.id())),
+                        })
+                    });
+            });
+        });
+
+        Ok(missing_messages)
+    }
+
+    /// Returns the bundles to look up messages in for the locale, in order.
+    fn bundles_for<'l>(
+        &'l self,
+        locale: &'l LanguageIdentifier,
+    ) -> impl Iterator<Item = &'l FluentBundle<FluentResource>> + 'l {
+        std::iter::once(locale)
+            .chain(std::iter::once(&self.fallback))
+            .flat_map(move |locale| {
+                let bundles_exact = self
+                    .bundles
+                    .iter()
+                    .filter(move |bundle| bundle.locales.first() == Some(locale));
+                let bundles_language = self.bundles.iter().filter(move |bundle| {
+                    bundle
+                        .locales
+                        .first()
+                        .map(|bundle_locale| {
+                            bundle_locale != locale && bundle_locale.language == locale.language
+                        })
+                        .unwrap_or(false)
+                });
+                bundles_exact.chain(bundles_language)
+            })
+    }
+}
+
+impl Default for Localizer {
+    fn default() -> Self {
+        Self {
+            bundles: Vec::new(),
+            fallback: langid!("en"),
+        }
+    }
+}
+
+impl fmt::Debug for Localizer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let locales = self
+            .bundles
+            .iter()
+            .map(|bundle| &bundle.locales)
+            .collect::<Vec<_>>();
+        f.debug_struct("Localizer")
+            .field("locales", &locales)
+            .field("fallback", &self.fallback)
+            .finish()
+    }
+}
+
+/// Returns the trailer with its message replaced by the translation.
+fn trailer_translated(trailer: Trailer, message: String) -> Trailer {
+    match trailer {
+        Trailer::Note(_) => Trailer::Note(message),
+        Trailer::Help(_) => Trailer::Help(message),
+        Trailer::Warning(_) => Trailer::Warning(message),
+        Trailer::SeeAlso(_) => Trailer::SeeAlso(message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use codespan_reporting::diagnostic::{LabelStyle, Severity};
+    use fluent_bundle::FluentArgs;
+    use unic_langid::langid;
+
+    use super::Localizer;
+    use crate::{
+        i18n::{ErrorDetailI18n, I18nError, I18nMessage, MissingMessage},
+        test_support::{self, TestErrorCode, TestErrorDetail, UnlistedErrorCode},
+    };
+
+    const EN: &str = "\
+E1 = `chosen` value is invalid.
+    .invalid = invalid value specified
+    .defined = defined here
+    .note-available = `chosen` value must come from one of `available` values
+";
+
+    const FR: &str = "\
+E1 = La valeur de `chosen` est invalide.
+    .invalid = la valeur `{ $value }` n'est pas disponible
+    .note-available = la valeur de `chosen` doit faire partie de `available`
+";
+
+    impl<'files> ErrorDetailI18n<'files> for TestErrorDetail {
+        fn label_messages(&self) -> Vec<Option<I18nMessage<'_>>> {
+            vec![
+                Some(I18nMessage::new("invalid").with_arg("value", "ghi")),
+                Some(I18nMessage::new("defined")),
+            ]
+        }
+
+        fn trailer_messages(&self, _files: &Self::Files) -> Vec<Option<I18nMessage<'_>>> {
+            vec![Some(I18nMessage::new("note-available"))]
+        }
+    }
+
+    fn localizer() -> Result<Localizer, I18nError> {
+        Localizer::new()
+            .with_ftl(langid!("en"), EN)?
+            .with_ftl(langid!("fr"), FR)
+    }
+
+    #[test]
+    fn as_diagnostic_translates_messages_for_locale() -> Result<(), I18nError> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_invalid_value(file_id, Severity::Error);
+
+        let diagnostic = localizer()?.as_diagnostic(&langid!("fr-FR"), &files, &source_error);
+
+        assert_eq!("La valeur de `chosen` est invalide.", diagnostic.message);
+        assert_eq!(
+            vec!["la valeur `ghi` n'est pas disponible", "defined here"],
+            diagnostic
+                .labels
+                .iter()
+                .map(|label| label.message.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![String::from(
+                "la valeur de `chosen` doit faire partie de `available`"
+            )],
+            diagnostic.notes
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn as_diagnostic_keeps_severity_and_secondary_label_styles() -> Result<(), I18nError> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_secondary_only(file_id, Severity::Warning);
+
+        let diagnostic = localizer()?.as_diagnostic(&langid!("fr"), &files, &source_error);
+
+        assert_eq!(Severity::Warning, diagnostic.severity);
+        assert_eq!(
+            vec![
+                (
+                    LabelStyle::Secondary,
+                    "la valeur `ghi` n'est pas disponible"
+                ),
+                (LabelStyle::Secondary, "defined here"),
+            ],
+            diagnostic
+                .labels
+                .iter()
+                .map(|label| (label.style, label.message.as_str()))
+                .collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn as_diagnostic_translates_notes_of_unlabelled_error() -> Result<(), I18nError> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_unlabelled(file_id, Severity::Note);
+
+        let diagnostic = localizer()?.as_diagnostic(&langid!("fr"), &files, &source_error);
+
+        assert_eq!(Severity::Note, diagnostic.severity);
+        assert!(diagnostic.labels.is_empty());
+        assert_eq!(
+            vec![String::from(
+                "la valeur de `chosen` doit faire partie de `available`"
+            )],
+            diagnostic.notes
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn as_diagnostic_falls_back_to_english_text_without_bundles() {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_invalid_value(file_id, Severity::Error);
+
+        let diagnostic = Localizer::new().as_diagnostic(&langid!("fr"), &files, &source_error);
+
+        assert_eq!(source_error.as_diagnostic(&files), diagnostic);
+    }
+
+    #[test]
+    fn message_falls_back_to_fallback_locale() -> Result<(), I18nError> {
+        let localizer = localizer()?;
+
+        assert_eq!(
+            Some(String::from("defined here")),
+            localizer.message(&langid!("fr"), "E1", Some("defined"), None)
+        );
+        assert_eq!(
+            Some(String::from("`chosen` value is invalid.")),
+            localizer.message(&langid!("de"), "E1", None, None)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn message_skips_bundle_when_argument_is_missing() -> Result<(), I18nError> {
+        let localizer = localizer()?;
+        let args = FluentArgs::new();
+
+        assert_eq!(
+            Some(String::from("invalid value specified")),
+            localizer.message(&langid!("fr"), "E1", Some("invalid"), Some(&args))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn missing_messages_lists_untranslated_attributes() -> Result<(), I18nError> {
+        let localizer = localizer()?.with_ftl(langid!("de"), "E2 = Unbekannt.\n")?;
+
+        assert_eq!(
+            vec![
+                MissingMessage {
+                    locale: langid!("fr"),
+                    id: String::from("E1"),
+                    attribute: Some(String::from("defined")),
+                },
+                MissingMessage {
+                    locale: langid!("de"),
+                    id: String::from("E1"),
+                    attribute: None,
+                },
+            ],
+            localizer.missing_messages::<TestErrorCode>()?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn missing_messages_returns_error_when_error_codes_are_not_listed() -> Result<(), I18nError> {
+        let localizer = localizer()?;
+
+        assert!(matches!(
+            localizer.missing_messages::<UnlistedErrorCode>(),
+            Err(I18nError::ErrorCodesNone { error_code_type })
+                if error_code_type.ends_with("UnlistedErrorCode")
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn with_ftl_returns_error_for_invalid_syntax() {
+        let result = Localizer::new().with_ftl(langid!("en"), "E1 = {");
+
+        assert!(matches!(
+            result,
+            Err(I18nError::FtlInvalid { locale, errors }) if locale == langid!("en") && !errors.is_empty()
+        ));
+    }
+}
@@ -0,0 +1,27 @@
+use std::fmt;
+
+use unic_langid::LanguageIdentifier;
+
+/// A message or attribute that a locale's bundle does not translate.
+///
+/// Returned by [`Localizer::missing_messages`].
+///
+/// [`Localizer::missing_messages`]: crate::i18n::Localizer::missing_messages
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MissingMessage {
+    /// Locale of the bundle.
+    pub locale: LanguageIdentifier,
+    /// ID of the message, e.g. `"E002"`.
+    pub id: String,
+    /// Name of the attribute, or `None` when the message itself is missing.
+    pub attribute: Option<String>,
+}
+
+impl fmt::Display for MissingMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.attribute {
+            Some(attribute) => write!(f, "{}: `{}.{}`", self.locale, self.id, attribute),
+            None => write!(f, "{}: `{}`", self.locale, self.id),
+        }
+    }
+}
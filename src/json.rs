@@ -0,0 +1,63 @@
+//! JSON representation of source errors.
+//!
+//! This is enabled by the `"json"` feature, and is intended for tools that
+//! consume errors machine-readably, such as CI pipelines.
+//!
+//! # Schema
+//!
+//! [`SourceError::to_json`] and [`SourceErrors::to_json`] both produce a
+//! [`JsonReport`]. The `version` field is incremented whenever the schema
+//! changes in a backwards incompatible way.
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "diagnostics": [
+//!     {
+//!       "code": "E1",
+//!       "code_number": 1,
+//!       "prefix": "E",
+//!       "severity": "error",
+//!       "message": "`chosen` value is invalid.",
+//!       "labels": [
+//!         {
+//!           "style": "primary",
+//!           "file": "examples/source_ref_hint.yaml",
+//!           "range": { "start": 44, "end": 49 },
+//!           "start": { "line": 6, "column": 9 },
+//!           "end": { "line": 6, "column": 14 },
+//!           "message": "invalid value specified"
+//!         }
+//!       ],
+//!       "notes": ["`chosen` value must come from one of `available` values"],
+//!       "help": ["use one of `\"abc\"`, `\"def\"`"]
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! * `severity`: One of `"bug"`, `"error"`, `"warning"`, `"note"`, `"help"`.
+//! * `range`: Byte offsets within the file; `end` is exclusive.
+//! * `start`, `end`: 1-based line and column, where columns count Unicode
+//!   scalar values; `end` is exclusive.
+//! * `help`, `warnings`, `see_also`: Trailers other than notes, omitted when
+//!   empty.
+//! * `suggestions`: Replacements that fix the error, each with a `file`,
+//!   `range`, `start`, `end`, `replacement`, `message`, and an `applicability`
+//!   of `"machine_applicable"`, `"maybe_incorrect"`, or `"has_placeholders"`.
+//!   Omitted when empty.
+//!
+//! [`SourceError::to_json`]: crate::SourceError::to_json
+//! [`SourceErrors::to_json`]: crate::SourceErrors::to_json
+
+pub use self::{
+    json_diagnostic::JsonDiagnostic, json_error::JsonError, json_label::JsonLabel,
+    json_report::JsonReport, json_severity::JsonSeverity, json_suggestion::JsonSuggestion,
+};
+
+mod json_diagnostic;
+mod json_error;
+mod json_label;
+mod json_report;
+mod json_severity;
+mod json_suggestion;
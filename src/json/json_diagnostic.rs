@@ -0,0 +1,317 @@
+use codespan_reporting::files::{self, Files};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    fmt::Code,
+    json::{JsonLabel, JsonSeverity, JsonSuggestion},
+    model::{ErrorCode, ErrorDetail, SourceError, Trailer},
+};
+
+/// JSON representation of a [`SourceError`].
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct JsonDiagnostic {
+    /// The formatted error code, e.g. `"E001"`.
+    pub code: String,
+    /// The numeric error code, e.g. `1`.
+    pub code_number: usize,
+    /// Prefix of the error code, e.g. `"E"`.
+    pub prefix: String,
+    /// Severity of the error.
+    pub severity: JsonSeverity,
+    /// Short description of the error code.
+    pub message: String,
+    /// Highlighted regions of the files.
+    pub labels: Vec<JsonLabel>,
+    /// Notes displayed beneath the snippets.
+    pub notes: Vec<String>,
+    /// Advice on how to fix the error.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub help: Vec<String>,
+    /// Context about related hazards.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// Links to further documentation about the error.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub see_also: Vec<String>,
+    /// Replacements that fix the error.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suggestions: Vec<JsonSuggestion>,
+}
+
+impl JsonDiagnostic {
+    /// Returns a `JsonDiagnostic` for the source error.
+    ///
+    /// # Parameters
+    ///
+    /// * `files`: Files that the error refers to.
+    /// * `source_error`: The error to convert.
+    pub fn new<'files, Ec, Ed, Fs>(
+        files: &'files Fs,
+        source_error: &SourceError<'files, Ec, Ed, Fs>,
+    ) -> Result<Self, files::Error>
+    where
+        Ec: ErrorCode,
+        Ed: ErrorDetail<'files, Files = Fs>,
+        Fs: Files<'files>,
+    {
+        let SourceError {
+            code,
+            detail,
+            severity,
+            marker: _,
+        } = source_error;
+        let labels = detail
+            .labels()
+            .iter()
+            .map(|label| JsonLabel::new(files, label))
+            .collect::<Result<Vec<_>, files::Error>>()?;
+        let suggestions = detail
+            .suggestions()
+            .iter()
+            .map(|suggestion| JsonSuggestion::new(files, suggestion))
+            .collect::<Result<Vec<_>, files::Error>>()?;
+
+        let mut notes = Vec::new();
+        let mut help = Vec::new();
+        let mut warnings = Vec::new();
+        let mut see_also = Vec::new();
+        detail
+            .trailers(files)
+            .into_iter()
+            .for_each(|trailer| match trailer {
+                Trailer::Note(note) => notes.push(note),
+                Trailer::Help(message) => help.push(message),
+                Trailer::Warning(warning) => warnings.push(warning),
+                Trailer::SeeAlso(link) => see_also.push(link),
+            });
+
+        Ok(Self {
+            code: Code::string(*code),
+            code_number: code.code(),
+            prefix: String::from(Ec::PREFIX),
+            severity: JsonSeverity::from(*severity),
+            message: String::from(code.description()),
+            labels,
+            notes,
+            help,
+            warnings,
+            see_also,
+            suggestions,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use codespan_reporting::diagnostic::Severity;
+
+    use super::JsonDiagnostic;
+    use crate::{
+        json::{JsonLabel, JsonSeverity, JsonSuggestion},
+        model::{Applicability, LabelStyle, LineColumn, Span, Suggestion, Trailer},
+        test_support,
+    };
+
+    #[test]
+    fn new_resolves_label_locations() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_invalid_value(file_id, Severity::Warning);
+
+        let json_diagnostic = JsonDiagnostic::new(&files, &source_error)?;
+
+        assert_eq!(
+            JsonDiagnostic {
+                code: String::from("E1"),
+                code_number: 1,
+                prefix: String::from("E"),
+                severity: JsonSeverity::Warning,
+                message: String::from("`chosen` value is invalid."),
+                labels: vec![
+                    JsonLabel {
+                        style: LabelStyle::Primary,
+                        file: String::from("examples/source_ref_hint.yaml"),
+                        range: 44..49,
+                        start: LineColumn { line: 6, column: 9 },
+                        end: LineColumn {
+                            line: 6,
+                            column: 14
+                        },
+                        message: String::from("invalid value specified"),
+                    },
+                    JsonLabel {
+                        style: LabelStyle::Secondary,
+                        file: String::from("examples/source_ref_hint.yaml"),
+                        range: 4..34,
+                        start: LineColumn { line: 2, column: 1 },
+                        end: LineColumn {
+                            line: 4,
+                            column: 10
+                        },
+                        message: String::from("defined here"),
+                    },
+                ],
+                notes: vec![String::from(
+                    "`chosen` value must come from one of `available` values"
+                )],
+                help: Vec::new(),
+                warnings: Vec::new(),
+                see_also: Vec::new(),
+                suggestions: Vec::new(),
+            },
+            json_diagnostic
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn new_maps_severity() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+
+        let severities = [
+            Severity::Bug,
+            Severity::Error,
+            Severity::Warning,
+            Severity::Note,
+            Severity::Help,
+        ]
+        .iter()
+        .map(|severity| {
+            let source_error = test_support::source_error_invalid_value(file_id, *severity);
+            JsonDiagnostic::new(&files, &source_error)
+                .map(|json_diagnostic| json_diagnostic.severity)
+        })
+        .collect::<Result<Vec<JsonSeverity>, _>>()?;
+
+        assert_eq!(
+            vec![
+                JsonSeverity::Bug,
+                JsonSeverity::Error,
+                JsonSeverity::Warning,
+                JsonSeverity::Note,
+                JsonSeverity::Help,
+            ],
+            severities
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn new_keeps_secondary_labels_and_notes_without_primary_labels(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error_secondary_only =
+            test_support::source_error_secondary_only(file_id, Severity::Error);
+        let source_error_unlabelled =
+            test_support::source_error_unlabelled(file_id, Severity::Error);
+
+        let json_diagnostic_secondary_only =
+            JsonDiagnostic::new(&files, &source_error_secondary_only)?;
+        let json_diagnostic_unlabelled = JsonDiagnostic::new(&files, &source_error_unlabelled)?;
+
+        assert_eq!(
+            vec![
+                (LabelStyle::Secondary, 44..49),
+                (LabelStyle::Secondary, 4..34)
+            ],
+            json_diagnostic_secondary_only
+                .labels
+                .iter()
+                .map(|json_label| (json_label.style, json_label.range.clone()))
+                .collect::<Vec<_>>()
+        );
+        assert!(json_diagnostic_unlabelled.labels.is_empty());
+        assert_eq!(
+            vec![String::from(
+                "`chosen` value must come from one of `available` values"
+            )],
+            json_diagnostic_unlabelled.notes
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn new_clamps_location_of_label_past_end_of_file() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_range_invalid(file_id, Severity::Error);
+
+        let json_diagnostic = JsonDiagnostic::new(&files, &source_error)?;
+
+        assert_eq!(
+            vec![JsonLabel {
+                style: LabelStyle::Primary,
+                file: String::from("examples/source_ref_hint.yaml"),
+                range: 395..400,
+                start: LineColumn { line: 7, column: 1 },
+                end: LineColumn { line: 7, column: 1 },
+                message: String::from("invalid value specified"),
+            }],
+            json_diagnostic.labels
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn new_separates_trailers_by_kind() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let mut source_error = test_support::source_error_invalid_value(file_id, Severity::Error);
+        source_error.detail.trailers.extend([
+            Trailer::Help(String::from("use `abc`")),
+            Trailer::SeeAlso(String::from("https://example.com/E1")),
+            Trailer::Warning(String::from("`abc` is deprecated")),
+        ]);
+
+        let json_diagnostic = JsonDiagnostic::new(&files, &source_error)?;
+
+        assert_eq!(
+            vec![String::from(
+                "`chosen` value must come from one of `available` values"
+            )],
+            json_diagnostic.notes
+        );
+        assert_eq!(vec![String::from("use `abc`")], json_diagnostic.help);
+        assert_eq!(
+            vec![String::from("`abc` is deprecated")],
+            json_diagnostic.warnings
+        );
+        assert_eq!(
+            vec![String::from("https://example.com/E1")],
+            json_diagnostic.see_also
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn new_resolves_suggestion_locations() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let mut source_error = test_support::source_error_invalid_value(file_id, Severity::Error);
+        source_error.detail.suggestions.push(
+            Suggestion::new(
+                Span::new(file_id, 44..49),
+                "\"abc\"",
+                Applicability::MachineApplicable,
+            )
+            .with_message("use `abc`"),
+        );
+
+        let json_diagnostic = JsonDiagnostic::new(&files, &source_error)?;
+
+        assert_eq!(
+            vec![JsonSuggestion {
+                file: String::from("examples/source_ref_hint.yaml"),
+                range: 44..49,
+                start: LineColumn { line: 6, column: 9 },
+                end: LineColumn {
+                    line: 6,
+                    column: 14
+                },
+                replacement: String::from("\"abc\""),
+                applicability: Applicability::MachineApplicable,
+                message: String::from("use `abc`"),
+            }],
+            json_diagnostic.suggestions
+        );
+        assert!(serde_json::to_string(&json_diagnostic)?
+            .contains(r#""applicability":"machine_applicable""#));
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+use std::fmt;
+
+use codespan_reporting::files;
+
+/// Error when serializing source errors to JSON.
+#[derive(Debug)]
+pub enum JsonError {
+    /// Failed to resolve a label's file or location.
+    Files(files::Error),
+    /// Failed to serialize the report.
+    Serde(serde_json::Error),
+}
+
+impl fmt::Display for JsonError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Files(_) => write!(f, "Failed to resolve source error location."),
+            Self::Serde(_) => write!(f, "Failed to serialize source errors to JSON."),
+        }
+    }
+}
+
+impl std::error::Error for JsonError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Files(error) => Some(error),
+            Self::Serde(error) => Some(error),
+        }
+    }
+}
+
+impl From<files::Error> for JsonError {
+    fn from(error: files::Error) -> Self {
+        Self::Files(error)
+    }
+}
+
+impl From<serde_json::Error> for JsonError {
+    fn from(error: serde_json::Error) -> Self {
+        Self::Serde(error)
+    }
+}
@@ -0,0 +1,54 @@
+use std::ops::Range;
+
+use codespan_reporting::files::{self, Files};
+use serde::{Deserialize, Serialize};
+
+use crate::model::{Label, LabelStyle, LineColumn, Span};
+
+/// A highlighted region of a file, with its location resolved.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct JsonLabel {
+    /// Whether this is a primary or secondary label.
+    pub style: LabelStyle,
+    /// Name of the file.
+    pub file: String,
+    /// Byte offsets within the file; `end` is exclusive.
+    pub range: Range<usize>,
+    /// 1-based line and column of the start of the label.
+    pub start: LineColumn,
+    /// 1-based line and column of the end of the label, exclusive.
+    pub end: LineColumn,
+    /// Message of the label.
+    pub message: String,
+}
+
+impl JsonLabel {
+    /// Returns a `JsonLabel` with locations resolved from the files.
+    ///
+    /// # Parameters
+    ///
+    /// * `files`: Files that the label refers to.
+    /// * `label`: The label to convert.
+    pub fn new<'files, Fs>(
+        files: &'files Fs,
+        label: &Label<Fs::FileId>,
+    ) -> Result<Self, files::Error>
+    where
+        Fs: Files<'files>,
+    {
+        let Label {
+            style,
+            span: Span { file_id, range },
+            message,
+        } = label;
+
+        Ok(Self {
+            style: *style,
+            file: files.name(*file_id)?.to_string(),
+            range: range.clone(),
+            start: LineColumn::new(files, *file_id, range.start)?,
+            end: LineColumn::new(files, *file_id, range.end)?,
+            message: message.clone(),
+        })
+    }
+}
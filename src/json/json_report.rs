@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+
+use crate::json::JsonDiagnostic;
+
+/// Top level JSON document, holding the schema version and diagnostics.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct JsonReport {
+    /// Version of the schema, see [`JsonReport::VERSION`].
+    pub version: u32,
+    /// The diagnostics in this report.
+    pub diagnostics: Vec<JsonDiagnostic>,
+}
+
+impl JsonReport {
+    /// Version of the schema produced by this crate.
+    pub const VERSION: u32 = 1;
+
+    /// Returns a `JsonReport` with the current schema version.
+    pub fn new(diagnostics: Vec<JsonDiagnostic>) -> Self {
+        Self {
+            version: Self::VERSION,
+            diagnostics,
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use codespan_reporting::diagnostic::Severity;
+use serde::{Deserialize, Serialize};
+
+/// Severity of a diagnostic, serialized in lower case.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JsonSeverity {
+    /// An unexpected bug.
+    Bug,
+    /// An error.
+    Error,
+    /// A warning.
+    Warning,
+    /// A note.
+    Note,
+    /// A help message.
+    Help,
+}
+
+impl From<Severity> for JsonSeverity {
+    fn from(severity: Severity) -> Self {
+        match severity {
+            Severity::Bug => Self::Bug,
+            Severity::Error => Self::Error,
+            Severity::Warning => Self::Warning,
+            Severity::Note => Self::Note,
+            Severity::Help => Self::Help,
+        }
+    }
+}
+
+impl From<JsonSeverity> for Severity {
+    fn from(json_severity: JsonSeverity) -> Self {
+        match json_severity {
+            JsonSeverity::Bug => Self::Bug,
+            JsonSeverity::Error => Self::Error,
+            JsonSeverity::Warning => Self::Warning,
+            JsonSeverity::Note => Self::Note,
+            JsonSeverity::Help => Self::Help,
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use std::ops::Range;
+
+use codespan_reporting::files::{self, Files};
+use serde::{Deserialize, Serialize};
+
+use crate::model::{Applicability, LineColumn, Span, Suggestion};
+
+/// Replacement text for a region of a file, with its location resolved.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct JsonSuggestion {
+    /// Name of the file.
+    pub file: String,
+    /// Byte offsets of the text to replace; `end` is exclusive.
+    pub range: Range<usize>,
+    /// 1-based line and column of the start of the text to replace.
+    pub start: LineColumn,
+    /// 1-based line and column of the end of the text to replace, exclusive.
+    pub end: LineColumn,
+    /// Text to replace the range with.
+    pub replacement: String,
+    /// How confident the suggestion is that the replacement fixes the error.
+    pub applicability: Applicability,
+    /// Message of the suggestion.
+    pub message: String,
+}
+
+impl JsonSuggestion {
+    /// Returns a `JsonSuggestion` with locations resolved from the files.
+    ///
+    /// # Parameters
+    ///
+    /// * `files`: Files that the suggestion refers to.
+    /// * `suggestion`: The suggestion to convert.
+    pub fn new<'files, Fs>(
+        files: &'files Fs,
+        suggestion: &Suggestion<Fs::FileId>,
+    ) -> Result<Self, files::Error>
+    where
+        Fs: Files<'files>,
+    {
+        let Suggestion {
+            span: Span { file_id, range },
+            replacement,
+            applicability,
+            message,
+        } = suggestion;
+
+        Ok(Self {
+            file: files.name(*file_id)?.to_string(),
+            range: range.clone(),
+            start: LineColumn::new(files, *file_id, range.start)?,
+            end: LineColumn::new(files, *file_id, range.end)?,
+            replacement: replacement.clone(),
+            applicability: *applicability,
+            message: message.clone(),
+        })
+    }
+}
@@ -12,13 +12,70 @@
 //!
 //! [`Severity`]: codespan_reporting::diagnostic::Severity
 //!
+//! Multiple errors can be accumulated in [`SourceErrors`] and emitted together.
+//! [`model::SeverityPolicy`] overrides their severities by error code, like
+//! `#[allow]` and `#[deny]` for lints, from configuration or command line flags.
+//!
+//! [`ErrorDetail::suggestions`] returns replacements that fix an error, and the
+//! [`fix`] module applies the machine applicable ones to the files, or renders
+//! them as a unified diff.
+//!
+//! The [`formatter`] module renders errors to any [`std::io::Write`], as plain
+//! text, or with the `"ansi_color"` feature, as ANSI coloured text. The
+//! `"ariadne"` and `"annotate_snippets"` features render errors in the style of
+//! those crates instead.
+//! [`formatter::ShortFormatter`] writes one `gcc` style line per error for
+//! grep-able logs and editor quickfix lists.
+//!
+//! The `"json"` feature adds `SourceError::to_json` and
+//! `SourceErrors::to_json`, which serialize errors using the schema documented
+//! in the `json` module.
+//!
+//! [`formatter::GithubActionsFormatter`] writes errors as GitHub Actions
+//! workflow commands, and with the `"json"` feature, the `gitlab` module
+//! exports them as a GitLab Code Quality report, so that errors are annotated
+//! on pull and merge requests.
+//!
+//! The `"sarif"` feature adds a SARIF 2.1.0 exporter for code scanning
+//! integrations.
+//!
+//! The `"lsp"` feature converts errors into Language Server Protocol
+//! diagnostics.
+//!
+//! The `"explain"` feature adds the `explain` module, which renders the
+//! Markdown [`ErrorCode::explanation`] of an error code to the terminal, so
+//! applications can offer `myapp explain E002` like `rustc --explain`.
+//!
+//! The `"error_index"` feature adds the `error_index` module, which generates a
+//! Markdown or HTML reference page of every error code, and can check that a
+//! checked-in copy is up to date.
+//!
+//! The `"miette"` feature adds `SourceError::to_miette`, which returns an
+//! adapter implementing `miette::Diagnostic`, so errors can be reported through
+//! a `miette` based `main`.
+//!
+//! The `locate` module finds the byte span of a value from its logical path,
+//! such as `server.port`, so labels need not hard-code byte ranges. The
+//! `"yaml"` feature adds `locate::yaml::YamlLocator` for YAML sources.
+//!
+//! The `"i18n"` feature adds the `i18n` module, which translates error
+//! descriptions, label messages, and notes with Fluent bundles, in a locale
+//! chosen at render time.
+//!
+//! The `"derive"` feature provides `#[derive(ErrorCode)]` and
+//! `#[derive(ErrorDetail)]` to generate the [`ErrorCode`] and [`ErrorDetail`]
+//! implementations from attributes.
+//!
 //! # Examples
 //!
 //! Sample usage can be seen in the repository [examples].
 //!
 //! [examples]: https://github.com/azriel91/srcerr/tree/main/examples
 
-pub use crate::model::{ErrorCode, ErrorDetail, SourceError};
+pub use crate::model::{ErrorCode, ErrorDetail, Label, SourceError, SourceErrors};
+
+#[cfg(feature = "derive")]
+pub use srcerr_derive::{ErrorCode, ErrorDetail};
 
 // Re-export `codespan` so consumers don't have to depend on the crate directly.
 #[cfg(feature = "codespan")]
@@ -28,5 +85,43 @@ pub use codespan;
 // directly.
 pub use codespan_reporting;
 
+// Re-export `fluent_bundle` and `unic_langid` so consumers use the same
+// versions as this crate.
+#[cfg(feature = "i18n")]
+pub use fluent_bundle;
+#[cfg(feature = "i18n")]
+pub use unic_langid;
+
+// Re-export `lsp_types` so consumers use the same version as this crate.
+#[cfg(feature = "lsp")]
+pub use lsp_types;
+
+// Re-export `miette` so consumers use the same version as this crate.
+#[cfg(feature = "miette")]
+pub use miette;
+
+pub mod fix;
 pub mod fmt;
+pub mod formatter;
+pub mod locate;
 pub mod model;
+
+#[cfg(feature = "error_index")]
+pub mod error_index;
+#[cfg(feature = "explain")]
+pub mod explain;
+#[cfg(feature = "json")]
+pub mod gitlab;
+#[cfg(feature = "i18n")]
+pub mod i18n;
+#[cfg(feature = "json")]
+pub mod json;
+#[cfg(feature = "lsp")]
+pub mod lsp;
+#[cfg(feature = "miette")]
+pub mod miette_interop;
+#[cfg(feature = "sarif")]
+pub mod sarif;
+
+#[cfg(test)]
+mod test_support;
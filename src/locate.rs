@@ -0,0 +1,29 @@
+//! Locates the byte span of a value in source data from its logical path.
+//!
+//! Validation errors usually know the path of the invalid value, e.g.
+//! `server.port` or `available[1]`, but labels need byte ranges. A locator
+//! parses the source once, and returns the span of a path's key, value, or
+//! whole entry:
+//!
+//! ```rust,ignore
+//! let locator = YamlLocator::new(source)?;
+//! let label = Label::primary(file_id, locator.value("server.port")?);
+//! ```
+//!
+//! [`ValuePath`] parses paths in the form produced by `serde_path_to_error`,
+//! where keys are separated by `.`, and sequence indices are in `[]`.
+//!
+//! The `"yaml"` feature enables the [`yaml`] locator.
+
+pub use self::{
+    path_parse_error::PathParseError, path_segment::PathSegment, span_kind::SpanKind,
+    value_path::ValuePath,
+};
+
+#[cfg(feature = "yaml")]
+pub mod yaml;
+
+mod path_parse_error;
+mod path_segment;
+mod span_kind;
+mod value_path;
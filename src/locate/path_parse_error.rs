@@ -0,0 +1,51 @@
+use std::fmt;
+
+/// Error when parsing a [`ValuePath`] string.
+///
+/// [`ValuePath`]: crate::locate::ValuePath
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PathParseError {
+    /// A key is empty, e.g. in `server..port` or `server.`.
+    KeyEmpty {
+        /// The path that was parsed.
+        path: String,
+        /// Byte index of the empty key.
+        position: usize,
+    },
+    /// A sequence index is not a number, or is missing its closing `]`.
+    IndexInvalid {
+        /// The path that was parsed.
+        path: String,
+        /// Byte index of the index's opening `[`.
+        position: usize,
+    },
+    /// A key follows a sequence index without a `.`, e.g. in `available[1]b`.
+    SeparatorMissing {
+        /// The path that was parsed.
+        path: String,
+        /// Byte index of the key.
+        position: usize,
+    },
+}
+
+impl fmt::Display for PathParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::KeyEmpty { path, position } => {
+                write!(f, "Path `{}` has an empty key at byte {}.", path, position)
+            }
+            Self::IndexInvalid { path, position } => write!(
+                f,
+                "Path `{}` has an invalid sequence index at byte {}.",
+                path, position
+            ),
+            Self::SeparatorMissing { path, position } => write!(
+                f,
+                "Path `{}` is missing a `.` before the key at byte {}.",
+                path, position
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PathParseError {}
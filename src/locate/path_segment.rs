@@ -0,0 +1,21 @@
+use std::fmt;
+
+/// A step in a [`ValuePath`], into a mapping or a sequence.
+///
+/// [`ValuePath`]: crate::locate::ValuePath
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PathSegment {
+    /// Key of a mapping entry, e.g. `port` in `server.port`.
+    Key(String),
+    /// Index of a sequence item, e.g. `1` in `available[1]`.
+    Index(usize),
+}
+
+impl fmt::Display for PathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Key(key) => f.write_str(key),
+            Self::Index(index) => write!(f, "[{}]", index),
+        }
+    }
+}
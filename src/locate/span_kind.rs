@@ -0,0 +1,23 @@
+/// Which part of an entry to return the span of.
+///
+/// For `port: 8080` at the path `server.port`:
+///
+/// ```yaml
+/// server:
+///   port: 8080
+/// #  ^^^^        Key
+/// #        ^^^^  Value
+/// #  ^^^^^^^^^^  Entry
+/// ```
+///
+/// Sequence items have no key, and their entry includes the `-` indicator of
+/// block sequences.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpanKind {
+    /// The key of a mapping entry.
+    Key,
+    /// The value, including quotes and nested collections.
+    Value,
+    /// The key and value of a mapping entry, or the whole sequence item.
+    Entry,
+}
@@ -0,0 +1,188 @@
+use std::{fmt, str::FromStr};
+
+use crate::locate::{PathParseError, PathSegment};
+
+/// Logical path to a value in source data, e.g. `server.port` or
+/// `available[1]`.
+///
+/// Keys are separated by `.`, and sequence indices are written in `[]`. The
+/// empty path refers to the root value.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ValuePath {
+    /// Segments from the root to the value.
+    segments: Vec<PathSegment>,
+}
+
+impl ValuePath {
+    /// Returns a new `ValuePath` from its segments.
+    pub fn new(segments: Vec<PathSegment>) -> Self {
+        Self { segments }
+    }
+
+    /// Parses a path string such as `servers[0].port`.
+    ///
+    /// # Parameters
+    ///
+    /// * `path`: The path to parse.
+    pub fn parse(path: &str) -> Result<Self, PathParseError> {
+        let mut segments = Vec::new();
+        let mut position = 0;
+        let mut key_required = false;
+        while position < path.len() || key_required {
+            let rest = &path[position..];
+            if rest.starts_with('[') && !key_required {
+                let (index, index_end) = rest
+                    .find(']')
+                    .and_then(|index_end| {
+                        rest[1..index_end]
+                            .parse::<usize>()
+                            .ok()
+                            .map(|index| (index, index_end))
+                    })
+                    .ok_or_else(|| PathParseError::IndexInvalid {
+                        path: String::from(path),
+                        position,
+                    })?;
+                segments.push(PathSegment::Index(index));
+                position += index_end + 1;
+
+                if !path[position..].is_empty() && !path[position..].starts_with(['.', '[']) {
+                    return Err(PathParseError::SeparatorMissing {
+                        path: String::from(path),
+                        position,
+                    });
+                }
+            } else {
+                let key_end = rest.find(['.', '[']).unwrap_or(rest.len());
+                if key_end == 0 {
+                    return Err(PathParseError::KeyEmpty {
+                        path: String::from(path),
+                        position,
+                    });
+                }
+                segments.push(PathSegment::Key(String::from(&rest[..key_end])));
+                position += key_end;
+                key_required = false;
+            }
+
+            if path[position..].starts_with('.') {
+                position += 1;
+                key_required = true;
+            }
+        }
+
+        Ok(Self { segments })
+    }
+
+    /// Returns the segments from the root to the value.
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.segments
+    }
+}
+
+impl FromStr for ValuePath {
+    type Err = PathParseError;
+
+    fn from_str(path: &str) -> Result<Self, PathParseError> {
+        Self::parse(path)
+    }
+}
+
+impl From<Vec<PathSegment>> for ValuePath {
+    fn from(segments: Vec<PathSegment>) -> Self {
+        Self::new(segments)
+    }
+}
+
+impl fmt::Display for ValuePath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.segments
+            .iter()
+            .enumerate()
+            .try_for_each(|(index, segment)| match segment {
+                PathSegment::Key(_) if index > 0 => write!(f, ".{}", segment),
+                _ => write!(f, "{}", segment),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ValuePath;
+    use crate::locate::{PathParseError, PathSegment};
+
+    #[test]
+    fn parse_splits_keys_and_indices() -> Result<(), PathParseError> {
+        let value_path = ValuePath::parse("servers[0].ports[12].name")?;
+
+        assert_eq!(
+            &[
+                PathSegment::Key(String::from("servers")),
+                PathSegment::Index(0),
+                PathSegment::Key(String::from("ports")),
+                PathSegment::Index(12),
+                PathSegment::Key(String::from("name")),
+            ],
+            value_path.segments()
+        );
+        assert_eq!("servers[0].ports[12].name", value_path.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn parse_returns_root_for_empty_path() -> Result<(), PathParseError> {
+        assert_eq!(ValuePath::default(), ValuePath::parse("")?);
+        assert_eq!(
+            &[PathSegment::Index(1)],
+            ValuePath::parse("[1]")?.segments()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn parse_rejects_empty_key() {
+        assert_eq!(
+            Err(PathParseError::KeyEmpty {
+                path: String::from("server..port"),
+                position: 7,
+            }),
+            ValuePath::parse("server..port")
+        );
+        assert_eq!(
+            Err(PathParseError::KeyEmpty {
+                path: String::from("server."),
+                position: 7,
+            }),
+            ValuePath::parse("server.")
+        );
+    }
+
+    #[test]
+    fn parse_rejects_key_without_separator_after_index() {
+        assert_eq!(
+            Err(PathParseError::SeparatorMissing {
+                path: String::from("a[0]b"),
+                position: 4,
+            }),
+            ValuePath::parse("a[0]b")
+        );
+    }
+
+    #[test]
+    fn parse_rejects_invalid_index() {
+        assert_eq!(
+            Err(PathParseError::IndexInvalid {
+                path: String::from("available[one]"),
+                position: 9,
+            }),
+            ValuePath::parse("available[one]")
+        );
+        assert_eq!(
+            Err(PathParseError::IndexInvalid {
+                path: String::from("available[1"),
+                position: 9,
+            }),
+            ValuePath::parse("available[1")
+        );
+    }
+}
@@ -0,0 +1,23 @@
+//! Locates values in YAML source by their path.
+//!
+//! This is enabled by the `"yaml"` feature. [`YamlLocator`] parses the YAML
+//! once, and returns the byte spans of keys, values, and entries, so labels can
+//! be built from the path of an invalid value:
+//!
+//! ```rust
+//! use srcerr::locate::yaml::YamlLocator;
+//!
+//! let source = "available:\n  - \"abc\"\n  - \"def\"\nchosen: \"ghi\"\n";
+//! let locator = YamlLocator::new(source)?;
+//!
+//! assert_eq!(39..44, locator.value("chosen")?);
+//! assert_eq!(23..30, locator.entry("available[1]")?);
+//! # Ok::<(), srcerr::locate::yaml::YamlLocateError>(())
+//! ```
+//!
+//! Only the first document of a multi-document stream is located.
+
+pub use self::{yaml_locate_error::YamlLocateError, yaml_locator::YamlLocator};
+
+mod yaml_locate_error;
+mod yaml_locator;
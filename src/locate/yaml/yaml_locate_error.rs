@@ -0,0 +1,66 @@
+use std::fmt;
+
+use saphyr_parser::ScanError;
+
+use crate::locate::PathParseError;
+
+/// Error when locating a value in YAML source.
+#[derive(Debug)]
+pub enum YamlLocateError {
+    /// The source is not valid YAML.
+    YamlInvalid {
+        /// The underlying error.
+        error: ScanError,
+    },
+    /// The path string is not a valid path.
+    PathInvalid {
+        /// The underlying error.
+        error: PathParseError,
+    },
+    /// No value exists at the path.
+    PathNotFound {
+        /// The path that was looked up.
+        path: String,
+    },
+    /// The key of a value was requested, but the value is a sequence item or
+    /// the root, which have no key.
+    KeyNone {
+        /// The path that was looked up.
+        path: String,
+    },
+}
+
+impl fmt::Display for YamlLocateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::YamlInvalid { .. } => write!(f, "Failed to parse YAML source."),
+            Self::PathInvalid { .. } => write!(f, "Failed to parse path."),
+            Self::PathNotFound { path } => {
+                write!(f, "Path `{}` was not found in the YAML source.", path)
+            }
+            Self::KeyNone { path } => write!(f, "Value at path `{}` has no key.", path),
+        }
+    }
+}
+
+impl std::error::Error for YamlLocateError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::YamlInvalid { error } => Some(error),
+            Self::PathInvalid { error } => Some(error),
+            Self::PathNotFound { .. } | Self::KeyNone { .. } => None,
+        }
+    }
+}
+
+impl From<ScanError> for YamlLocateError {
+    fn from(error: ScanError) -> Self {
+        Self::YamlInvalid { error }
+    }
+}
+
+impl From<PathParseError> for YamlLocateError {
+    fn from(error: PathParseError) -> Self {
+        Self::PathInvalid { error }
+    }
+}
@@ -0,0 +1,618 @@
+use std::ops::Range;
+
+use saphyr_parser::{Event, Marker, Parser, ScalarStyle, ScanError, Span, StrInput};
+
+use crate::locate::{yaml::YamlLocateError, PathSegment, SpanKind, ValuePath};
+
+/// Locates the byte spans of values in YAML source by their path.
+///
+/// Mapping keys are matched by their scalar value, so `"port"` and `port` are
+/// the same key. When a key appears more than once, the first entry is used.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct YamlLocator {
+    /// Root node of the first document, or `None` for an empty stream.
+    root: Option<Node>,
+}
+
+impl YamlLocator {
+    /// Parses the YAML source, returning a locator for its values.
+    ///
+    /// # Parameters
+    ///
+    /// * `source`: The YAML source.
+    pub fn new(source: &str) -> Result<Self, YamlLocateError> {
+        let mut node_builder = NodeBuilder::new(source);
+        let root = node_builder.root()?;
+
+        Ok(Self { root })
+    }
+
+    /// Returns the byte span of the key of the mapping entry at the path.
+    ///
+    /// # Parameters
+    ///
+    /// * `path`: Path to the value, e.g. `server.port`.
+    pub fn key(&self, path: &str) -> Result<Range<usize>, YamlLocateError> {
+        self.span(&ValuePath::parse(path)?, SpanKind::Key)
+    }
+
+    /// Returns the byte span of the value at the path.
+    ///
+    /// # Parameters
+    ///
+    /// * `path`: Path to the value, e.g. `available[1]`.
+    pub fn value(&self, path: &str) -> Result<Range<usize>, YamlLocateError> {
+        self.span(&ValuePath::parse(path)?, SpanKind::Value)
+    }
+
+    /// Returns the byte span of the whole entry at the path.
+    ///
+    /// # Parameters
+    ///
+    /// * `path`: Path to the value, e.g. `server.port`.
+    pub fn entry(&self, path: &str) -> Result<Range<usize>, YamlLocateError> {
+        self.span(&ValuePath::parse(path)?, SpanKind::Entry)
+    }
+
+    /// Returns the byte span of part of the entry at the path.
+    ///
+    /// # Parameters
+    ///
+    /// * `path`: Path to the value.
+    /// * `span_kind`: Whether to return the span of the key, value, or entry.
+    pub fn span(
+        &self,
+        path: &ValuePath,
+        span_kind: SpanKind,
+    ) -> Result<Range<usize>, YamlLocateError> {
+        let path_not_found = || YamlLocateError::PathNotFound {
+            path: path.to_string(),
+        };
+
+        let mut node = self.root.as_ref().ok_or_else(path_not_found)?;
+        let mut key_span = None;
+        let mut entry_span = node.span.clone();
+        path.segments().iter().try_for_each(|segment| {
+            match (segment, &node.kind) {
+                (PathSegment::Key(key), NodeKind::Mapping(entries)) => {
+                    let entry = entries
+                        .iter()
+                        .find(|entry| entry.key_value.as_deref() == Some(key.as_str()))
+                        .ok_or_else(path_not_found)?;
+                    key_span = Some(entry.key.span.clone());
+                    entry_span = entry.key.span.start..entry.value.span.end;
+                    node = &entry.value;
+                }
+                (PathSegment::Index(index), NodeKind::Sequence(items)) => {
+                    let item = items.get(*index).ok_or_else(path_not_found)?;
+                    key_span = None;
+                    entry_span = item.entry_start..item.value.span.end;
+                    node = &item.value;
+                }
+                _ => return Err(path_not_found()),
+            }
+            Ok(())
+        })?;
+
+        match span_kind {
+            SpanKind::Key => key_span.ok_or_else(|| YamlLocateError::KeyNone {
+                path: path.to_string(),
+            }),
+            SpanKind::Value => Ok(node.span.clone()),
+            SpanKind::Entry => Ok(entry_span),
+        }
+    }
+}
+
+/// A YAML value with its byte span.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Node {
+    /// Byte span of the value.
+    span: Range<usize>,
+    /// Scalar, sequence, or mapping content.
+    kind: NodeKind,
+}
+
+/// Content of a YAML value.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum NodeKind {
+    /// A scalar or alias.
+    Scalar,
+    /// A sequence and its items.
+    Sequence(Vec<SequenceItem>),
+    /// A mapping and its entries.
+    Mapping(Vec<MappingEntry>),
+}
+
+/// An item of a YAML sequence.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct SequenceItem {
+    /// Byte index of the item's `-` indicator, or of the value in flow
+    /// sequences.
+    entry_start: usize,
+    /// The item's value.
+    value: Node,
+}
+
+/// An entry of a YAML mapping.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct MappingEntry {
+    /// Value of the key, when the key is a scalar.
+    key_value: Option<String>,
+    /// The entry's key.
+    key: Node,
+    /// The entry's value.
+    value: Node,
+}
+
+/// Builds [`Node`]s from parser events.
+struct NodeBuilder<'s> {
+    /// The YAML source.
+    source: &'s str,
+    /// Byte index of each character, as parser markers count characters.
+    char_byte_indices: Vec<usize>,
+    /// Parser over the source.
+    parser: Parser<'s, StrInput<'s>>,
+}
+
+impl<'s> NodeBuilder<'s> {
+    fn new(source: &'s str) -> Self {
+        let char_byte_indices = source
+            .char_indices()
+            .map(|(byte_index, _)| byte_index)
+            .chain(std::iter::once(source.len()))
+            .collect();
+
+        Self {
+            source,
+            char_byte_indices,
+            parser: Parser::new_from_str(source),
+        }
+    }
+
+    /// Returns the root node of the first document.
+    fn root(&mut self) -> Result<Option<Node>, ScanError> {
+        loop {
+            match self.next_event()? {
+                (Event::StreamStart, _) | (Event::DocumentStart(_), _) => {}
+                (event, span) if self.is_implicit_null(&event, span) => return Ok(None),
+                (
+                    event @ (Event::Scalar(..)
+                    | Event::Alias(..)
+                    | Event::SequenceStart(..)
+                    | Event::MappingStart(..)),
+                    span,
+                ) => return self.node(event, span).map(Some),
+                // The stream ended before the first document's content.
+                _ => return Ok(None),
+            }
+        }
+    }
+
+    /// Returns whether the event is the null the parser emits for an empty
+    /// node, which has the span of the following token, such as `...`.
+    fn is_implicit_null(&self, event: &Event<'s>, span: Span) -> bool {
+        match event {
+            Event::Scalar(value, ScalarStyle::Plain, ..) => {
+                value == "~" && &self.source[self.byte_range(span)] != "~"
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the node that begins with the event.
+    fn node(&mut self, event: Event<'s>, span: Span) -> Result<Node, ScanError> {
+        let span_bytes = self.byte_range(span);
+        let is_flow = !span_bytes.is_empty();
+        match event {
+            Event::SequenceStart(..) => {
+                let mut items = Vec::new();
+                let end = loop {
+                    match self.next_event()? {
+                        (Event::SequenceEnd, span_end) => {
+                            break self.collection_end(is_flow, span_end, &span_bytes, || {
+                                items.last().map(|item: &SequenceItem| item.value.span.end)
+                            });
+                        }
+                        (event, span) => {
+                            let value = if self.is_implicit_null(&event, span) {
+                                let item_start = items
+                                    .last()
+                                    .map_or(span_bytes.start, |item| item.value.span.end);
+                                self.empty_node(item_start, '-', span)
+                            } else {
+                                self.node(event, span)?
+                            };
+                            let entry_start = if is_flow {
+                                value.span.start
+                            } else {
+                                self.indicator_start(value.span.start)
+                            };
+                            items.push(SequenceItem { entry_start, value });
+                        }
+                    }
+                };
+                Ok(Node {
+                    span: span_bytes.start..end,
+                    kind: NodeKind::Sequence(items),
+                })
+            }
+            Event::MappingStart(..) => {
+                let mut entries = Vec::new();
+                let end = loop {
+                    match self.next_event()? {
+                        (Event::MappingEnd, span_end) => {
+                            break self.collection_end(is_flow, span_end, &span_bytes, || {
+                                entries
+                                    .last()
+                                    .map(|entry: &MappingEntry| entry.value.span.end)
+                            });
+                        }
+                        (event, span) => {
+                            let key_value = match &event {
+                                Event::Scalar(key_value, ..) => {
+                                    Some(String::from(key_value.as_ref()))
+                                }
+                                _ => None,
+                            };
+                            let key = self.node(event, span)?;
+                            let (event, span) = self.next_event()?;
+                            let value = if self.is_implicit_null(&event, span) {
+                                self.empty_node(key.span.end, ':', span)
+                            } else {
+                                self.node(event, span)?
+                            };
+                            entries.push(MappingEntry {
+                                key_value,
+                                key,
+                                value,
+                            });
+                        }
+                    }
+                };
+                Ok(Node {
+                    span: span_bytes.start..end,
+                    kind: NodeKind::Mapping(entries),
+                })
+            }
+            // Quoted scalars' spans extend to the next token, over trailing
+            // comments.
+            Event::Scalar(_, ScalarStyle::SingleQuoted, ..) => Ok(Node {
+                span: span_bytes.start..self.quoted_end(&span_bytes, '\''),
+                kind: NodeKind::Scalar,
+            }),
+            Event::Scalar(_, ScalarStyle::DoubleQuoted, ..) => Ok(Node {
+                span: span_bytes.start..self.quoted_end(&span_bytes, '"'),
+                kind: NodeKind::Scalar,
+            }),
+            _ => {
+                // Block scalars include their trailing line breaks.
+                let text = &self.source[span_bytes.clone()];
+                let end = span_bytes.start + text.trim_end().len();
+                Ok(Node {
+                    span: span_bytes.start..end,
+                    kind: NodeKind::Scalar,
+                })
+            }
+        }
+    }
+
+    /// Returns the node of an implicit null, with an empty span just after the
+    /// indicator that precedes it.
+    ///
+    /// The parser's span of an implicit null may be before the indicator, or
+    /// after a trailing comment.
+    ///
+    /// # Parameters
+    ///
+    /// * `from`: Byte index to search for the indicator from.
+    /// * `indicator`: `:` for mapping values, or `-` for sequence items.
+    /// * `span`: The parser's span of the null.
+    fn empty_node(&self, from: usize, indicator: char, span: Span) -> Node {
+        let index = self
+            .indicator_end(from, indicator)
+            .unwrap_or_else(|| self.byte_index(span.start));
+        Node {
+            span: index..index,
+            kind: NodeKind::Scalar,
+        }
+    }
+
+    /// Returns the byte index after the indicator that is next from `from`,
+    /// skipping whitespace and comments, or `None` if another token is next.
+    fn indicator_end(&self, from: usize, indicator: char) -> Option<usize> {
+        let mut rest = self.source[from..].trim_start();
+        while rest.starts_with('#') {
+            rest = rest.find('\n').map_or("", |line_end| &rest[line_end..]);
+            rest = rest.trim_start();
+        }
+        rest.strip_prefix(indicator)
+            .map(|after| self.source.len() - after.len())
+    }
+
+    /// Returns the byte index after the closing quote of a quoted scalar.
+    ///
+    /// # Parameters
+    ///
+    /// * `span`: Byte span of the scalar, which may begin with a tag or anchor.
+    /// * `quote`: `'` or `"`.
+    fn quoted_end(&self, span: &Range<usize>, quote: char) -> usize {
+        let text = &self.source[span.clone()];
+        let content_start = match text.find(quote) {
+            Some(quote_index) => quote_index + quote.len_utf8(),
+            None => return span.end,
+        };
+        let mut chars = text[content_start..].char_indices().peekable();
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '\\' if quote == '"' => {
+                    chars.next();
+                }
+                // `''` is an escaped quote in single-quoted scalars.
+                '\'' if quote == '\'' && chars.peek().map(|(_, c)| *c) == Some('\'') => {
+                    chars.next();
+                }
+                c if c == quote => return span.start + content_start + index + c.len_utf8(),
+                _ => {}
+            }
+        }
+        span.end
+    }
+
+    /// Returns the byte index after a collection.
+    ///
+    /// Flow collections end just after their closing bracket, and block
+    /// collections at their last value, as the parser's end span may extend
+    /// over trailing whitespace and comments.
+    fn collection_end(
+        &self,
+        is_flow: bool,
+        span_end: Span,
+        span_start: &Range<usize>,
+        last_value_end: impl FnOnce() -> Option<usize>,
+    ) -> usize {
+        if is_flow {
+            // The closing `]` or `}` is a single byte.
+            self.byte_index(span_end.start) + 1
+        } else {
+            last_value_end().unwrap_or(span_start.end)
+        }
+    }
+
+    /// Returns the byte index of the `-` indicator before a block sequence
+    /// item, or the item's start if there is none.
+    fn indicator_start(&self, value_start: usize) -> usize {
+        let before = self.source[..value_start].trim_end();
+        if before.ends_with('-') {
+            before.len() - 1
+        } else {
+            value_start
+        }
+    }
+
+    /// Returns the next parser event.
+    fn next_event(&mut self) -> Result<(Event<'s>, Span), ScanError> {
+        self.parser.next_event().unwrap_or_else(|| {
+            Err(ScanError::new_str(
+                Marker::new(self.char_byte_indices.len() - 1, 0, 0),
+                "unexpected end of events",
+            ))
+        })
+    }
+
+    /// Returns the byte range of a parser span.
+    fn byte_range(&self, span: Span) -> Range<usize> {
+        self.byte_index(span.start)..self.byte_index(span.end)
+    }
+
+    /// Returns the byte index of a parser marker.
+    fn byte_index(&self, marker: Marker) -> usize {
+        self.char_byte_indices
+            .get(marker.index())
+            .copied()
+            .unwrap_or(self.source.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::YamlLocator;
+    use crate::{
+        locate::{yaml::YamlLocateError, SpanKind, ValuePath},
+        test_support::SOURCE_REF_HINT_YAML,
+    };
+
+    const SERVER_YAML: &str = "\
+# Servers é
+server:
+  host: \"localhost\"
+  port: 8080
+ports: [80, 443]
+limits: {cpu: 2, memory: 512}
+users:
+  - name: azriel
+    admin: true
+  - name: ferris
+motd: |
+  hello
+  world
+
+";
+
+    fn locate<'s>(
+        source: &'s str,
+        path: &str,
+        span_kind: SpanKind,
+    ) -> Result<&'s str, YamlLocateError> {
+        let locator = YamlLocator::new(source)?;
+        let path = ValuePath::parse(path)?;
+        locator.span(&path, span_kind).map(|range| &source[range])
+    }
+
+    #[test]
+    fn locates_ranges_used_by_examples() -> Result<(), YamlLocateError> {
+        let locator = YamlLocator::new(SOURCE_REF_HINT_YAML)?;
+
+        assert_eq!(44..49, locator.value("chosen")?);
+        assert_eq!(4..34, locator.entry("available")?);
+        assert_eq!(4..13, locator.key("available")?);
+        Ok(())
+    }
+
+    #[test]
+    fn locates_key_value_and_entry_in_nested_mapping() -> Result<(), YamlLocateError> {
+        assert_eq!("port", locate(SERVER_YAML, "server.port", SpanKind::Key)?);
+        assert_eq!("8080", locate(SERVER_YAML, "server.port", SpanKind::Value)?);
+        assert_eq!(
+            "port: 8080",
+            locate(SERVER_YAML, "server.port", SpanKind::Entry)?
+        );
+        assert_eq!(
+            "\"localhost\"",
+            locate(SERVER_YAML, "server.host", SpanKind::Value)?
+        );
+        assert_eq!(
+            "host: \"localhost\"\n  port: 8080",
+            locate(SERVER_YAML, "server", SpanKind::Value)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn locates_items_in_block_and_flow_collections() -> Result<(), YamlLocateError> {
+        assert_eq!("443", locate(SERVER_YAML, "ports[1]", SpanKind::Entry)?);
+        assert_eq!("[80, 443]", locate(SERVER_YAML, "ports", SpanKind::Value)?);
+        assert_eq!(
+            "{cpu: 2, memory: 512}",
+            locate(SERVER_YAML, "limits", SpanKind::Value)?
+        );
+        assert_eq!(
+            "memory: 512",
+            locate(SERVER_YAML, "limits.memory", SpanKind::Entry)?
+        );
+        assert_eq!(
+            "- name: azriel\n    admin: true",
+            locate(SERVER_YAML, "users[0]", SpanKind::Entry)?
+        );
+        assert_eq!(
+            "ferris",
+            locate(SERVER_YAML, "users[1].name", SpanKind::Value)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn locates_flow_collections_without_trailing_comments() -> Result<(), YamlLocateError> {
+        assert_eq!("[1, 2]", locate("k: [1, 2] # c\n", "k", SpanKind::Value)?);
+        assert_eq!("{a: 1}", locate("k: {a: 1} # c\n", "k", SpanKind::Value)?);
+        assert_eq!(
+            "[1, 2]",
+            locate("k: [1, 2]   \nz: 1\n", "k", SpanKind::Value)?
+        );
+        assert_eq!(
+            "- [1, 2]",
+            locate("s:\n  - [1, 2]  # c\n", "s[0]", SpanKind::Entry)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn locates_quoted_scalars_without_trailing_comments() -> Result<(), YamlLocateError> {
+        assert_eq!(
+            "\"ghi\"",
+            locate("chosen: \"ghi\" # pick one\n", "chosen", SpanKind::Value)?
+        );
+        assert_eq!("\"x\"", locate("- \"x\" # c\n", "[0]", SpanKind::Value)?);
+        assert_eq!(
+            "'it''s'",
+            locate("a: 'it''s'  # c\n", "a", SpanKind::Value)?
+        );
+        assert_eq!(
+            "\"a\\\"b\"",
+            locate("a: \"a\\\"b\" # c\n", "a", SpanKind::Value)?
+        );
+        assert_eq!(
+            "k: \"v\"",
+            locate("k: \"v\"  # c\nz: 1\n", "k", SpanKind::Entry)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn locates_empty_values_after_their_indicator() -> Result<(), YamlLocateError> {
+        let source = "a:\nb: 1\n";
+        let locator = YamlLocator::new(source)?;
+        assert_eq!(2..2, locator.value("a")?);
+        assert_eq!("a:", &source[locator.entry("a")?]);
+
+        assert_eq!(3..3, YamlLocator::new("a :   # c\nb: 1\n")?.value("a")?);
+        assert_eq!(
+            6..6,
+            YamlLocator::new("s:\n  - # c\n  - 2\n")?.value("s[0]")?
+        );
+        assert_eq!(12..12, YamlLocator::new("s:\n  - 1\n  -\n")?.value("s[1]")?);
+        Ok(())
+    }
+
+    #[test]
+    fn locates_block_scalar_without_trailing_line_breaks() -> Result<(), YamlLocateError> {
+        assert_eq!(
+            "hello\n  world",
+            locate(SERVER_YAML, "motd", SpanKind::Value)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn returns_error_when_path_is_not_found() {
+        let result = locate(SERVER_YAML, "server.ports", SpanKind::Value);
+        assert!(matches!(
+            result,
+            Err(YamlLocateError::PathNotFound { path }) if path == "server.ports"
+        ));
+
+        let result = locate(SERVER_YAML, "ports[2]", SpanKind::Value);
+        assert!(matches!(
+            result,
+            Err(YamlLocateError::PathNotFound { path }) if path == "ports[2]"
+        ));
+
+        let result = locate(SERVER_YAML, "server[0]", SpanKind::Value);
+        assert!(matches!(
+            result,
+            Err(YamlLocateError::PathNotFound { path }) if path == "server[0]"
+        ));
+    }
+
+    #[test]
+    fn returns_error_when_document_is_empty() -> Result<(), YamlLocateError> {
+        let locator = YamlLocator::new("---\n...\n")?;
+
+        assert!(matches!(
+            locator.value(""),
+            Err(YamlLocateError::PathNotFound { path }) if path.is_empty()
+        ));
+        assert!(matches!(
+            YamlLocator::new("")?.value(""),
+            Err(YamlLocateError::PathNotFound { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn returns_error_when_key_of_sequence_item_is_requested() {
+        let result = locate(SERVER_YAML, "ports[0]", SpanKind::Key);
+
+        assert!(matches!(
+            result,
+            Err(YamlLocateError::KeyNone { path }) if path == "ports[0]"
+        ));
+    }
+
+    #[test]
+    fn returns_error_when_yaml_is_invalid() {
+        let result = YamlLocator::new("server: [80, 443\n");
+
+        assert!(matches!(result, Err(YamlLocateError::YamlInvalid { .. })));
+    }
+}
@@ -0,0 +1,8 @@
+//! Conversion of source errors into Language Server Protocol diagnostics.
+//!
+//! This is enabled by the `"lsp"` feature, so that editor extensions can
+//! publish the same errors that are rendered on the command line.
+
+pub use self::lsp_converter::LspConverter;
+
+mod lsp_converter;
@@ -0,0 +1,444 @@
+use std::collections::HashMap;
+
+use codespan_reporting::{
+    diagnostic::Severity,
+    files::{self, Files},
+};
+use lsp_types::{
+    Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location, NumberOrString,
+    Position, Range, Url,
+};
+
+use crate::{
+    fmt::Code,
+    model::{ErrorCode, ErrorDetail, Label, LabelStyle, SourceError, SourceErrors, Trailer},
+};
+
+/// Converts source errors into [`lsp_types::Diagnostic`]s.
+///
+/// Each primary label of a source error becomes a diagnostic in the label's
+/// file. Errors without primary labels become a diagnostic at their first
+/// label, and errors without labels are skipped, as they have no file to be
+/// reported in.
+///
+/// * `code` is the [`Code::string`] of the error code.
+/// * `message` is the error code description, followed by the label message and
+///   notes on separate lines.
+/// * `relatedInformation` holds the other labels.
+///
+/// Positions use the UTF-16 encoding, which is the LSP default.
+#[derive(Clone, Debug, Default)]
+pub struct LspConverter {
+    /// Name of the tool that produced the diagnostics, e.g. `"myapp"`.
+    source: Option<String>,
+}
+
+impl LspConverter {
+    /// Returns a new `LspConverter`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the `source` of the diagnostics, shown in the editor alongside
+    /// the message.
+    #[must_use]
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Returns the diagnostics for the source error, grouped by file URI.
+    ///
+    /// # Parameters
+    ///
+    /// * `files`: Files that the error refers to.
+    /// * `source_error`: The error to convert.
+    /// * `file_uri`: Returns the URI of a file.
+    pub fn diagnostics<'files, Ec, Ed, Fs, F>(
+        &self,
+        files: &'files Fs,
+        source_error: &SourceError<'files, Ec, Ed, Fs>,
+        mut file_uri: F,
+    ) -> Result<HashMap<Url, Vec<Diagnostic>>, files::Error>
+    where
+        Ec: ErrorCode,
+        Ed: ErrorDetail<'files, Files = Fs>,
+        Fs: Files<'files>,
+        F: FnMut(Fs::FileId) -> Url,
+    {
+        let mut diagnostics_by_uri = HashMap::new();
+        self.diagnostics_insert(files, source_error, &mut file_uri, &mut diagnostics_by_uri)?;
+
+        Ok(diagnostics_by_uri)
+    }
+
+    /// Returns the diagnostics for all source errors, grouped by file URI.
+    ///
+    /// # Parameters
+    ///
+    /// * `files`: Files that the errors refer to.
+    /// * `source_errors`: The errors to convert.
+    /// * `file_uri`: Returns the URI of a file.
+    pub fn diagnostics_all<'files, Ec, Ed, Fs, F>(
+        &self,
+        files: &'files Fs,
+        source_errors: &SourceErrors<'files, Ec, Ed, Fs>,
+        mut file_uri: F,
+    ) -> Result<HashMap<Url, Vec<Diagnostic>>, files::Error>
+    where
+        Ec: ErrorCode,
+        Ed: ErrorDetail<'files, Files = Fs>,
+        Fs: Files<'files>,
+        F: FnMut(Fs::FileId) -> Url,
+    {
+        let mut diagnostics_by_uri = HashMap::new();
+        source_errors.iter().try_for_each(|source_error| {
+            self.diagnostics_insert(files, source_error, &mut file_uri, &mut diagnostics_by_uri)
+        })?;
+
+        Ok(diagnostics_by_uri)
+    }
+
+    /// Returns the 0-based line and UTF-16 character offset of a byte index.
+    ///
+    /// # Parameters
+    ///
+    /// * `files`: Files containing the file.
+    /// * `file_id`: ID of the file.
+    /// * `byte_index`: Byte index within the file's source.
+    pub fn position<'files, Fs>(
+        files: &'files Fs,
+        file_id: Fs::FileId,
+        byte_index: usize,
+    ) -> Result<Position, files::Error>
+    where
+        Fs: Files<'files>,
+    {
+        let source = files.source(file_id)?;
+        let source = source.as_ref();
+        let line_index = files.line_index(file_id, byte_index)?;
+        let line_range = files.line_range(file_id, line_index)?;
+        let line_prefix = source
+            .get(line_range.start..byte_index.min(source.len()))
+            .ok_or(files::Error::InvalidCharBoundary { given: byte_index })?;
+        let character = line_prefix.encode_utf16().count();
+
+        Ok(Position::new(line_index as u32, character as u32))
+    }
+
+    /// Returns the LSP range of a label.
+    fn range<'files, Fs>(
+        files: &'files Fs,
+        label: &Label<Fs::FileId>,
+    ) -> Result<Range, files::Error>
+    where
+        Fs: Files<'files>,
+    {
+        Ok(Range::new(
+            Self::position(files, label.span.file_id, label.span.range.start)?,
+            Self::position(files, label.span.file_id, label.span.range.end)?,
+        ))
+    }
+
+    /// Inserts the diagnostics for the source error into the map.
+    fn diagnostics_insert<'files, Ec, Ed, Fs, F>(
+        &self,
+        files: &'files Fs,
+        source_error: &SourceError<'files, Ec, Ed, Fs>,
+        file_uri: &mut F,
+        diagnostics_by_uri: &mut HashMap<Url, Vec<Diagnostic>>,
+    ) -> Result<(), files::Error>
+    where
+        Ec: ErrorCode,
+        Ed: ErrorDetail<'files, Files = Fs>,
+        Fs: Files<'files>,
+        F: FnMut(Fs::FileId) -> Url,
+    {
+        let labels = source_error.detail.labels();
+        let trailers = source_error
+            .detail
+            .trailers(files)
+            .iter()
+            .map(Trailer::to_string)
+            .collect::<Vec<String>>();
+        // Errors without primary labels are reported at their first label.
+        let has_primary = labels
+            .iter()
+            .any(|label| label.style == LabelStyle::Primary);
+        let is_reported = |label_index: usize, label: &Label<Fs::FileId>| {
+            if has_primary {
+                label.style == LabelStyle::Primary
+            } else {
+                label_index == 0
+            }
+        };
+        let related_information = labels
+            .iter()
+            .enumerate()
+            .filter(|(label_index, label)| !is_reported(*label_index, label))
+            .map(|(_, label)| {
+                Ok(DiagnosticRelatedInformation {
+                    location: Location::new(
+                        file_uri(label.span.file_id),
+                        Self::range(files, label)?,
+                    ),
+                    message: label.message.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>, files::Error>>()?;
+
+        labels
+            .iter()
+            .enumerate()
+            .filter(|(label_index, label)| is_reported(*label_index, label))
+            .try_for_each(|(_, label)| {
+                let mut message = String::from(source_error.code.description());
+                std::iter::once(&label.message)
+                    .filter(|label_message| !label_message.is_empty())
+                    .chain(trailers.iter())
+                    .for_each(|line| {
+                        message.push('\n');
+                        message.push_str(line);
+                    });
+
+                let diagnostic = Diagnostic {
+                    range: Self::range(files, label)?,
+                    severity: Some(severity(source_error.severity)),
+                    code: Some(NumberOrString::String(Code::string(source_error.code))),
+                    source: self.source.clone(),
+                    message,
+                    related_information: if related_information.is_empty() {
+                        None
+                    } else {
+                        Some(related_information.clone())
+                    },
+                    ..Diagnostic::default()
+                };
+
+                diagnostics_by_uri
+                    .entry(file_uri(label.span.file_id))
+                    .or_default()
+                    .push(diagnostic);
+
+                Ok(())
+            })
+    }
+}
+
+/// Returns the LSP severity for the severity.
+fn severity(severity: Severity) -> DiagnosticSeverity {
+    match severity {
+        Severity::Bug | Severity::Error => DiagnosticSeverity::ERROR,
+        Severity::Warning => DiagnosticSeverity::WARNING,
+        Severity::Note => DiagnosticSeverity::INFORMATION,
+        Severity::Help => DiagnosticSeverity::HINT,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use codespan_reporting::diagnostic::Severity;
+    use lsp_types::{
+        Diagnostic, DiagnosticRelatedInformation, DiagnosticSeverity, Location, NumberOrString,
+        Position, Range, Url,
+    };
+
+    use super::LspConverter;
+    use crate::{
+        test_support::{self, TestErrorCode, TestErrorDetail, TestFiles},
+        Label, SourceError, SourceErrors,
+    };
+
+    #[test]
+    fn diagnostics_maps_primary_labels_and_related_information(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_invalid_value(file_id, Severity::Warning);
+        let uri = Url::parse("file:///project/examples/source_ref_hint.yaml")?;
+
+        let diagnostics_by_uri = LspConverter::new().with_source("srcerr-test").diagnostics(
+            &files,
+            &source_error,
+            |_| uri.clone(),
+        )?;
+
+        assert_eq!(1, diagnostics_by_uri.len());
+        assert_eq!(
+            Some(&vec![Diagnostic {
+                range: Range::new(Position::new(5, 8), Position::new(5, 13)),
+                severity: Some(DiagnosticSeverity::WARNING),
+                code: Some(NumberOrString::String(String::from("E1"))),
+                source: Some(String::from("srcerr-test")),
+                message: String::from(
+                    "`chosen` value is invalid.\n\
+                    invalid value specified\n\
+                    `chosen` value must come from one of `available` values"
+                ),
+                related_information: Some(vec![DiagnosticRelatedInformation {
+                    location: Location::new(
+                        uri.clone(),
+                        Range::new(Position::new(1, 0), Position::new(3, 9))
+                    ),
+                    message: String::from("defined here"),
+                }]),
+                ..Diagnostic::default()
+            }]),
+            diagnostics_by_uri.get(&uri)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn position_counts_utf16_code_units() -> Result<(), Box<dyn std::error::Error>> {
+        let mut files = TestFiles::new();
+        // `é` is 2 bytes and 1 UTF-16 code unit, `😀` is 4 bytes and 2 UTF-16 code
+        // units.
+        let file_id = files.add("file", "a: 1\nb: \"é😀x\"\n");
+
+        assert_eq!(
+            Position::new(0, 0),
+            LspConverter::position(&files, file_id, 0)?
+        );
+        assert_eq!(
+            Position::new(1, 4),
+            LspConverter::position(&files, file_id, 9)?
+        );
+        assert_eq!(
+            Position::new(1, 7),
+            LspConverter::position(&files, file_id, 15)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn diagnostics_all_groups_by_file_uri() -> Result<(), Box<dyn std::error::Error>> {
+        let mut files = TestFiles::new();
+        let file_a = files.add("a.yaml", "a: 1\n");
+        let file_b = files.add("b.yaml", "b: 2\n");
+        let source_error = |file_id: usize| {
+            let detail = TestErrorDetail {
+                labels: vec![Label::primary(file_id, 3..4)],
+                trailers: Vec::new(),
+                suggestions: Vec::new(),
+            };
+            SourceError::new(TestErrorCode::InvalidValue, detail, Severity::Error)
+        };
+        let source_errors = SourceErrors::from(vec![
+            source_error(file_a),
+            source_error(file_b),
+            source_error(file_a),
+        ]);
+
+        let diagnostics_by_uri =
+            LspConverter::new().diagnostics_all(&files, &source_errors, |file_id| {
+                let name = files.get(file_id).expect("Expected file to exist.").name();
+                Url::parse(&format!("file:///project/{name}")).expect("Expected valid URL.")
+            })?;
+
+        let uri_a = Url::parse("file:///project/a.yaml")?;
+        let uri_b = Url::parse("file:///project/b.yaml")?;
+        assert_eq!(Some(2), diagnostics_by_uri.get(&uri_a).map(Vec::len));
+        assert_eq!(Some(1), diagnostics_by_uri.get(&uri_b).map(Vec::len));
+        assert_eq!(
+            Some(&NumberOrString::String(String::from("E1"))),
+            diagnostics_by_uri[&uri_b][0].code.as_ref()
+        );
+        assert_eq!(
+            "`chosen` value is invalid.",
+            diagnostics_by_uri[&uri_b][0].message
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn diagnostics_reports_first_label_when_none_are_primary(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_secondary_only(file_id, Severity::Error);
+        let uri = Url::parse("file:///project/examples/source_ref_hint.yaml")?;
+
+        let diagnostics_by_uri =
+            LspConverter::new().diagnostics(&files, &source_error, |_| uri.clone())?;
+
+        let diagnostics = &diagnostics_by_uri[&uri];
+        assert_eq!(1, diagnostics.len());
+        assert_eq!(
+            Range::new(Position::new(5, 8), Position::new(5, 13)),
+            diagnostics[0].range
+        );
+        assert_eq!(
+            Some(vec![DiagnosticRelatedInformation {
+                location: Location::new(
+                    uri.clone(),
+                    Range::new(Position::new(1, 0), Position::new(3, 9))
+                ),
+                message: String::from("defined here"),
+            }]),
+            diagnostics[0].related_information
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn diagnostics_skips_errors_without_labels() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_unlabelled(file_id, Severity::Error);
+        let uri = Url::parse("file:///project/examples/source_ref_hint.yaml")?;
+
+        let diagnostics_by_uri =
+            LspConverter::new().diagnostics(&files, &source_error, |_| uri.clone())?;
+
+        assert!(diagnostics_by_uri.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn diagnostics_maps_severity() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let uri = Url::parse("file:///project/examples/source_ref_hint.yaml")?;
+
+        let source_errors = [
+            Severity::Bug,
+            Severity::Error,
+            Severity::Warning,
+            Severity::Note,
+            Severity::Help,
+        ]
+        .iter()
+        .map(|severity| test_support::source_error_invalid_value(file_id, *severity))
+        .collect::<SourceErrors<'_, _, _, _>>();
+        let diagnostics_by_uri =
+            LspConverter::new().diagnostics_all(&files, &source_errors, |_| uri.clone())?;
+
+        assert_eq!(
+            vec![
+                Some(DiagnosticSeverity::ERROR),
+                Some(DiagnosticSeverity::ERROR),
+                Some(DiagnosticSeverity::WARNING),
+                Some(DiagnosticSeverity::INFORMATION),
+                Some(DiagnosticSeverity::HINT),
+            ],
+            diagnostics_by_uri[&uri]
+                .iter()
+                .map(|diagnostic| diagnostic.severity)
+                .collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn diagnostics_clamps_label_past_end_of_file() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_range_invalid(file_id, Severity::Error);
+        let uri = Url::parse("file:///project/examples/source_ref_hint.yaml")?;
+
+        let diagnostics_by_uri =
+            LspConverter::new().diagnostics(&files, &source_error, |_| uri.clone())?;
+
+        assert_eq!(
+            Range::new(Position::new(6, 0), Position::new(6, 0)),
+            diagnostics_by_uri[&uri][0].range
+        );
+        Ok(())
+    }
+}
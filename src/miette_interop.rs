@@ -0,0 +1,13 @@
+//! Interoperability with [`miette`], so source errors can be reported by a
+//! `miette` based application.
+//!
+//! This is enabled by the `"miette"` feature. [`MietteAdapter`] captures a
+//! [`SourceError`] together with the source of the files it refers to, and
+//! implements [`miette::Diagnostic`], so it can be returned as a
+//! [`miette::Report`] from `main`.
+//!
+//! [`SourceError`]: crate::SourceError
+
+pub use self::miette_adapter::MietteAdapter;
+
+mod miette_adapter;
@@ -0,0 +1,439 @@
+use std::fmt;
+
+use codespan_reporting::{
+    diagnostic::Severity,
+    files::{self, Files},
+};
+use miette::{Diagnostic, LabeledSpan, NamedSource, SourceCode};
+
+use crate::{
+    fmt::Code,
+    model::{ErrorCode, ErrorDetail, Label, LabelStyle, SourceError, Trailer},
+};
+
+/// A [`SourceError`] bound to its files, implementing [`miette::Diagnostic`].
+///
+/// The adapter owns a copy of the source, so it is `'static` and can be
+/// returned through [`miette::Result`]:
+///
+/// * `code` is the [`Code::string`] of the error code.
+/// * The message is the error code description.
+/// * `severity` maps [`Severity::Bug`] and [`Severity::Error`] to
+///   [`miette::Severity::Error`], [`Severity::Warning`] to
+///   [`miette::Severity::Warning`], and [`Severity::Note`] and
+///   [`Severity::Help`] to [`miette::Severity::Advice`].
+/// * `labels` are the labels in the file of the first primary label, which is
+///   also the `source_code`.
+/// * `help` holds the trailers, one per line, except for
+///   [`Trailer::SeeAlso`] links.
+/// * `url` is the first [`Trailer::SeeAlso`] link.
+/// * Labels in other files are reported as `related` diagnostics, each with
+///   its own source.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use miette::IntoDiagnostic;
+///
+/// fn main() -> miette::Result<()> {
+///     let files = load_files();
+///     if let Err(source_error) = validate(&files) {
+///         let miette_adapter = source_error.to_miette(&files).into_diagnostic()?;
+///         return Err(miette_adapter.into());
+///     }
+///
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct MietteAdapter {
+    /// The error code string, e.g. `"E011"`.
+    code: String,
+    /// The error code description.
+    message: String,
+    /// Severity of the error.
+    severity: miette::Severity,
+    /// Trailers joined by new lines, if any.
+    help: Option<String>,
+    /// Link to further documentation about the error.
+    url: Option<String>,
+    /// Source of the file that the labels refer to.
+    source_code: Option<NamedSource<String>>,
+    /// Labels within `source_code`.
+    labels: Vec<LabeledSpan>,
+    /// Labels in other files.
+    related: Vec<MietteAdapter>,
+}
+
+impl MietteAdapter {
+    /// Returns a new `MietteAdapter` for the source error.
+    ///
+    /// # Parameters
+    ///
+    /// * `files`: Files that the error refers to.
+    /// * `source_error`: The error to adapt.
+    pub fn new<'files, Ec, Ed, Fs>(
+        files: &'files Fs,
+        source_error: &SourceError<'files, Ec, Ed, Fs>,
+    ) -> Result<Self, files::Error>
+    where
+        Ec: ErrorCode,
+        Ed: ErrorDetail<'files, Files = Fs>,
+        Fs: Files<'files>,
+    {
+        let code = Code::string(source_error.code);
+        let message = String::from(source_error.code.description());
+        let severity = severity(source_error.severity);
+        let trailers = source_error.detail.trailers(files);
+        let help = trailers
+            .iter()
+            .filter(|trailer| !matches!(trailer, Trailer::SeeAlso(_)))
+            .map(Trailer::to_string)
+            .collect::<Vec<String>>();
+        let help = if help.is_empty() {
+            None
+        } else {
+            Some(help.join("\n"))
+        };
+        let url = trailers.into_iter().find_map(|trailer| match trailer {
+            Trailer::SeeAlso(url) => Some(url),
+            _ => None,
+        });
+
+        // Group labels by file, starting with the file of the first primary label.
+        let labels = source_error.detail.labels();
+        let mut labels_by_file = Vec::<(Fs::FileId, Vec<&Label<Fs::FileId>>)>::new();
+        labels
+            .iter()
+            .filter(|label| label.style == LabelStyle::Primary)
+            .chain(
+                labels
+                    .iter()
+                    .filter(|label| label.style == LabelStyle::Secondary),
+            )
+            .for_each(|label| {
+                match labels_by_file
+                    .iter_mut()
+                    .find(|(file_id, _)| *file_id == label.span.file_id)
+                {
+                    Some((_, file_labels)) => file_labels.push(label),
+                    None => labels_by_file.push((label.span.file_id, vec![label])),
+                }
+            });
+        let mut labels_by_file = labels_by_file.into_iter();
+
+        let (source_code, labels) = match labels_by_file.next() {
+            Some((file_id, file_labels)) => {
+                let (source_code, labels) = Self::source_and_labels(files, file_id, &file_labels)?;
+                (Some(source_code), labels)
+            }
+            None => (None, Vec::new()),
+        };
+        let related = labels_by_file
+            .map(|(file_id, file_labels)| {
+                let (source_code, labels) = Self::source_and_labels(files, file_id, &file_labels)?;
+                Ok(MietteAdapter {
+                    code: code.clone(),
+                    message: message.clone(),
+                    severity,
+                    help: None,
+                    url: None,
+                    source_code: Some(source_code),
+                    labels,
+                    related: Vec::new(),
+                })
+            })
+            .collect::<Result<Vec<_>, files::Error>>()?;
+
+        Ok(Self {
+            code,
+            message,
+            severity,
+            help,
+            url,
+            source_code,
+            labels,
+            related,
+        })
+    }
+
+    /// Returns the named source of the file, and the labels as spans within
+    /// it.
+    fn source_and_labels<'files, Fs>(
+        files: &'files Fs,
+        file_id: Fs::FileId,
+        labels: &[&Label<Fs::FileId>],
+    ) -> Result<(NamedSource<String>, Vec<LabeledSpan>), files::Error>
+    where
+        Fs: Files<'files>,
+    {
+        let name = files.name(file_id)?.to_string();
+        let source = String::from(files.source(file_id)?.as_ref());
+        let labeled_spans = labels
+            .iter()
+            .map(|label| {
+                let message = if label.message.is_empty() {
+                    None
+                } else {
+                    Some(label.message.clone())
+                };
+                let span = label.span.range.clone();
+                match label.style {
+                    LabelStyle::Primary => LabeledSpan::new_primary_with_span(message, span),
+                    LabelStyle::Secondary => LabeledSpan::new_with_span(message, span),
+                }
+            })
+            .collect();
+
+        Ok((NamedSource::new(name, source), labeled_spans))
+    }
+}
+
+impl fmt::Display for MietteAdapter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for MietteAdapter {}
+
+impl Diagnostic for MietteAdapter {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        Some(Box::new(&self.code))
+    }
+
+    fn severity(&self) -> Option<miette::Severity> {
+        Some(self.severity)
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.help
+            .as_ref()
+            .map(|help| Box::new(help) as Box<dyn fmt::Display + 'a>)
+    }
+
+    fn url<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.url
+            .as_ref()
+            .map(|url| Box::new(url) as Box<dyn fmt::Display + 'a>)
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        self.source_code
+            .as_ref()
+            .map(|source_code| source_code as &dyn SourceCode)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        if self.labels.is_empty() {
+            None
+        } else {
+            Some(Box::new(self.labels.iter().cloned()))
+        }
+    }
+
+    fn related<'a>(&'a self) -> Option<Box<dyn Iterator<Item = &'a dyn Diagnostic> + 'a>> {
+        if self.related.is_empty() {
+            None
+        } else {
+            Some(Box::new(
+                self.related
+                    .iter()
+                    .map(|related| related as &dyn Diagnostic),
+            ))
+        }
+    }
+}
+
+/// Returns the `miette` severity for the severity.
+fn severity(severity: Severity) -> miette::Severity {
+    match severity {
+        Severity::Bug | Severity::Error => miette::Severity::Error,
+        Severity::Warning => miette::Severity::Warning,
+        Severity::Note | Severity::Help => miette::Severity::Advice,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use codespan_reporting::diagnostic::Severity;
+    use miette::{Diagnostic, LabeledSpan};
+
+    use super::MietteAdapter;
+    use crate::{
+        test_support::{self, TestErrorCode, TestErrorDetail},
+        Label, SourceError,
+    };
+
+    #[test]
+    fn maps_code_severity_labels_and_help() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_invalid_value(file_id, Severity::Warning);
+
+        let miette_adapter = MietteAdapter::new(&files, &source_error)?;
+
+        assert_eq!("`chosen` value is invalid.", miette_adapter.to_string());
+        assert_eq!(
+            Some(String::from("E1")),
+            miette_adapter.code().map(|code| code.to_string())
+        );
+        assert_eq!(Some(miette::Severity::Warning), miette_adapter.severity());
+        assert_eq!(
+            Some(String::from(
+                "`chosen` value must come from one of `available` values"
+            )),
+            miette_adapter.help().map(|help| help.to_string())
+        );
+        assert_eq!(
+            vec![
+                LabeledSpan::new_primary_with_span(
+                    Some(String::from("invalid value specified")),
+                    44..49
+                ),
+                LabeledSpan::new_with_span(Some(String::from("defined here")), 4..34),
+            ],
+            miette_adapter
+                .labels()
+                .map(Iterator::collect::<Vec<_>>)
+                .unwrap_or_default()
+        );
+
+        let source_code = miette_adapter
+            .source_code()
+            .expect("Expected source code to be set.");
+        let span_contents = source_code.read_span(&(44..49).into(), 0, 0)?;
+        assert_eq!(b"\"ghi\"", span_contents.data());
+        assert_eq!(Some("examples/source_ref_hint.yaml"), span_contents.name());
+        assert!(miette_adapter.related().is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn maps_severity() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+
+        let severities = [
+            Severity::Bug,
+            Severity::Error,
+            Severity::Warning,
+            Severity::Note,
+            Severity::Help,
+        ]
+        .iter()
+        .map(|severity| {
+            let source_error = test_support::source_error_invalid_value(file_id, *severity);
+            MietteAdapter::new(&files, &source_error)
+                .map(|miette_adapter| miette_adapter.severity())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+        assert_eq!(
+            vec![
+                Some(miette::Severity::Error),
+                Some(miette::Severity::Error),
+                Some(miette::Severity::Warning),
+                Some(miette::Severity::Advice),
+                Some(miette::Severity::Advice),
+            ],
+            severities
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn maps_secondary_labels_when_none_are_primary() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_secondary_only(file_id, Severity::Error);
+
+        let miette_adapter = MietteAdapter::new(&files, &source_error)?;
+
+        assert_eq!(
+            vec![
+                LabeledSpan::new_with_span(Some(String::from("invalid value specified")), 44..49),
+                LabeledSpan::new_with_span(Some(String::from("defined here")), 4..34),
+            ],
+            miette_adapter
+                .labels()
+                .map(Iterator::collect::<Vec<_>>)
+                .unwrap_or_default()
+        );
+        assert!(miette_adapter.source_code().is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn omits_source_code_and_labels_for_unlabelled_error() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_unlabelled(file_id, Severity::Error);
+
+        let miette_adapter = MietteAdapter::new(&files, &source_error)?;
+
+        assert!(miette_adapter.source_code().is_none());
+        assert!(miette_adapter.labels().is_none());
+        assert_eq!(
+            Some(String::from(
+                "`chosen` value must come from one of `available` values"
+            )),
+            miette_adapter.help().map(|help| help.to_string())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn source_code_returns_error_for_label_past_end_of_file(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_error = test_support::source_error_range_invalid(file_id, Severity::Error);
+
+        let miette_adapter = MietteAdapter::new(&files, &source_error)?;
+
+        let source_code = miette_adapter
+            .source_code()
+            .expect("Expected source code to be set.");
+        assert!(source_code.read_span(&(395..400).into(), 0, 0).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn reports_labels_in_other_files_as_related() -> Result<(), Box<dyn std::error::Error>> {
+        let (mut files, file_id) = test_support::files();
+        let file_id_other = files.add("other.yaml", "chosen: \"abc\"\n");
+        let detail = TestErrorDetail {
+            labels: vec![
+                Label::primary(file_id, 44..49),
+                Label::secondary(file_id_other, 8..13).with_message("also here"),
+            ],
+            trailers: Vec::new(),
+            suggestions: Vec::new(),
+        };
+        let source_error = SourceError::new(TestErrorCode::InvalidValue, detail, Severity::Error);
+
+        let miette_adapter = MietteAdapter::new(&files, &source_error)?;
+
+        assert!(miette_adapter.help().is_none());
+        assert_eq!(
+            vec![LabeledSpan::new_primary_with_span(None, 44..49)],
+            miette_adapter
+                .labels()
+                .map(Iterator::collect::<Vec<_>>)
+                .unwrap_or_default()
+        );
+        let related = miette_adapter
+            .related()
+            .map(Iterator::collect::<Vec<_>>)
+            .unwrap_or_default();
+        assert_eq!(1, related.len());
+        assert_eq!(
+            vec![LabeledSpan::new_with_span(
+                Some(String::from("also here")),
+                8..13
+            )],
+            related[0]
+                .labels()
+                .map(Iterator::collect::<Vec<_>>)
+                .unwrap_or_default()
+        );
+        Ok(())
+    }
+}
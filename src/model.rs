@@ -1,7 +1,25 @@
 //! Data types representing the source error.
 
-pub use self::{error_code::ErrorCode, error_detail::ErrorDetail, source_error::SourceError};
+pub use self::{
+    applicability::Applicability, code_selector::CodeSelector, error_code::ErrorCode,
+    error_detail::ErrorDetail, label::Label, label_style::LabelStyle, line_column::LineColumn,
+    lint_level::LintLevel, severity_policy::SeverityPolicy,
+    severity_policy_error::SeverityPolicyError, source_error::SourceError,
+    source_errors::SourceErrors, span::Span, suggestion::Suggestion, trailer::Trailer,
+};
 
+mod applicability;
+mod code_selector;
 mod error_code;
 mod error_detail;
+mod label;
+mod label_style;
+mod line_column;
+mod lint_level;
+mod severity_policy;
+mod severity_policy_error;
 mod source_error;
+mod source_errors;
+mod span;
+mod suggestion;
+mod trailer;
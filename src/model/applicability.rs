@@ -0,0 +1,36 @@
+use std::fmt;
+
+/// How confident a [`Suggestion`] is that its replacement fixes the error.
+///
+/// [`Suggestion`]: crate::model::Suggestion
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Applicability {
+    /// The replacement is definitely what the user intended, and may be
+    /// applied automatically.
+    MachineApplicable,
+    /// The replacement may be what the user intended, but should be reviewed
+    /// before it is applied.
+    MaybeIncorrect,
+    /// The replacement contains placeholders such as `<value>`, which the user
+    /// must fill in.
+    HasPlaceholders,
+}
+
+impl Applicability {
+    /// Returns the applicability as a `snake_case` string.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::MachineApplicable => "machine_applicable",
+            Self::MaybeIncorrect => "maybe_incorrect",
+            Self::HasPlaceholders => "has_placeholders",
+        }
+    }
+}
+
+impl fmt::Display for Applicability {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
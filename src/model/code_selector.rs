@@ -0,0 +1,214 @@
+use std::{fmt, str::FromStr};
+
+use crate::{model::SeverityPolicyError, ErrorCode};
+
+/// Selects which error codes a [`SeverityPolicy`] rule applies to.
+///
+/// Selectors are parsed from strings, and compare prefixes ignoring ASCII case:
+///
+/// * `"*"`: Every error code.
+/// * `"W"`: Every error code with the prefix `W`.
+/// * `"E010..=E019"` or `"E010-E019"`: Error codes from `E010` to `E019`
+///   inclusive.
+/// * `"E011"`: The single error code `E011`. Leading zeros are optional.
+///
+/// [`SeverityPolicy`]: crate::model::SeverityPolicy
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CodeSelector {
+    /// Every error code.
+    All,
+    /// Every error code with the prefix.
+    Prefix(String),
+    /// Error codes with the prefix, whose value is within the inclusive range.
+    Range {
+        /// The error code prefix, e.g. `"E"`.
+        prefix: String,
+        /// The first error code value in the range.
+        start: usize,
+        /// The last error code value in the range.
+        end: usize,
+    },
+    /// A single error code.
+    Code {
+        /// The error code prefix, e.g. `"E"`.
+        prefix: String,
+        /// The error code value.
+        value: usize,
+    },
+}
+
+impl CodeSelector {
+    /// Returns whether the error code is selected.
+    pub fn matches<Ec>(&self, error_code: Ec) -> bool
+    where
+        Ec: ErrorCode,
+    {
+        match self {
+            Self::All => true,
+            Self::Prefix(prefix) => prefix.eq_ignore_ascii_case(Ec::PREFIX),
+            Self::Range { prefix, start, end } => {
+                prefix.eq_ignore_ascii_case(Ec::PREFIX)
+                    && (*start..=*end).contains(&error_code.code())
+            }
+            Self::Code { prefix, value } => {
+                prefix.eq_ignore_ascii_case(Ec::PREFIX) && *value == error_code.code()
+            }
+        }
+    }
+
+    /// Returns how specific this selector is, used to decide which rule wins
+    /// when several rules select the same error code.
+    pub(crate) fn specificity(&self) -> u8 {
+        match self {
+            Self::All => 0,
+            Self::Prefix(_) => 1,
+            Self::Range { .. } => 2,
+            Self::Code { .. } => 3,
+        }
+    }
+
+    /// Returns the selector for exactly the given error code.
+    pub fn code<Ec>(error_code: Ec) -> Self
+    where
+        Ec: ErrorCode,
+    {
+        Self::Code {
+            prefix: String::from(Ec::PREFIX),
+            value: error_code.code(),
+        }
+    }
+
+    /// Splits `"E011"` into its prefix and value.
+    ///
+    /// The value is `None` when there are no digits after the prefix.
+    fn prefix_and_value(code: &str) -> Option<(&str, Option<usize>)> {
+        let digits_start = code
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(code.len());
+        let (prefix, digits) = code.split_at(digits_start);
+        if digits.is_empty() {
+            Some((prefix, None))
+        } else if digits.bytes().all(|b| b.is_ascii_digit()) {
+            digits.parse().ok().map(|value| (prefix, Some(value)))
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for CodeSelector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::All => f.write_str("*"),
+            Self::Prefix(prefix) => f.write_str(prefix),
+            Self::Range { prefix, start, end } => write!(f, "{prefix}{start}..={prefix}{end}"),
+            Self::Code { prefix, value } => write!(f, "{prefix}{value}"),
+        }
+    }
+}
+
+impl FromStr for CodeSelector {
+    type Err = SeverityPolicyError;
+
+    fn from_str(selector: &str) -> Result<Self, Self::Err> {
+        let selector_trimmed = selector.trim();
+        let selector_invalid = || SeverityPolicyError::SelectorInvalid {
+            selector: String::from(selector),
+        };
+        if selector_trimmed == "*" {
+            return Ok(Self::All);
+        }
+
+        let range = selector_trimmed
+            .split_once("..=")
+            .or_else(|| selector_trimmed.split_once('-'));
+        match range {
+            Some((start, end)) => {
+                let (prefix, start) =
+                    Self::prefix_and_value(start.trim()).ok_or_else(selector_invalid)?;
+                let (prefix_end, end) =
+                    Self::prefix_and_value(end.trim()).ok_or_else(selector_invalid)?;
+                match (start, end) {
+                    (Some(start), Some(end))
+                        if !prefix.is_empty()
+                            && prefix.eq_ignore_ascii_case(prefix_end)
+                            && start <= end =>
+                    {
+                        Ok(Self::Range {
+                            prefix: String::from(prefix),
+                            start,
+                            end,
+                        })
+                    }
+                    _ => Err(selector_invalid()),
+                }
+            }
+            None => match Self::prefix_and_value(selector_trimmed).ok_or_else(selector_invalid)? {
+                ("", None) => Err(selector_invalid()),
+                (prefix, None) => Ok(Self::Prefix(String::from(prefix))),
+                (prefix, Some(value)) => Ok(Self::Code {
+                    prefix: String::from(prefix),
+                    value,
+                }),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CodeSelector;
+    use crate::{model::SeverityPolicyError, test_support::TestErrorCode};
+
+    #[test]
+    fn parses_all_prefix_range_and_code() -> Result<(), SeverityPolicyError> {
+        assert_eq!(CodeSelector::All, "*".parse()?);
+        assert_eq!(CodeSelector::Prefix(String::from("W")), "W".parse()?);
+        let range = CodeSelector::Range {
+            prefix: String::from("E"),
+            start: 10,
+            end: 19,
+        };
+        assert_eq!(range, "E010..=E019".parse()?);
+        assert_eq!(range, "E010-E019".parse()?);
+        assert_eq!(
+            CodeSelector::Code {
+                prefix: String::from("E"),
+                value: 11,
+            },
+            " E011 ".parse()?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_invalid_selectors() {
+        ["", "E1a", "E019..=E010", "E010..=W019"]
+            .iter()
+            .for_each(|selector| {
+                assert_eq!(
+                    Err(SeverityPolicyError::SelectorInvalid {
+                        selector: String::from(*selector),
+                    }),
+                    selector.parse::<CodeSelector>()
+                );
+            });
+    }
+
+    #[test]
+    fn matches_compares_prefix_ignoring_case() -> Result<(), SeverityPolicyError> {
+        assert!("e1"
+            .parse::<CodeSelector>()?
+            .matches(TestErrorCode::InvalidValue));
+        assert!("E0..=E5"
+            .parse::<CodeSelector>()?
+            .matches(TestErrorCode::InvalidValue));
+        assert!(!"W"
+            .parse::<CodeSelector>()?
+            .matches(TestErrorCode::InvalidValue));
+        assert!(!"E2"
+            .parse::<CodeSelector>()?
+            .matches(TestErrorCode::InvalidValue));
+        Ok(())
+    }
+}
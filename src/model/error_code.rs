@@ -1,3 +1,5 @@
+use codespan_reporting::diagnostic::Severity;
+
 /// Error codes of an application.
 ///
 /// This is intended to be implemented by a flat enum in an application that
@@ -6,7 +8,10 @@ pub trait ErrorCode: Copy + Eq {
     /// Returns the largest possible error code value.
     ///
     /// This is used to determine the number of leading `0`s when formatting the
-    /// error message.
+    /// error message. Every code must be at most this value, which can be
+    /// asserted at compile time with [`Code::fits`].
+    ///
+    /// [`Code::fits`]: crate::fmt::Code::fits
     const ERROR_CODE_MAX: usize;
 
     /// Returns the `&str` to prefix the error code -- the `"E"` in `"E001"`.
@@ -19,4 +24,38 @@ pub trait ErrorCode: Copy + Eq {
 
     /// Returns a short description of the error.
     fn description(self) -> &'static str;
+
+    /// Returns an extended explanation of the error in Markdown, if any.
+    ///
+    /// This is rendered by the `explain` module when the user asks for more
+    /// detail about an error code, similar to `rustc --explain`.
+    ///
+    /// Defaults to `None`.
+    fn explanation(self) -> Option<&'static str> {
+        None
+    }
+
+    /// Returns the severity that the error is reported with by default.
+    ///
+    /// This is shown in the error index generated by the `error_index` module.
+    ///
+    /// Defaults to [`Severity::Error`].
+    fn severity(self) -> Severity {
+        Severity::Error
+    }
+
+    /// Returns every error code, in ascending order of [`code`].
+    ///
+    /// This is used to look up an error code from its string form, and to list
+    /// all errors of an application.
+    ///
+    /// Defaults to an empty slice.
+    ///
+    /// [`code`]: Self::code
+    fn all() -> &'static [Self]
+    where
+        Self: 'static,
+    {
+        &[]
+    }
 }
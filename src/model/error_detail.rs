@@ -1,4 +1,6 @@
-use codespan_reporting::{diagnostic::Label, files::Files};
+use codespan_reporting::files::Files;
+
+use crate::model::{Label, Suggestion, Trailer};
 
 /// Detail of a specific error.
 ///
@@ -38,5 +40,39 @@ pub trait ErrorDetail<'files> {
     ///    |
     ///    = `selected` value must come from one of the `available` values.
     /// ```
-    fn notes(&self, files: &Self::Files) -> Vec<String>;
+    ///
+    /// Defaults to no notes. Implement [`trailers`](Self::trailers) instead to
+    /// distinguish help, warnings, and links from notes.
+    fn notes(&self, files: &Self::Files) -> Vec<String> {
+        let _ = files;
+        Vec::new()
+    }
+
+    /// Returns the [`Trailer`]s to display beneath the error snippets.
+    ///
+    /// Each kind of trailer is rendered with its own prefix:
+    ///
+    /// ```yaml
+    ///  8 | selected: "ghi"
+    ///    |           ^^^^^ this is not an available value.
+    ///    |
+    ///    = `selected` value must come from one of the `available` values.
+    ///    = help: use one of `"abc"`, `"def"`
+    /// ```
+    ///
+    /// Defaults to the [`notes`](Self::notes), as [`Trailer::Note`]s.
+    fn trailers(&self, files: &Self::Files) -> Vec<Trailer> {
+        self.notes(files).into_iter().map(Trailer::Note).collect()
+    }
+
+    /// Returns [`Suggestion`]s to fix the error.
+    ///
+    /// Each suggestion is rendered as a `help:` note with a diff of the
+    /// affected lines, and exported with its applicability so that tooling can
+    /// apply it.
+    ///
+    /// Defaults to no suggestions.
+    fn suggestions(&self) -> Vec<Suggestion<<Self::Files as Files<'files>>::FileId>> {
+        Vec::new()
+    }
 }
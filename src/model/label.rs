@@ -0,0 +1,125 @@
+use std::ops::Range;
+
+use codespan_reporting::diagnostic;
+
+use crate::model::{LabelStyle, Span};
+
+/// Part of a file to highlight when rendering an error.
+///
+/// This is independent of the rendering backend, so [`ErrorDetail`]
+/// implementations do not depend on a particular `codespan-reporting` version.
+/// Labels convert into [`codespan_reporting::diagnostic::Label`]s with
+/// [`From`].
+///
+/// [`ErrorDetail`]: crate::ErrorDetail
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Label<FileId> {
+    /// Whether the label describes the cause of the error, or provides context.
+    pub style: LabelStyle,
+    /// Part of the file to highlight.
+    pub span: Span<FileId>,
+    /// Message to render with the highlighted part, may be empty.
+    pub message: String,
+}
+
+impl<FileId> Label<FileId> {
+    /// Returns a new `Label` without a message.
+    ///
+    /// # Parameters
+    ///
+    /// * `style`: Whether the label describes the cause of the error, or
+    ///   provides context.
+    /// * `span`: Part of the file to highlight.
+    pub fn new(style: LabelStyle, span: Span<FileId>) -> Self {
+        Self {
+            style,
+            span,
+            message: String::new(),
+        }
+    }
+
+    /// Returns a new primary `Label`, describing the cause of the error.
+    ///
+    /// # Parameters
+    ///
+    /// * `file_id`: ID of the file to highlight.
+    /// * `range`: Byte begin and end indices to highlight.
+    pub fn primary(file_id: FileId, range: impl Into<Range<usize>>) -> Self {
+        Self::new(LabelStyle::Primary, Span::new(file_id, range))
+    }
+
+    /// Returns a new secondary `Label`, providing context for the error.
+    ///
+    /// # Parameters
+    ///
+    /// * `file_id`: ID of the file to highlight.
+    /// * `range`: Byte begin and end indices to highlight.
+    pub fn secondary(file_id: FileId, range: impl Into<Range<usize>>) -> Self {
+        Self::new(LabelStyle::Secondary, Span::new(file_id, range))
+    }
+
+    /// Sets the message to render with the highlighted part.
+    #[must_use]
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = message.into();
+        self
+    }
+}
+
+impl<FileId> From<Label<FileId>> for diagnostic::Label<FileId> {
+    fn from(label: Label<FileId>) -> Self {
+        let Label {
+            style,
+            span: Span { file_id, range },
+            message,
+        } = label;
+
+        diagnostic::Label::new(style.into(), file_id, range).with_message(message)
+    }
+}
+
+impl<FileId> From<diagnostic::Label<FileId>> for Label<FileId> {
+    fn from(label: diagnostic::Label<FileId>) -> Self {
+        let diagnostic::Label {
+            style,
+            file_id,
+            range,
+            message,
+        } = label;
+
+        Label {
+            style: style.into(),
+            span: Span { file_id, range },
+            message,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use codespan_reporting::diagnostic;
+
+    use super::Label;
+    use crate::model::{LabelStyle, Span};
+
+    #[test]
+    fn converts_to_and_from_codespan_reporting_label() {
+        let label = Label::secondary(1, 4..34).with_message("defined here");
+        let label_codespan = diagnostic::Label::from(label.clone());
+
+        assert_eq!(
+            diagnostic::Label::secondary(1, 4..34).with_message("defined here"),
+            label_codespan
+        );
+        assert_eq!(label, Label::from(label_codespan));
+    }
+
+    #[test]
+    fn primary_is_new_with_primary_style() {
+        assert_eq!(
+            Label::new(LabelStyle::Primary, Span::new(0, 9..13)),
+            Label::primary(0, 9..13)
+        );
+    }
+}
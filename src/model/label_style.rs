@@ -0,0 +1,32 @@
+use codespan_reporting::diagnostic;
+
+/// Whether a [`Label`] describes the cause of an error, or provides context.
+///
+/// [`Label`]: crate::model::Label
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum LabelStyle {
+    /// Label describing the primary cause of the error.
+    Primary,
+    /// Label providing additional context for the error.
+    Secondary,
+}
+
+impl From<LabelStyle> for diagnostic::LabelStyle {
+    fn from(label_style: LabelStyle) -> Self {
+        match label_style {
+            LabelStyle::Primary => Self::Primary,
+            LabelStyle::Secondary => Self::Secondary,
+        }
+    }
+}
+
+impl From<diagnostic::LabelStyle> for LabelStyle {
+    fn from(label_style: diagnostic::LabelStyle) -> Self {
+        match label_style {
+            diagnostic::LabelStyle::Primary => Self::Primary,
+            diagnostic::LabelStyle::Secondary => Self::Secondary,
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use codespan_reporting::files::{self, Files};
+
+/// 1-based line and column of a position within a file.
+///
+/// Columns count Unicode scalar values, consistent with the columns rendered by
+/// `codespan-reporting`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct LineColumn {
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column number.
+    pub column: usize,
+}
+
+impl LineColumn {
+    /// Returns the line and column of the byte index within the file.
+    ///
+    /// # Parameters
+    ///
+    /// * `files`: Files containing the file.
+    /// * `file_id`: ID of the file.
+    /// * `byte_index`: Byte index within the file's source.
+    pub fn new<'files, Fs>(
+        files: &'files Fs,
+        file_id: Fs::FileId,
+        byte_index: usize,
+    ) -> Result<Self, files::Error>
+    where
+        Fs: Files<'files>,
+    {
+        let location = files.location(file_id, byte_index)?;
+
+        Ok(Self {
+            line: location.line_number,
+            column: location.column_number,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use codespan_reporting::files::SimpleFiles;
+
+    use super::LineColumn;
+
+    #[test]
+    fn new_returns_one_based_line_and_column() -> Result<(), Box<dyn std::error::Error>> {
+        let mut files = SimpleFiles::new();
+        let file_id = files.add("file", "a: 1\nbc: \"é\"\n");
+
+        assert_eq!(
+            LineColumn { line: 1, column: 1 },
+            LineColumn::new(&files, file_id, 0)?
+        );
+        assert_eq!(
+            LineColumn { line: 2, column: 5 },
+            LineColumn::new(&files, file_id, 9)?
+        );
+        // `é` is 2 bytes, but counts as one column.
+        assert_eq!(
+            LineColumn { line: 2, column: 7 },
+            LineColumn::new(&files, file_id, 12)?
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use std::{fmt, str::FromStr};
+
+use codespan_reporting::diagnostic::Severity;
+
+use crate::model::SeverityPolicyError;
+
+/// Level that an error code is reported at, similar to `#[allow]` and `#[deny]`
+/// for lints.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum LintLevel {
+    /// The error is dropped.
+    Allow,
+    /// The error is reported as a [`Severity::Warning`].
+    Warn,
+    /// The error is reported as a [`Severity::Error`].
+    Deny,
+    /// The error is reported as a [`Severity::Error`], and cannot be lowered by
+    /// other rules.
+    Forbid,
+}
+
+impl LintLevel {
+    /// Returns the severity to report errors at, or `None` if they are dropped.
+    pub fn severity(self) -> Option<Severity> {
+        match self {
+            Self::Allow => None,
+            Self::Warn => Some(Severity::Warning),
+            Self::Deny | Self::Forbid => Some(Severity::Error),
+        }
+    }
+
+    /// Returns the lower case name of the level.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Allow => "allow",
+            Self::Warn => "warn",
+            Self::Deny => "deny",
+            Self::Forbid => "forbid",
+        }
+    }
+}
+
+impl fmt::Display for LintLevel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for LintLevel {
+    type Err = SeverityPolicyError;
+
+    fn from_str(level: &str) -> Result<Self, Self::Err> {
+        [Self::Allow, Self::Warn, Self::Deny, Self::Forbid]
+            .iter()
+            .copied()
+            .find(|lint_level| lint_level.as_str().eq_ignore_ascii_case(level.trim()))
+            .ok_or_else(|| SeverityPolicyError::LevelInvalid {
+                level: String::from(level),
+            })
+    }
+}
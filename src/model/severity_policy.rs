@@ -0,0 +1,338 @@
+use codespan_reporting::{diagnostic::Severity, files::Files};
+
+use crate::model::{
+    CodeSelector, ErrorCode, ErrorDetail, LintLevel, SeverityPolicyError, SourceError,
+};
+
+/// Overrides the severity of errors by their code, similar to `#[allow]` and
+/// `#[deny]` for lints.
+///
+/// Each rule maps a [`CodeSelector`] to a [`LintLevel`]. When several rules
+/// select an error code, the most specific selector wins -- a single code over
+/// a range, over a prefix, over `*` -- and later rules win over earlier rules
+/// with the same specificity. A `forbid` rule cannot be overridden.
+///
+/// Errors that no rule selects keep the severity they were created with.
+///
+/// With the `"serde"` feature, a policy can be deserialized from a map of
+/// selectors to levels, such as a section in a TOML or YAML configuration file:
+///
+/// ```toml
+/// [lints]
+/// "*" = "warn"
+/// "E010..=E019" = "deny"
+/// E011 = "allow"
+/// ```
+///
+/// # Examples
+///
+/// ```rust
+/// use srcerr::model::{LintLevel, SeverityPolicy};
+///
+/// let severity_policy = SeverityPolicy::new()
+///     .with_level("W".parse()?, LintLevel::Deny)
+///     .with_args(["--allow", "E011,E012", "-DE013"])?;
+/// # Ok::<(), srcerr::model::SeverityPolicyError>(())
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SeverityPolicy {
+    /// Rules in the order they were added.
+    rules: Vec<(CodeSelector, LintLevel)>,
+}
+
+impl SeverityPolicy {
+    /// Returns a new `SeverityPolicy` without any rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a rule setting the level of the selected error codes.
+    #[must_use]
+    pub fn with_level(mut self, code_selector: CodeSelector, lint_level: LintLevel) -> Self {
+        self.push(code_selector, lint_level);
+        self
+    }
+
+    /// Adds rules from command line arguments.
+    ///
+    /// Each flag is followed by a comma separated list of selectors, either as
+    /// the next argument, after `=`, or directly after a short flag:
+    ///
+    /// * `--allow E011`, `--allow=E011`, `-A E011`, `-AE011`
+    /// * `--warn`, `-W`
+    /// * `--deny`, `-D`
+    /// * `--forbid`, `-F`
+    ///
+    /// # Parameters
+    ///
+    /// * `args`: The lint level arguments, e.g. `["--deny", "W,E011"]`.
+    pub fn with_args<I, S>(mut self, args: I) -> Result<Self, SeverityPolicyError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let arg = arg.as_ref();
+            let (flag, value) = if arg.starts_with("--") {
+                match arg.split_once('=') {
+                    Some((flag, value)) => (flag, Some(String::from(value))),
+                    None => (arg, None),
+                }
+            } else if arg.starts_with('-') && arg.len() > 2 && arg.is_char_boundary(2) {
+                (&arg[..2], Some(String::from(&arg[2..])))
+            } else {
+                (arg, None)
+            };
+
+            let lint_level = match flag {
+                "--allow" | "-A" => LintLevel::Allow,
+                "--warn" | "-W" => LintLevel::Warn,
+                "--deny" | "-D" => LintLevel::Deny,
+                "--forbid" | "-F" => LintLevel::Forbid,
+                _ => {
+                    return Err(SeverityPolicyError::ArgUnknown {
+                        arg: String::from(arg),
+                    });
+                }
+            };
+            let value = value
+                .or_else(|| args.next().map(|value| String::from(value.as_ref())))
+                .ok_or_else(|| SeverityPolicyError::ArgValueMissing {
+                    arg: String::from(arg),
+                })?;
+
+            value.split(',').try_for_each(|selector| {
+                selector
+                    .parse()
+                    .map(|code_selector| self.push(code_selector, lint_level))
+            })?;
+        }
+
+        Ok(self)
+    }
+
+    /// Adds a rule setting the level of the selected error codes.
+    pub fn push(&mut self, code_selector: CodeSelector, lint_level: LintLevel) {
+        self.rules.push((code_selector, lint_level));
+    }
+
+    /// Returns the level of the error code, or `None` if no rule selects it.
+    pub fn level<Ec>(&self, error_code: Ec) -> Option<LintLevel>
+    where
+        Ec: ErrorCode,
+    {
+        let rules_matching = self
+            .rules
+            .iter()
+            .filter(|(code_selector, _)| code_selector.matches(error_code))
+            .collect::<Vec<_>>();
+        if rules_matching
+            .iter()
+            .any(|(_, lint_level)| *lint_level == LintLevel::Forbid)
+        {
+            return Some(LintLevel::Forbid);
+        }
+
+        rules_matching
+            .into_iter()
+            .max_by_key(|(code_selector, _)| code_selector.specificity())
+            .map(|(_, lint_level)| *lint_level)
+    }
+
+    /// Returns the severity to report the error code at, or `None` if it is
+    /// allowed.
+    ///
+    /// # Parameters
+    ///
+    /// * `error_code`: The error code.
+    /// * `severity`: Severity to use when no rule selects the error code.
+    pub fn severity<Ec>(&self, error_code: Ec, severity: Severity) -> Option<Severity>
+    where
+        Ec: ErrorCode,
+    {
+        match self.level(error_code) {
+            Some(lint_level) => lint_level.severity(),
+            None => Some(severity),
+        }
+    }
+
+    /// Returns the error with its severity overridden, or `None` if it is
+    /// allowed.
+    pub fn apply<'files, Ec, Ed, Fs>(
+        &self,
+        mut source_error: SourceError<'files, Ec, Ed, Fs>,
+    ) -> Option<SourceError<'files, Ec, Ed, Fs>>
+    where
+        Ec: ErrorCode,
+        Ed: ErrorDetail<'files, Files = Fs>,
+        Fs: Files<'files>,
+    {
+        let severity = self.severity(source_error.code, source_error.severity)?;
+        source_error.severity = severity;
+        Some(source_error)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SeverityPolicy {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use std::fmt;
+
+        use serde::de::{self, MapAccess, Visitor};
+
+        struct SeverityPolicyVisitor;
+
+        impl<'de> Visitor<'de> for SeverityPolicyVisitor {
+            type Value = SeverityPolicy;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a map of error code selectors to lint levels")
+            }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>,
+            {
+                let mut severity_policy = SeverityPolicy::new();
+                while let Some((selector, lint_level)) = map.next_entry::<String, LintLevel>()? {
+                    let code_selector = selector.parse().map_err(de::Error::custom)?;
+                    severity_policy.push(code_selector, lint_level);
+                }
+
+                Ok(severity_policy)
+            }
+        }
+
+        deserializer.deserialize_map(SeverityPolicyVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use codespan_reporting::diagnostic::Severity;
+
+    use super::SeverityPolicy;
+    use crate::{
+        model::{LintLevel, SeverityPolicyError},
+        test_support::{self, TestErrorCode},
+    };
+
+    #[test]
+    fn level_prefers_most_specific_then_latest_rule() -> Result<(), SeverityPolicyError> {
+        let severity_policy = SeverityPolicy::new()
+            .with_level("E1".parse()?, LintLevel::Allow)
+            .with_level("E".parse()?, LintLevel::Deny)
+            .with_level("*".parse()?, LintLevel::Warn);
+        assert_eq!(
+            Some(LintLevel::Allow),
+            severity_policy.level(TestErrorCode::InvalidValue)
+        );
+
+        let severity_policy = severity_policy.with_level("e01".parse()?, LintLevel::Warn);
+        assert_eq!(
+            Some(LintLevel::Warn),
+            severity_policy.level(TestErrorCode::InvalidValue)
+        );
+        assert_eq!(
+            None,
+            SeverityPolicy::new().level(TestErrorCode::InvalidValue)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn level_forbid_cannot_be_overridden() -> Result<(), SeverityPolicyError> {
+        let severity_policy = SeverityPolicy::new()
+            .with_level("E".parse()?, LintLevel::Forbid)
+            .with_level("E1".parse()?, LintLevel::Allow);
+
+        assert_eq!(
+            Some(LintLevel::Forbid),
+            severity_policy.level(TestErrorCode::InvalidValue)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn with_args_parses_long_short_and_attached_values() -> Result<(), SeverityPolicyError> {
+        let severity_policy = SeverityPolicy::new().with_args([
+            "--allow",
+            "W1,E2",
+            "--warn=E3",
+            "-D",
+            "E4",
+            "-FE5",
+        ])?;
+
+        let expected = SeverityPolicy::new()
+            .with_level("W1".parse()?, LintLevel::Allow)
+            .with_level("E2".parse()?, LintLevel::Allow)
+            .with_level("E3".parse()?, LintLevel::Warn)
+            .with_level("E4".parse()?, LintLevel::Deny)
+            .with_level("E5".parse()?, LintLevel::Forbid);
+        assert_eq!(expected, severity_policy);
+        Ok(())
+    }
+
+    #[test]
+    fn with_args_rejects_unknown_flag_and_missing_value() {
+        assert_eq!(
+            Err(SeverityPolicyError::ArgUnknown {
+                arg: String::from("--quiet"),
+            }),
+            SeverityPolicy::new().with_args(["--quiet"])
+        );
+        assert_eq!(
+            Err(SeverityPolicyError::ArgValueMissing {
+                arg: String::from("--deny"),
+            }),
+            SeverityPolicy::new().with_args(["--deny"])
+        );
+    }
+
+    #[test]
+    fn apply_drops_allowed_and_overrides_severity() -> Result<(), SeverityPolicyError> {
+        let (_files, file_id) = test_support::files();
+        let source_error = test_support::source_error_invalid_value(file_id, Severity::Warning);
+
+        let severity_policy = SeverityPolicy::new().with_level("E1".parse()?, LintLevel::Deny);
+        let source_error_applied = severity_policy
+            .apply(source_error.clone())
+            .expect("Expected denied error to be kept.");
+        assert_eq!(Severity::Error, source_error_applied.severity);
+
+        let severity_policy = SeverityPolicy::new().with_level("E".parse()?, LintLevel::Allow);
+        assert!(severity_policy.apply(source_error.clone()).is_none());
+
+        let source_error_applied = SeverityPolicy::new()
+            .apply(source_error)
+            .expect("Expected error without rules to be kept.");
+        assert_eq!(Severity::Warning, source_error_applied.severity);
+        Ok(())
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn deserializes_from_map_of_selectors_to_levels() -> Result<(), Box<dyn std::error::Error>> {
+        let severity_policy = serde_json::from_str::<SeverityPolicy>(
+            r#"{ "*": "warn", "E010..=E019": "deny", "E011": "allow" }"#,
+        )?;
+
+        let expected = SeverityPolicy::new()
+            .with_level("*".parse()?, LintLevel::Warn)
+            .with_level("E010..=E019".parse()?, LintLevel::Deny)
+            .with_level("E011".parse()?, LintLevel::Allow);
+        assert_eq!(expected, severity_policy);
+
+        let error = serde_json::from_str::<SeverityPolicy>(r#"{ "E01x": "deny" }"#)
+            .expect_err("Expected invalid selector to be rejected.");
+        assert!(error
+            .to_string()
+            .contains("`E01x` is not a valid error code selector"));
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+use std::fmt;
+
+/// Error when building a [`SeverityPolicy`].
+///
+/// [`SeverityPolicy`]: crate::model::SeverityPolicy
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SeverityPolicyError {
+    /// The code selector could not be parsed.
+    SelectorInvalid {
+        /// The selector that was parsed, e.g. `"E01x"`.
+        selector: String,
+    },
+    /// The lint level is not one of `allow`, `warn`, `deny`, or `forbid`.
+    LevelInvalid {
+        /// The level that was parsed.
+        level: String,
+    },
+    /// The command line argument is not a lint level flag.
+    ArgUnknown {
+        /// The command line argument.
+        arg: String,
+    },
+    /// The lint level flag is not followed by a code selector.
+    ArgValueMissing {
+        /// The command line argument.
+        arg: String,
+    },
+}
+
+impl fmt::Display for SeverityPolicyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::SelectorInvalid { selector } => write!(
+                f,
+                "`{selector}` is not a valid error code selector, expected `*`, `E`, `E010..=E019`, or `E011`."
+            ),
+            Self::LevelInvalid { level } => write!(
+                f,
+                "`{level}` is not a valid lint level, expected `allow`, `warn`, `deny`, or `forbid`."
+            ),
+            Self::ArgUnknown { arg } => write!(
+                f,
+                "`{arg}` is not a lint level flag, expected `--allow`, `--warn`, `--deny`, or `--forbid`."
+            ),
+            Self::ArgValueMissing { arg } => {
+                write!(f, "`{arg}` must be followed by an error code selector.")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SeverityPolicyError {}
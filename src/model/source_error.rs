@@ -7,9 +7,14 @@ use codespan_reporting::{
 
 use crate::{
     fmt::Code,
-    model::{ErrorCode, ErrorDetail},
+    model::{ErrorCode, ErrorDetail, Trailer},
 };
 
+#[cfg(feature = "json")]
+use crate::json::{JsonDiagnostic, JsonError, JsonReport};
+#[cfg(feature = "miette")]
+use crate::miette_interop::MietteAdapter;
+
 /// Information about an error from source data.
 ///
 /// # Type Parameters
@@ -47,7 +52,11 @@ where
     }
 
     /// Returns a `Diagnostic` built from this error.
-    pub fn as_diagnostic(&self, files: &Fs) -> Diagnostic<Fs::FileId> {
+    ///
+    /// Suggestions are rendered as `help:` notes after the trailers.
+    /// Suggestions whose span is outside their file are skipped, as their diff
+    /// cannot be rendered.
+    pub fn as_diagnostic(&self, files: &'files Fs) -> Diagnostic<Fs::FileId> {
         let SourceError {
             code,
             detail,
@@ -58,8 +67,47 @@ where
         Diagnostic::new(*severity)
             .with_code(code_str)
             .with_message(code.description())
-            .with_labels(detail.labels())
-            .with_notes(detail.notes(files))
+            .with_labels(detail.labels().into_iter().map(Into::into).collect())
+            .with_notes(
+                detail
+                    .trailers(files)
+                    .into_iter()
+                    .chain(
+                        detail.suggestions().iter().filter_map(|suggestion| {
+                            suggestion.help(files).ok().map(Trailer::Help)
+                        }),
+                    )
+                    .map(|trailer| trailer.to_string())
+                    .collect(),
+            )
+    }
+
+    /// Returns this error serialized as a JSON [`JsonReport`].
+    ///
+    /// See the [`json`] module for the schema.
+    ///
+    /// [`json`]: crate::json
+    /// [`JsonReport`]: crate::json::JsonReport
+    #[cfg(feature = "json")]
+    pub fn to_json(&self, files: &'files Fs) -> Result<String, JsonError> {
+        let json_diagnostic = JsonDiagnostic::new(files, self)?;
+        let json_report = JsonReport::new(vec![json_diagnostic]);
+
+        serde_json::to_string(&json_report).map_err(JsonError::from)
+    }
+
+    /// Returns this error bound to its files, implementing
+    /// `miette::Diagnostic`.
+    ///
+    /// See [`MietteAdapter`] for how the error is mapped.
+    ///
+    /// [`MietteAdapter`]: crate::miette_interop::MietteAdapter
+    #[cfg(feature = "miette")]
+    pub fn to_miette(
+        &self,
+        files: &'files Fs,
+    ) -> Result<MietteAdapter, codespan_reporting::files::Error> {
+        MietteAdapter::new(files, self)
     }
 }
 
@@ -90,12 +138,15 @@ mod tests {
     use std::{fmt, ops::Range};
 
     use codespan_reporting::{
-        diagnostic::{Diagnostic, Label, Severity},
+        diagnostic::{self, Diagnostic, Severity},
         files::SimpleFiles,
     };
 
     use super::SourceError;
-    use crate::{ErrorCode, ErrorDetail};
+    use crate::{
+        model::{Applicability, Span, Suggestion},
+        test_support, ErrorCode, ErrorDetail, Label,
+    };
 
     #[test]
     fn as_diagnostic_passes_through_all_members() {
@@ -110,7 +161,7 @@ mod tests {
                 code: Some(String::from("E01")),
                 message: String::from("`chosen` value is invalid."),
                 labels: vec![
-                    Label::primary(source_error.detail.file_id, 9..13)
+                    diagnostic::Label::primary(source_error.detail.file_id, 9..13)
                         .with_message("label_message")
                 ],
                 notes: vec![String::from("note_message")]
@@ -119,6 +170,55 @@ mod tests {
         );
     }
 
+    #[test]
+    fn as_diagnostic_skips_suggestions_outside_their_file() {
+        let (files, file_id) = test_support::files();
+        let mut source_error = test_support::source_error_invalid_value(file_id, Severity::Error);
+        source_error.detail.suggestions = vec![
+            Suggestion::new(
+                Span::new(file_id, 400..405),
+                "\"abc\"",
+                Applicability::MachineApplicable,
+            ),
+            Suggestion::new(
+                Span::new(file_id, 44..49),
+                "\"abc\"",
+                Applicability::MachineApplicable,
+            ),
+        ];
+
+        let diagnostic = source_error.as_diagnostic(&files);
+
+        assert_eq!(
+            vec![
+                String::from("`chosen` value must come from one of `available` values"),
+                String::from("help: try\n6 - chosen: \"ghi\"\n6 + chosen: \"abc\""),
+            ],
+            diagnostic.notes
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn to_json_serializes_report_with_version() -> Result<(), Box<dyn std::error::Error>> {
+        let mut files = SimpleFiles::new();
+        let source_error = source_error(&mut files, None);
+
+        let json = source_error.to_json(&files)?;
+
+        assert_eq!(
+            concat!(
+                r#"{"version":1,"diagnostics":[{"code":"E01","code_number":1,"prefix":"E","#,
+                r#""severity":"error","message":"`chosen` value is invalid.","labels":[{"#,
+                r#""style":"primary","file":"path/to/file","range":{"start":9,"end":13},"#,
+                r#""start":{"line":2,"column":6},"end":{"line":2,"column":10},"#,
+                r#""message":"label_message"}],"notes":["note_message"]}]}"#,
+            ),
+            json
+        );
+        Ok(())
+    }
+
     #[test]
     fn delegates_error_source_to_error_detail() {
         let mut files = SimpleFiles::new();
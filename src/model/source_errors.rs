@@ -0,0 +1,495 @@
+use std::iter::FromIterator;
+
+use codespan_reporting::{
+    diagnostic::Severity,
+    files::{self, Files},
+    term::{self, termcolor::WriteColor},
+};
+
+use crate::model::{ErrorCode, ErrorDetail, Label, LabelStyle, SeverityPolicy, SourceError};
+
+#[cfg(feature = "json")]
+use crate::json::{JsonDiagnostic, JsonError, JsonReport};
+
+/// Collection of [`SourceError`]s, accumulated while validating source data.
+///
+/// # Type Parameters
+///
+/// * `Ec`: [`ErrorCode`][crate::ErrorCode] type.
+/// * `Ed`: [`ErrorDetail`][crate::ErrorDetail] type.
+/// * `Fs`: [`Files`] referenced by the errors.
+///
+/// [`Files`]: codespan_reporting::files::Files
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SourceErrors<'files, Ec, Ed, Fs> {
+    /// The accumulated errors.
+    source_errors: Vec<SourceError<'files, Ec, Ed, Fs>>,
+}
+
+impl<'files, Ec, Ed, Fs> SourceErrors<'files, Ec, Ed, Fs>
+where
+    Ec: ErrorCode,
+    Ed: ErrorDetail<'files, Files = Fs>,
+    Fs: Files<'files>,
+{
+    /// Returns a new, empty `SourceErrors`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a `SourceError` to this collection.
+    pub fn push(&mut self, source_error: SourceError<'files, Ec, Ed, Fs>) {
+        self.source_errors.push(source_error);
+    }
+
+    /// Returns the number of errors in this collection.
+    pub fn len(&self) -> usize {
+        self.source_errors.len()
+    }
+
+    /// Returns whether this collection contains no errors.
+    pub fn is_empty(&self) -> bool {
+        self.source_errors.is_empty()
+    }
+
+    /// Returns an iterator over the errors.
+    pub fn iter(&self) -> std::slice::Iter<'_, SourceError<'files, Ec, Ed, Fs>> {
+        self.source_errors.iter()
+    }
+
+    /// Returns the number of errors with the given severity.
+    pub fn count(&self, severity: Severity) -> usize {
+        self.source_errors
+            .iter()
+            .filter(|source_error| source_error.severity == severity)
+            .count()
+    }
+
+    /// Returns whether any error has a severity of [`Severity::Error`] or
+    /// higher.
+    pub fn has_errors(&self) -> bool {
+        self.source_errors
+            .iter()
+            .any(|source_error| source_error.severity >= Severity::Error)
+    }
+
+    /// Sorts the errors by the file and byte offset of their primary label.
+    ///
+    /// Errors without a primary label are placed last. The sort is stable, so
+    /// errors at the same location retain their insertion order.
+    pub fn sort(&mut self)
+    where
+        Fs::FileId: Ord,
+    {
+        self.source_errors.sort_by_cached_key(|source_error| {
+            let location = source_error
+                .detail
+                .labels()
+                .into_iter()
+                .find(|label| label.style == LabelStyle::Primary)
+                .map(|label| (label.span.file_id, label.span.range.start));
+
+            (location.is_none(), location)
+        });
+    }
+
+    /// Removes errors whose code and labels are identical to an earlier error.
+    pub fn dedup(&mut self) {
+        let mut seen = Vec::<(Ec, Vec<Label<Fs::FileId>>)>::new();
+        self.source_errors.retain(|source_error| {
+            let key = (source_error.code, source_error.detail.labels());
+            if seen.contains(&key) {
+                false
+            } else {
+                seen.push(key);
+                true
+            }
+        });
+    }
+
+    /// Applies the severity policy to every error.
+    ///
+    /// Errors that the policy allows are removed, and the severity of the
+    /// remaining errors is overridden by the policy's rules.
+    pub fn apply_policy(&mut self, severity_policy: &SeverityPolicy) {
+        self.source_errors = std::mem::take(&mut self.source_errors)
+            .into_iter()
+            .filter_map(|source_error| severity_policy.apply(source_error))
+            .collect();
+    }
+
+    /// Renders every error followed by a summary line, e.g. `"3 errors, 1
+    /// warning emitted"`.
+    ///
+    /// Nothing is written when this collection is empty.
+    ///
+    /// # Parameters
+    ///
+    /// * `writer`: Where to write the rendered errors.
+    /// * `config`: Configuration for how to render the errors.
+    /// * `files`: Files that the errors refer to.
+    pub fn emit_all(
+        &self,
+        writer: &mut dyn WriteColor,
+        config: &term::Config,
+        files: &'files Fs,
+    ) -> Result<(), files::Error> {
+        self.source_errors.iter().try_for_each(|source_error| {
+            term::emit(writer, config, files, &source_error.as_diagnostic(files))
+        })?;
+
+        if let Some(summary) = self.summary() {
+            writeln!(writer, "{summary}")?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns these errors serialized as a JSON [`JsonReport`].
+    ///
+    /// See the [`json`] module for the schema.
+    ///
+    /// [`json`]: crate::json
+    /// [`JsonReport`]: crate::json::JsonReport
+    #[cfg(feature = "json")]
+    pub fn to_json(&self, files: &'files Fs) -> Result<String, JsonError> {
+        let json_diagnostics = self
+            .source_errors
+            .iter()
+            .map(|source_error| JsonDiagnostic::new(files, source_error))
+            .collect::<Result<Vec<_>, _>>()?;
+        let json_report = JsonReport::new(json_diagnostics);
+
+        serde_json::to_string(&json_report).map_err(JsonError::from)
+    }
+
+    /// Returns the summary line, e.g. `"3 errors, 1 warning emitted"`.
+    ///
+    /// Returns `None` when this collection is empty.
+    pub fn summary(&self) -> Option<String> {
+        let counts = [
+            (
+                self.count(Severity::Bug) + self.count(Severity::Error),
+                "error",
+            ),
+            (self.count(Severity::Warning), "warning"),
+            (self.count(Severity::Note), "note"),
+            (self.count(Severity::Help), "help message"),
+        ];
+
+        let summary = counts
+            .iter()
+            .filter(|(count, _)| *count > 0)
+            .map(|(count, noun)| {
+                let plural = if *count == 1 { "" } else { "s" };
+                format!("{count} {noun}{plural}")
+            })
+            .collect::<Vec<_>>();
+
+        if summary.is_empty() {
+            None
+        } else {
+            Some(format!("{} emitted", summary.join(", ")))
+        }
+    }
+}
+
+impl<'files, Ec, Ed, Fs> Default for SourceErrors<'files, Ec, Ed, Fs> {
+    fn default() -> Self {
+        Self {
+            source_errors: Vec::new(),
+        }
+    }
+}
+
+impl<'files, Ec, Ed, Fs> Extend<SourceError<'files, Ec, Ed, Fs>>
+    for SourceErrors<'files, Ec, Ed, Fs>
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = SourceError<'files, Ec, Ed, Fs>>,
+    {
+        self.source_errors.extend(iter);
+    }
+}
+
+impl<'files, Ec, Ed, Fs> FromIterator<SourceError<'files, Ec, Ed, Fs>>
+    for SourceErrors<'files, Ec, Ed, Fs>
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = SourceError<'files, Ec, Ed, Fs>>,
+    {
+        Self {
+            source_errors: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<'files, Ec, Ed, Fs> IntoIterator for SourceErrors<'files, Ec, Ed, Fs> {
+    type IntoIter = std::vec::IntoIter<SourceError<'files, Ec, Ed, Fs>>;
+    type Item = SourceError<'files, Ec, Ed, Fs>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.source_errors.into_iter()
+    }
+}
+
+impl<'a, 'files, Ec, Ed, Fs> IntoIterator for &'a SourceErrors<'files, Ec, Ed, Fs> {
+    type IntoIter = std::slice::Iter<'a, SourceError<'files, Ec, Ed, Fs>>;
+    type Item = &'a SourceError<'files, Ec, Ed, Fs>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.source_errors.iter()
+    }
+}
+
+impl<'files, Ec, Ed, Fs> From<Vec<SourceError<'files, Ec, Ed, Fs>>>
+    for SourceErrors<'files, Ec, Ed, Fs>
+{
+    fn from(source_errors: Vec<SourceError<'files, Ec, Ed, Fs>>) -> Self {
+        Self { source_errors }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Range;
+
+    use codespan_reporting::{
+        diagnostic::Severity,
+        files::SimpleFiles,
+        term::{self, termcolor::NoColor},
+    };
+
+    use super::SourceErrors;
+    use crate::{
+        model::{CodeSelector, LintLevel, SeverityPolicy},
+        ErrorCode, ErrorDetail, Label, SourceError,
+    };
+
+    type TestSourceError = SourceError<
+        'static,
+        TestErrorCode,
+        TestErrorDetail,
+        SimpleFiles<&'static str, &'static str>,
+    >;
+
+    #[test]
+    fn counts_errors_by_severity() {
+        let source_errors = vec![
+            source_error(TestErrorCode::One, 0, 1..2, Severity::Error),
+            source_error(TestErrorCode::One, 0, 3..4, Severity::Bug),
+            source_error(TestErrorCode::Two, 0, 5..6, Severity::Warning),
+        ]
+        .into_iter()
+        .collect::<SourceErrors<'_, _, _, _>>();
+
+        assert_eq!(3, source_errors.len());
+        assert_eq!(1, source_errors.count(Severity::Error));
+        assert_eq!(1, source_errors.count(Severity::Bug));
+        assert_eq!(1, source_errors.count(Severity::Warning));
+        assert_eq!(0, source_errors.count(Severity::Note));
+        assert!(source_errors.has_errors());
+    }
+
+    #[test]
+    fn has_errors_is_false_when_only_warnings() {
+        let mut source_errors = SourceErrors::new();
+        assert!(!source_errors.has_errors());
+
+        source_errors.push(source_error(TestErrorCode::One, 0, 1..2, Severity::Warning));
+        source_errors.extend(std::iter::once(source_error(
+            TestErrorCode::Two,
+            0,
+            3..4,
+            Severity::Note,
+        )));
+
+        assert!(!source_errors.has_errors());
+    }
+
+    #[test]
+    fn sort_orders_by_file_then_byte_offset() {
+        let mut source_errors = SourceErrors::from(vec![
+            source_error(TestErrorCode::One, 1, 3..4, Severity::Error),
+            source_error(TestErrorCode::One, 0, 7..8, Severity::Error),
+            source_error(TestErrorCode::Two, 0, 1..2, Severity::Error),
+        ]);
+
+        source_errors.sort();
+
+        let locations = source_errors
+            .iter()
+            .map(|source_error| (source_error.detail.file_id, source_error.detail.range.start))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![(0, 1), (0, 7), (1, 3)], locations);
+    }
+
+    #[test]
+    fn dedup_removes_errors_with_identical_code_and_labels() {
+        let mut source_errors = SourceErrors::from(vec![
+            source_error(TestErrorCode::One, 0, 1..2, Severity::Error),
+            source_error(TestErrorCode::Two, 0, 1..2, Severity::Error),
+            source_error(TestErrorCode::One, 0, 3..4, Severity::Error),
+            source_error(TestErrorCode::One, 0, 1..2, Severity::Warning),
+        ]);
+
+        source_errors.dedup();
+
+        let codes_and_ranges = source_errors
+            .iter()
+            .map(|source_error| (source_error.code, source_error.detail.range.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (TestErrorCode::One, 1..2),
+                (TestErrorCode::Two, 1..2),
+                (TestErrorCode::One, 3..4),
+            ],
+            codes_and_ranges
+        );
+    }
+
+    #[test]
+    fn apply_policy_removes_allowed_and_overrides_severity() {
+        let mut source_errors = SourceErrors::from(vec![
+            source_error(TestErrorCode::One, 0, 1..2, Severity::Error),
+            source_error(TestErrorCode::Two, 0, 3..4, Severity::Warning),
+            source_error(TestErrorCode::One, 0, 5..6, Severity::Warning),
+        ]);
+        let severity_policy = SeverityPolicy::new()
+            .with_level(CodeSelector::code(TestErrorCode::One), LintLevel::Allow)
+            .with_level(CodeSelector::code(TestErrorCode::Two), LintLevel::Deny);
+
+        source_errors.apply_policy(&severity_policy);
+
+        let codes_and_severities = source_errors
+            .iter()
+            .map(|source_error| (source_error.code, source_error.severity))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![(TestErrorCode::Two, Severity::Error)],
+            codes_and_severities
+        );
+    }
+
+    #[test]
+    fn summary_pluralizes_counts() {
+        let source_errors = SourceErrors::from(vec![
+            source_error(TestErrorCode::One, 0, 1..2, Severity::Error),
+            source_error(TestErrorCode::One, 0, 3..4, Severity::Error),
+            source_error(TestErrorCode::Two, 0, 5..6, Severity::Error),
+            source_error(TestErrorCode::Two, 0, 7..8, Severity::Warning),
+        ]);
+
+        assert_eq!(
+            Some(String::from("3 errors, 1 warning emitted")),
+            source_errors.summary()
+        );
+    }
+
+    #[test]
+    fn summary_is_none_when_empty() {
+        let source_errors = SourceErrors::<'_, TestErrorCode, TestErrorDetail, _>::new();
+
+        assert_eq!(None, source_errors.summary());
+    }
+
+    #[test]
+    fn emit_all_renders_each_error_and_summary() -> Result<(), Box<dyn std::error::Error>> {
+        let mut files = SimpleFiles::new();
+        let file_id = files.add("path/to/file", "---\ncon: tent\n");
+        let source_errors = SourceErrors::from(vec![
+            source_error(TestErrorCode::One, file_id, 4..7, Severity::Error),
+            source_error(TestErrorCode::Two, file_id, 9..13, Severity::Warning),
+        ]);
+
+        let mut writer = NoColor::new(Vec::new());
+        source_errors.emit_all(&mut writer, &term::Config::default(), &files)?;
+
+        let output = String::from_utf8(writer.into_inner())?;
+        assert!(output.contains("error[E1]: one"));
+        assert!(output.contains("warning[E2]: two"));
+        assert!(output.ends_with("1 error, 1 warning emitted\n"));
+        Ok(())
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn to_json_serializes_all_errors() -> Result<(), Box<dyn std::error::Error>> {
+        let mut files = SimpleFiles::new();
+        let file_id = files.add("path/to/file", "---\ncon: tent\n");
+        let source_errors = SourceErrors::from(vec![
+            source_error(TestErrorCode::One, file_id, 4..7, Severity::Error),
+            source_error(TestErrorCode::Two, file_id, 9..13, Severity::Warning),
+        ]);
+
+        let json = source_errors.to_json(&files)?;
+
+        let json_report = serde_json::from_str::<crate::json::JsonReport>(&json)?;
+        assert_eq!(crate::json::JsonReport::VERSION, json_report.version);
+        let codes = json_report
+            .diagnostics
+            .iter()
+            .map(|json_diagnostic| json_diagnostic.code.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["E1", "E2"], codes);
+        Ok(())
+    }
+
+    fn source_error(
+        code: TestErrorCode,
+        file_id: usize,
+        range: Range<usize>,
+        severity: Severity,
+    ) -> TestSourceError {
+        SourceError::new(code, TestErrorDetail { file_id, range }, severity)
+    }
+
+    /// Error codes for test.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub enum TestErrorCode {
+        One,
+        Two,
+    }
+
+    impl ErrorCode for TestErrorCode {
+        const ERROR_CODE_MAX: usize = 2;
+
+        fn code(self) -> usize {
+            match self {
+                Self::One => 1,
+                Self::Two => 2,
+            }
+        }
+
+        fn description(self) -> &'static str {
+            match self {
+                Self::One => "one",
+                Self::Two => "two",
+            }
+        }
+    }
+
+    /// Error detail for test.
+    #[derive(Debug)]
+    pub struct TestErrorDetail {
+        /// ID of the file containing the error.
+        pub file_id: usize,
+        /// Byte begin and end indices of the error.
+        pub range: Range<usize>,
+    }
+
+    impl<'files> ErrorDetail<'files> for TestErrorDetail {
+        type Files = SimpleFiles<&'files str, &'files str>;
+
+        fn labels(&self) -> Vec<Label<usize>> {
+            vec![Label::primary(self.file_id, self.range.clone())]
+        }
+
+        fn notes(&self, _files: &Self::Files) -> Vec<String> {
+            Vec::new()
+        }
+    }
+}
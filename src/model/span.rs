@@ -0,0 +1,26 @@
+use std::ops::Range;
+
+/// Byte range within a file.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Span<FileId> {
+    /// ID of the file the range is within.
+    pub file_id: FileId,
+    /// Byte begin and end indices within the file's source.
+    pub range: Range<usize>,
+}
+
+impl<FileId> Span<FileId> {
+    /// Returns a new `Span`.
+    ///
+    /// # Parameters
+    ///
+    /// * `file_id`: ID of the file the range is within.
+    /// * `range`: Byte begin and end indices within the file's source.
+    pub fn new(file_id: FileId, range: impl Into<Range<usize>>) -> Self {
+        Self {
+            file_id,
+            range: range.into(),
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use std::ops::Range;
+
+use codespan_reporting::files::{self, Files};
+
+use crate::model::{Applicability, Span};
+
+/// Replacement text for part of a file, which fixes an error.
+///
+/// Suggestions are rendered as a `help:` note with a diff of the affected
+/// lines:
+///
+/// ```text
+///   = help: use one of the `available` values
+///     6 - chosen: "ghi"
+///     6 + chosen: "abc"
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Suggestion<FileId> {
+    /// Part of the file to replace.
+    pub span: Span<FileId>,
+    /// Text to replace the span with.
+    pub replacement: String,
+    /// How confident the suggestion is that the replacement fixes the error.
+    pub applicability: Applicability,
+    /// Message to render with the suggestion, may be empty.
+    pub message: String,
+}
+
+impl<FileId> Suggestion<FileId> {
+    /// Returns a new `Suggestion` without a message.
+    ///
+    /// # Parameters
+    ///
+    /// * `span`: Part of the file to replace.
+    /// * `replacement`: Text to replace the span with.
+    /// * `applicability`: How confident the suggestion is that the replacement
+    ///   fixes the error.
+    pub fn new(
+        span: Span<FileId>,
+        replacement: impl Into<String>,
+        applicability: Applicability,
+    ) -> Self {
+        Self {
+            span,
+            replacement: replacement.into(),
+            applicability,
+            message: String::new(),
+        }
+    }
+
+    /// Sets the message to render with the suggestion.
+    #[must_use]
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = message.into();
+        self
+    }
+
+    /// Returns the message followed by a diff of the affected lines, to render
+    /// as a [`Trailer::Help`].
+    ///
+    /// The message defaults to `"try"` when empty.
+    ///
+    /// [`Trailer::Help`]: crate::model::Trailer::Help
+    pub fn help<'files, Fs>(&self, files: &'files Fs) -> Result<String, files::Error>
+    where
+        Fs: Files<'files, FileId = FileId>,
+        FileId: Copy,
+    {
+        let message = if self.message.is_empty() {
+            "try"
+        } else {
+            self.message.as_str()
+        };
+
+        Ok(format!("{}\n{}", message, self.diff(files)?))
+    }
+
+    /// Returns the affected lines before and after the replacement, prefixed
+    /// with their line numbers and `-` or `+`.
+    pub fn diff<'files, Fs>(&self, files: &'files Fs) -> Result<String, files::Error>
+    where
+        Fs: Files<'files, FileId = FileId>,
+        FileId: Copy,
+    {
+        let Span { file_id, range } = &self.span;
+        let file_id = *file_id;
+        let source = files.source(file_id)?;
+        let source = source.as_ref();
+        let line_index_first = files.line_index(file_id, range.start)?;
+        let line_index_last = files.line_index(file_id, range_end_byte(range))?;
+        let lines_range = files.line_range(file_id, line_index_first)?.start
+            ..files.line_range(file_id, line_index_last)?.end;
+
+        let lines_before = source
+            .get(lines_range.clone())
+            .ok_or(files::Error::IndexTooLarge {
+                given: lines_range.end,
+                max: source.len(),
+            })?;
+        let lines_after = source
+            .get(lines_range.start..range.start)
+            .and_then(|prefix| {
+                source
+                    .get(range.end..lines_range.end)
+                    .map(|suffix| format!("{}{}{}", prefix, self.replacement, suffix))
+            })
+            .ok_or(files::Error::IndexTooLarge {
+                given: range.end,
+                max: source.len(),
+            })?;
+
+        let line_number_first = line_index_first + 1;
+        let line_number_last = line_number_first
+            + lines_before
+                .lines()
+                .count()
+                .max(lines_after.lines().count())
+                .saturating_sub(1);
+        let width = line_number_last.to_string().len();
+
+        let mut diff = String::new();
+        [("-", lines_before), ("+", lines_after.as_str())]
+            .iter()
+            .for_each(|(marker, lines)| {
+                lines.lines().enumerate().for_each(|(index, line)| {
+                    if !diff.is_empty() {
+                        diff.push('\n');
+                    }
+                    diff.push_str(&format!(
+                        "{:>width$} {} {}",
+                        line_number_first + index,
+                        marker,
+                        line,
+                        width = width
+                    ));
+                });
+            });
+
+        Ok(diff)
+    }
+}
+
+/// Returns the byte index of the last character in the range, so that a range
+/// ending with a line's `\n` does not include the next line.
+fn range_end_byte(range: &Range<usize>) -> usize {
+    if range.end > range.start {
+        range.end - 1
+    } else {
+        range.start
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use codespan_reporting::files::SimpleFiles;
+
+    use super::Suggestion;
+    use crate::model::{Applicability, Span};
+
+    #[test]
+    fn help_renders_diff_of_affected_lines() -> Result<(), Box<dyn std::error::Error>> {
+        let mut files = SimpleFiles::new();
+        let file_id = files.add("config.yaml", "available: [\"abc\"]\nchosen: \"ghi\"\n");
+        let suggestion = Suggestion::new(
+            Span::new(file_id, 27..32),
+            "\"abc\"",
+            Applicability::MachineApplicable,
+        )
+        .with_message("use one of the `available` values");
+
+        let expected = [
+            "use one of the `available` values",
+            "2 - chosen: \"ghi\"",
+            "2 + chosen: \"abc\"",
+        ]
+        .join("\n");
+        assert_eq!(expected, suggestion.help(&files)?);
+        Ok(())
+    }
+
+    #[test]
+    fn diff_spans_multiple_lines_and_pads_line_numbers() -> Result<(), Box<dyn std::error::Error>> {
+        let mut files = SimpleFiles::new();
+        let source = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\nk\n";
+        let file_id = files.add("letters.txt", source);
+        let suggestion = Suggestion::new(
+            Span::new(file_id, 16..22),
+            "ij",
+            Applicability::MaybeIncorrect,
+        );
+
+        let expected = [" 9 - i", "10 - j", "11 - k", " 9 + ij"].join("\n");
+        assert_eq!(expected, suggestion.diff(&files)?);
+        assert!(suggestion.help(&files)?.starts_with("try\n"));
+        Ok(())
+    }
+}
@@ -0,0 +1,89 @@
+use std::fmt;
+
+/// Message displayed beneath the error snippets.
+///
+/// Each kind is rendered with its own prefix in text output, similar to
+/// `rustc`, and kept as a separate field in structured output. Notes are
+/// rendered without a prefix, so notes returned by [`ErrorDetail::notes`]
+/// render as they did before trailers were introduced.
+///
+/// ```text
+///   = `chosen` value must come from one of `available` values
+///   = help: use one of `"abc"`, `"def"`
+///   = warning: `available` is also used by the `fallback` value
+///   = see also: https://example.com/config#chosen
+/// ```
+///
+/// [`ErrorDetail::notes`]: crate::ErrorDetail::notes
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Trailer {
+    /// Context about why the error occurred.
+    Note(String),
+    /// Advice on how to fix the error.
+    Help(String),
+    /// Context about a related hazard, even once the error is fixed.
+    Warning(String),
+    /// Link to further documentation about the error.
+    SeeAlso(String),
+}
+
+impl Trailer {
+    /// Returns the prefix rendered before the message, or `None` for notes.
+    pub fn prefix(&self) -> Option<&'static str> {
+        match self {
+            Self::Note(_) => None,
+            Self::Help(_) => Some("help"),
+            Self::Warning(_) => Some("warning"),
+            Self::SeeAlso(_) => Some("see also"),
+        }
+    }
+
+    /// Returns the message, or link for [`Trailer::SeeAlso`].
+    pub fn message(&self) -> &str {
+        match self {
+            Self::Note(message)
+            | Self::Help(message)
+            | Self::Warning(message)
+            | Self::SeeAlso(message) => message,
+        }
+    }
+}
+
+impl fmt::Display for Trailer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.prefix() {
+            Some(prefix) => write!(f, "{}: {}", prefix, self.message()),
+            None => f.write_str(self.message()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Trailer;
+
+    #[test]
+    fn display_prefixes_all_but_notes() {
+        let trailers = [
+            Trailer::Note(String::from("`chosen` must be available")),
+            Trailer::Help(String::from("use `abc`")),
+            Trailer::Warning(String::from("`abc` is deprecated")),
+            Trailer::SeeAlso(String::from("https://example.com/E1")),
+        ];
+
+        assert_eq!(
+            vec![
+                "`chosen` must be available",
+                "help: use `abc`",
+                "warning: `abc` is deprecated",
+                "see also: https://example.com/E1",
+            ],
+            trailers
+                .iter()
+                .map(Trailer::to_string)
+                .collect::<Vec<String>>()
+        );
+    }
+}
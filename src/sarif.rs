@@ -0,0 +1,10 @@
+//! SARIF 2.1.0 export of source errors.
+//!
+//! This is enabled by the `"sarif"` feature, so that errors can be uploaded to
+//! code scanning tools that ingest [SARIF].
+//!
+//! [SARIF]: https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html
+
+pub use self::sarif_exporter::SarifExporter;
+
+mod sarif_exporter;
@@ -0,0 +1,519 @@
+use codespan_reporting::{
+    diagnostic::Severity,
+    files::{self, Files},
+};
+use serde_json::{json, Map, Value};
+
+use crate::{
+    fmt::Code,
+    model::{ErrorCode, ErrorDetail, Label, LabelStyle, LineColumn, SourceErrors, Suggestion},
+};
+
+/// Exports source errors as a SARIF 2.1.0 log.
+///
+/// * Each distinct [`ErrorCode`] becomes a `rule`, with its `id` from
+///   [`Code::string`] and `shortDescription` from [`ErrorCode::description`].
+/// * Each [`SourceError`] becomes a `result`, with its `level` derived from the
+///   [`Severity`].
+/// * Primary labels become `locations`, and secondary labels become
+///   `relatedLocations`.
+/// * Suggestions become `fixes`, each replacing a byte region of a file.
+///
+/// Columns in regions count Unicode code points, as declared by the run's
+/// `columnKind`.
+///
+/// [`SourceError`]: crate::SourceError
+#[derive(Clone, Debug)]
+pub struct SarifExporter {
+    /// Name of the tool that produced the errors.
+    tool_name: String,
+    /// Version of the tool that produced the errors.
+    tool_version: Option<String>,
+    /// URI of the tool's documentation.
+    information_uri: Option<String>,
+}
+
+impl SarifExporter {
+    /// URI of the SARIF 2.1.0 JSON schema.
+    pub const SCHEMA: &'static str = "https://json.schemastore.org/sarif-2.1.0.json";
+    /// Version of SARIF produced by this exporter.
+    pub const VERSION: &'static str = "2.1.0";
+
+    /// Returns a new `SarifExporter`.
+    ///
+    /// # Parameters
+    ///
+    /// * `tool_name`: Name of the tool that produced the errors.
+    pub fn new(tool_name: impl Into<String>) -> Self {
+        Self {
+            tool_name: tool_name.into(),
+            tool_version: None,
+            information_uri: None,
+        }
+    }
+
+    /// Sets the version of the tool that produced the errors.
+    #[must_use]
+    pub fn with_tool_version(mut self, tool_version: impl Into<String>) -> Self {
+        self.tool_version = Some(tool_version.into());
+        self
+    }
+
+    /// Sets the URI of the tool's documentation.
+    #[must_use]
+    pub fn with_information_uri(mut self, information_uri: impl Into<String>) -> Self {
+        self.information_uri = Some(information_uri.into());
+        self
+    }
+
+    /// Returns the SARIF log as a JSON string.
+    ///
+    /// # Parameters
+    ///
+    /// * `files`: Files that the errors refer to.
+    /// * `source_errors`: The errors to export.
+    pub fn to_string<'files, Ec, Ed, Fs>(
+        &self,
+        files: &'files Fs,
+        source_errors: &SourceErrors<'files, Ec, Ed, Fs>,
+    ) -> Result<String, files::Error>
+    where
+        Ec: ErrorCode,
+        Ed: ErrorDetail<'files, Files = Fs>,
+        Fs: Files<'files>,
+    {
+        self.to_value(files, source_errors)
+            .map(|sarif_log| sarif_log.to_string())
+    }
+
+    /// Returns the SARIF log as a JSON value.
+    ///
+    /// # Parameters
+    ///
+    /// * `files`: Files that the errors refer to.
+    /// * `source_errors`: The errors to export.
+    pub fn to_value<'files, Ec, Ed, Fs>(
+        &self,
+        files: &'files Fs,
+        source_errors: &SourceErrors<'files, Ec, Ed, Fs>,
+    ) -> Result<Value, files::Error>
+    where
+        Ec: ErrorCode,
+        Ed: ErrorDetail<'files, Files = Fs>,
+        Fs: Files<'files>,
+    {
+        let mut codes = Vec::<Ec>::new();
+        let results = source_errors
+            .iter()
+            .map(|source_error| {
+                let rule_index = match codes.iter().position(|code| *code == source_error.code) {
+                    Some(rule_index) => rule_index,
+                    None => {
+                        codes.push(source_error.code);
+                        codes.len() - 1
+                    }
+                };
+
+                let labels = source_error.detail.labels();
+                let locations = Self::locations(files, &labels, LabelStyle::Primary)?;
+                let related_locations = Self::locations(files, &labels, LabelStyle::Secondary)?;
+
+                let mut message = String::from(source_error.code.description());
+                source_error
+                    .detail
+                    .trailers(files)
+                    .iter()
+                    .for_each(|trailer| {
+                        message.push('\n');
+                        message.push_str(&trailer.to_string());
+                    });
+
+                let mut result = json!({
+                    "ruleId": Code::string(source_error.code),
+                    "ruleIndex": rule_index,
+                    "level": level(source_error.severity),
+                    "message": { "text": message },
+                    "locations": locations,
+                });
+                if !related_locations.is_empty() {
+                    result["relatedLocations"] = Value::Array(related_locations);
+                }
+                let fixes = Self::fixes(files, &source_error.detail.suggestions())?;
+                if !fixes.is_empty() {
+                    result["fixes"] = Value::Array(fixes);
+                }
+
+                Ok(result)
+            })
+            .collect::<Result<Vec<Value>, files::Error>>()?;
+
+        let rules = codes
+            .iter()
+            .map(|code| {
+                json!({
+                    "id": Code::string(*code),
+                    "shortDescription": { "text": code.description() },
+                })
+            })
+            .collect::<Vec<Value>>();
+
+        let mut driver = Map::new();
+        driver.insert(String::from("name"), Value::from(self.tool_name.as_str()));
+        if let Some(tool_version) = self.tool_version.as_ref() {
+            driver.insert(String::from("version"), Value::from(tool_version.as_str()));
+        }
+        if let Some(information_uri) = self.information_uri.as_ref() {
+            driver.insert(
+                String::from("informationUri"),
+                Value::from(information_uri.as_str()),
+            );
+        }
+        driver.insert(String::from("rules"), Value::Array(rules));
+
+        Ok(json!({
+            "$schema": Self::SCHEMA,
+            "version": Self::VERSION,
+            "runs": [{
+                "tool": { "driver": driver },
+                "columnKind": "unicodeCodePoints",
+                "results": results,
+            }],
+        }))
+    }
+
+    /// Returns SARIF `location`s for labels with the given style.
+    fn locations<'files, Fs>(
+        files: &'files Fs,
+        labels: &[Label<Fs::FileId>],
+        label_style: LabelStyle,
+    ) -> Result<Vec<Value>, files::Error>
+    where
+        Fs: Files<'files>,
+    {
+        labels
+            .iter()
+            .filter(|label| label.style == label_style)
+            .enumerate()
+            .map(|(index, label)| {
+                let start = LineColumn::new(files, label.span.file_id, label.span.range.start)?;
+                let end = LineColumn::new(files, label.span.file_id, label.span.range.end)?;
+                let uri = files
+                    .name(label.span.file_id)?
+                    .to_string()
+                    .replace('\\', "/");
+
+                let mut location = json!({
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": uri },
+                        "region": {
+                            "startLine": start.line,
+                            "startColumn": start.column,
+                            "endLine": end.line,
+                            "endColumn": end.column,
+                            "byteOffset": label.span.range.start,
+                            "byteLength": label.span.range.end - label.span.range.start,
+                        },
+                    },
+                });
+                if label_style == LabelStyle::Secondary {
+                    location["id"] = Value::from(index);
+                }
+                if !label.message.is_empty() {
+                    location["message"] = json!({ "text": label.message });
+                }
+
+                Ok(location)
+            })
+            .collect()
+    }
+
+    /// Returns the SARIF `fixes` for the suggestions.
+    fn fixes<'files, Fs>(
+        files: &'files Fs,
+        suggestions: &[Suggestion<Fs::FileId>],
+    ) -> Result<Vec<Value>, files::Error>
+    where
+        Fs: Files<'files>,
+    {
+        suggestions
+            .iter()
+            .map(|suggestion| {
+                let uri = files
+                    .name(suggestion.span.file_id)?
+                    .to_string()
+                    .replace('\\', "/");
+                let range = &suggestion.span.range;
+
+                let mut fix = json!({
+                    "artifactChanges": [{
+                        "artifactLocation": { "uri": uri },
+                        "replacements": [{
+                            "deletedRegion": {
+                                "byteOffset": range.start,
+                                "byteLength": range.end - range.start,
+                            },
+                            "insertedContent": { "text": suggestion.replacement },
+                        }],
+                    }],
+                });
+                if !suggestion.message.is_empty() {
+                    fix["description"] = json!({ "text": suggestion.message });
+                }
+
+                Ok(fix)
+            })
+            .collect()
+    }
+}
+
+/// Returns the SARIF `level` for the severity.
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Bug | Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note | Severity::Help => "note",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use codespan_reporting::diagnostic::Severity;
+    use serde_json::json;
+
+    use super::SarifExporter;
+    use crate::{
+        model::{Applicability, Span, Suggestion},
+        test_support::{self, TestSourceError},
+        SourceErrors,
+    };
+
+    #[test]
+    fn to_value_maps_codes_to_rules_and_errors_to_results() -> Result<(), Box<dyn std::error::Error>>
+    {
+        let (files, file_id) = test_support::files();
+        let source_errors = SourceErrors::from(vec![
+            test_support::source_error_invalid_value(file_id, Severity::Error),
+            test_support::source_error_invalid_value(file_id, Severity::Help),
+        ]);
+
+        let sarif_log = SarifExporter::new("srcerr-test")
+            .with_tool_version("1.2.3")
+            .with_information_uri("https://example.com/errors")
+            .to_value(&files, &source_errors)?;
+
+        let result = |level: &str| {
+            json!({
+                "ruleId": "E1",
+                "ruleIndex": 0,
+                "level": level,
+                "message": {
+                    "text": "`chosen` value is invalid.\n\
+                        `chosen` value must come from one of `available` values",
+                },
+                "locations": [{
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": "examples/source_ref_hint.yaml" },
+                        "region": {
+                            "startLine": 6,
+                            "startColumn": 9,
+                            "endLine": 6,
+                            "endColumn": 14,
+                            "byteOffset": 44,
+                            "byteLength": 5,
+                        },
+                    },
+                    "message": { "text": "invalid value specified" },
+                }],
+                "relatedLocations": [{
+                    "id": 0,
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": "examples/source_ref_hint.yaml" },
+                        "region": {
+                            "startLine": 2,
+                            "startColumn": 1,
+                            "endLine": 4,
+                            "endColumn": 10,
+                            "byteOffset": 4,
+                            "byteLength": 30,
+                        },
+                    },
+                    "message": { "text": "defined here" },
+                }],
+            })
+        };
+        assert_eq!(
+            json!({
+                "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+                "version": "2.1.0",
+                "runs": [{
+                    "tool": {
+                        "driver": {
+                            "name": "srcerr-test",
+                            "version": "1.2.3",
+                            "informationUri": "https://example.com/errors",
+                            "rules": [{
+                                "id": "E1",
+                                "shortDescription": { "text": "`chosen` value is invalid." },
+                            }],
+                        },
+                    },
+                    "columnKind": "unicodeCodePoints",
+                    "results": [result("error"), result("note")],
+                }],
+            }),
+            sarif_log
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn to_value_maps_severity_to_level() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_errors = [
+            Severity::Bug,
+            Severity::Error,
+            Severity::Warning,
+            Severity::Note,
+            Severity::Help,
+        ]
+        .iter()
+        .map(|severity| test_support::source_error_invalid_value(file_id, *severity))
+        .collect::<SourceErrors<'_, _, _, _>>();
+
+        let sarif_log = SarifExporter::new("srcerr-test").to_value(&files, &source_errors)?;
+
+        let levels = sarif_log["runs"][0]["results"]
+            .as_array()
+            .map(|results| {
+                results
+                    .iter()
+                    .map(|result| result["level"].clone())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        assert_eq!(
+            vec![
+                json!("error"),
+                json!("error"),
+                json!("warning"),
+                json!("note"),
+                json!("note"),
+            ],
+            levels
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn to_value_maps_secondary_labels_to_related_locations_when_none_are_primary(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_errors = SourceErrors::from(vec![
+            test_support::source_error_secondary_only(file_id, Severity::Error),
+            test_support::source_error_unlabelled(file_id, Severity::Error),
+        ]);
+
+        let sarif_log = SarifExporter::new("srcerr-test").to_value(&files, &source_errors)?;
+
+        let results = &sarif_log["runs"][0]["results"];
+        assert_eq!(json!([]), results[0]["locations"]);
+        assert_eq!(
+            vec![json!(44), json!(4)],
+            results[0]["relatedLocations"]
+                .as_array()
+                .map(|related_locations| {
+                    related_locations
+                        .iter()
+                        .map(|location| {
+                            location["physicalLocation"]["region"]["byteOffset"].clone()
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        );
+        assert_eq!(json!([]), results[1]["locations"]);
+        assert_eq!(None, results[1].get("relatedLocations"));
+        assert_eq!(
+            json!(
+                "`chosen` value is invalid.\n\
+                `chosen` value must come from one of `available` values"
+            ),
+            results[1]["message"]["text"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn to_value_clamps_lines_of_label_past_end_of_file() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let source_errors = SourceErrors::from(vec![test_support::source_error_range_invalid(
+            file_id,
+            Severity::Error,
+        )]);
+
+        let sarif_log = SarifExporter::new("srcerr-test").to_value(&files, &source_errors)?;
+
+        assert_eq!(
+            json!({
+                "startLine": 7,
+                "startColumn": 1,
+                "endLine": 7,
+                "endColumn": 1,
+                "byteOffset": 395,
+                "byteLength": 5,
+            }),
+            sarif_log["runs"][0]["results"][0]["locations"][0]["physicalLocation"]["region"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn to_value_maps_suggestions_to_fixes() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, file_id) = test_support::files();
+        let mut source_error = test_support::source_error_invalid_value(file_id, Severity::Error);
+        source_error.detail.suggestions.push(
+            Suggestion::new(
+                Span::new(file_id, 44..49),
+                "\"abc\"",
+                Applicability::MachineApplicable,
+            )
+            .with_message("use `abc`"),
+        );
+        let source_errors = SourceErrors::from(vec![source_error]);
+
+        let sarif_log = SarifExporter::new("srcerr-test").to_value(&files, &source_errors)?;
+
+        assert_eq!(
+            json!([{
+                "description": { "text": "use `abc`" },
+                "artifactChanges": [{
+                    "artifactLocation": { "uri": "examples/source_ref_hint.yaml" },
+                    "replacements": [{
+                        "deletedRegion": { "byteOffset": 44, "byteLength": 5 },
+                        "insertedContent": { "text": "\"abc\"" },
+                    }],
+                }],
+            }]),
+            sarif_log["runs"][0]["results"][0]["fixes"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn to_string_serializes_empty_run() -> Result<(), Box<dyn std::error::Error>> {
+        let (files, _file_id) = test_support::files();
+        let source_errors = SourceErrors::from(Vec::<TestSourceError>::new());
+
+        let sarif_log = SarifExporter::new("srcerr-test").to_string(&files, &source_errors)?;
+
+        assert_eq!(
+            concat!(
+                r#"{"$schema":"https://json.schemastore.org/sarif-2.1.0.json","#,
+                r#""runs":[{"columnKind":"unicodeCodePoints","results":[],"#,
+                r#""tool":{"driver":{"name":"srcerr-test","rules":[]}}}],"version":"2.1.0"}"#
+            ),
+            sarif_log
+        );
+        Ok(())
+    }
+}
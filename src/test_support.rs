@@ -0,0 +1,153 @@
+//! Error types shared by tests.
+
+use codespan_reporting::{diagnostic::Severity, files::SimpleFiles};
+
+use crate::{
+    model::{Suggestion, Trailer},
+    ErrorCode, ErrorDetail, Label, SourceError,
+};
+
+/// Content of the file that test errors refer to.
+pub(crate) const SOURCE_REF_HINT_YAML: &str = include_str!("../examples/source_ref_hint.yaml");
+
+/// Files type used in tests.
+pub(crate) type TestFiles = SimpleFiles<&'static str, &'static str>;
+
+/// Source error type used in tests.
+pub(crate) type TestSourceError = SourceError<'static, TestErrorCode, TestErrorDetail, TestFiles>;
+
+/// Returns files containing `examples/source_ref_hint.yaml`, and its file ID.
+pub(crate) fn files() -> (TestFiles, usize) {
+    let mut files = SimpleFiles::new();
+    let file_id = files.add("examples/source_ref_hint.yaml", SOURCE_REF_HINT_YAML);
+    (files, file_id)
+}
+
+/// Returns an error with a primary and secondary label, and a note.
+pub(crate) fn source_error_invalid_value(file_id: usize, severity: Severity) -> TestSourceError {
+    let detail = TestErrorDetail {
+        labels: vec![
+            Label::primary(file_id, 44..49).with_message("invalid value specified"),
+            Label::secondary(file_id, 4..34).with_message("defined here"),
+        ],
+        trailers: vec![Trailer::Note(String::from(
+            "`chosen` value must come from one of `available` values",
+        ))],
+        suggestions: Vec::new(),
+    };
+    SourceError::new(TestErrorCode::InvalidValue, detail, severity)
+}
+
+/// Returns an error whose labels are all secondary.
+pub(crate) fn source_error_secondary_only(file_id: usize, severity: Severity) -> TestSourceError {
+    let mut source_error = source_error_invalid_value(file_id, severity);
+    source_error.detail.labels = vec![
+        Label::secondary(file_id, 44..49).with_message("invalid value specified"),
+        Label::secondary(file_id, 4..34).with_message("defined here"),
+    ];
+    source_error
+}
+
+/// Returns an error without labels.
+pub(crate) fn source_error_unlabelled(file_id: usize, severity: Severity) -> TestSourceError {
+    let mut source_error = source_error_invalid_value(file_id, severity);
+    source_error.detail.labels = Vec::new();
+    source_error
+}
+
+/// Returns an error whose primary label is past the end of the file.
+pub(crate) fn source_error_range_invalid(file_id: usize, severity: Severity) -> TestSourceError {
+    let mut source_error = source_error_invalid_value(file_id, severity);
+    source_error.detail.labels =
+        vec![Label::primary(file_id, 395..400).with_message("invalid value specified")];
+    source_error
+}
+
+/// Error codes for tests.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum TestErrorCode {
+    /// Error when a value is not one of the available values.
+    InvalidValue,
+}
+
+impl ErrorCode for TestErrorCode {
+    const ERROR_CODE_MAX: usize = 1;
+
+    fn code(self) -> usize {
+        match self {
+            Self::InvalidValue => 1,
+        }
+    }
+
+    fn description(self) -> &'static str {
+        match self {
+            Self::InvalidValue => "`chosen` value is invalid.",
+        }
+    }
+
+    fn explanation(self) -> Option<&'static str> {
+        match self {
+            Self::InvalidValue => Some(
+                "The `chosen` value is not one of the `available` values.\n\
+                \n\
+                Erroneous example:\n\
+                \n\
+                ```yaml\n\
+                available:\n\
+                \x20 - \"abc\"\n\
+                chosen: \"ghi\"\n\
+                ```\n",
+            ),
+        }
+    }
+
+    fn all() -> &'static [Self] {
+        &[Self::InvalidValue]
+    }
+}
+
+/// Error code that does not implement `ErrorCode::all`.
+#[cfg(any(feature = "error_index", feature = "i18n"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct UnlistedErrorCode;
+
+#[cfg(any(feature = "error_index", feature = "i18n"))]
+impl ErrorCode for UnlistedErrorCode {
+    const ERROR_CODE_MAX: usize = 1;
+
+    fn code(self) -> usize {
+        1
+    }
+
+    fn description(self) -> &'static str {
+        "Unlisted error."
+    }
+}
+
+/// Error detail for tests, holding the labels, trailers, and suggestions to
+/// return.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct TestErrorDetail {
+    /// Labels to return.
+    pub(crate) labels: Vec<Label<usize>>,
+    /// Trailers to return.
+    pub(crate) trailers: Vec<Trailer>,
+    /// Suggestions to return.
+    pub(crate) suggestions: Vec<Suggestion<usize>>,
+}
+
+impl<'files> ErrorDetail<'files> for TestErrorDetail {
+    type Files = SimpleFiles<&'files str, &'files str>;
+
+    fn labels(&self) -> Vec<Label<usize>> {
+        self.labels.clone()
+    }
+
+    fn trailers(&self, _files: &Self::Files) -> Vec<Trailer> {
+        self.trailers.clone()
+    }
+
+    fn suggestions(&self) -> Vec<Suggestion<usize>> {
+        self.suggestions.clone()
+    }
+}